@@ -0,0 +1,128 @@
+use crate::cli::github_release::{fetch_release_for, github_client_for};
+use crate::cli::output;
+use crate::cli::result::HandlerResult;
+use crate::cli::root_command::LatestStrategy;
+use crate::cli::table::table;
+use crate::github::release::{Asset, ReleaseFilter, Tag};
+use crate::github::repository::Repository;
+use indicatif::HumanBytes;
+use serde::Serialize;
+
+pub struct AssetsHandler {
+    repository: Repository,
+    tag: Option<Tag>,
+    json: bool,
+}
+
+/// Item of `dra assets --json`
+#[derive(Debug, Serialize, PartialEq)]
+struct AssetItem {
+    name: String,
+    size: Option<u64>,
+    content_type: Option<String>,
+    download_count: Option<u64>,
+    download_url: String,
+}
+
+impl AssetsHandler {
+    pub fn new(repository: Repository, tag: Option<String>, json: bool) -> Self {
+        Self {
+            tag: tag.or(repository.tag.clone()).map(Tag),
+            repository,
+            json,
+        }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        if self.json {
+            output::reserve_stdout();
+        }
+        let github = github_client_for(&self.repository);
+        let release = fetch_release_for(
+            &github,
+            &self.repository,
+            self.tag.as_ref(),
+            ReleaseFilter::default(),
+            LatestStrategy::default(),
+        )?;
+        let items = release
+            .assets
+            .iter()
+            .filter(|x| !x.is_source_code())
+            .map(AssetItem::from)
+            .collect::<Vec<_>>();
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&items).unwrap_or_default()
+            );
+        } else {
+            let header = ["NAME", "SIZE", "CONTENT-TYPE", "DOWNLOADS"];
+            print!("{}", table(header, &rows(&items)));
+        }
+        Ok(())
+    }
+}
+
+impl From<&Asset> for AssetItem {
+    fn from(asset: &Asset) -> Self {
+        Self {
+            name: asset.name.clone(),
+            size: asset.size,
+            content_type: asset.content_type.clone(),
+            download_count: asset.download_count,
+            download_url: asset.download_url.clone(),
+        }
+    }
+}
+
+/// Values unknown to the forge are shown as `-`
+fn rows(items: &[AssetItem]) -> Vec<[String; 4]> {
+    let or_dash = |x: Option<String>| x.unwrap_or_else(|| "-".to_string());
+    items
+        .iter()
+        .map(|x| {
+            [
+                x.name.clone(),
+                or_dash(x.size.map(|size| HumanBytes(size).to_string())),
+                or_dash(x.content_type.clone()),
+                or_dash(x.download_count.map(|count| count.to_string())),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asset_rows() {
+        let items = vec![
+            AssetItem {
+                name: "helloworld.tar.gz".to_string(),
+                size: Some(2048),
+                content_type: Some("application/gzip".to_string()),
+                download_count: Some(42),
+                download_url: "https://example.com/helloworld.tar.gz".to_string(),
+            },
+            AssetItem {
+                name: "helloworld.deb".to_string(),
+                size: None,
+                content_type: None,
+                download_count: None,
+                download_url: "https://example.com/helloworld.deb".to_string(),
+            },
+        ];
+
+        let result = rows(&items);
+
+        assert_eq!(
+            vec![
+                ["helloworld.tar.gz", "2.00 KiB", "application/gzip", "42"].map(String::from),
+                ["helloworld.deb", "-", "-", "-"].map(String::from),
+            ],
+            result
+        );
+    }
+}
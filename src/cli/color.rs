@@ -24,6 +24,12 @@ impl<'a> Color<'a> {
         }
     }
 
+    pub fn yellow(self) -> Self {
+        Color {
+            inner: self.inner.yellow(),
+        }
+    }
+
     pub fn green(self) -> Self {
         Color {
             inner: self.inner.green(),
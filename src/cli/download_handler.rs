@@ -1,44 +1,178 @@
 use crate::cli::color::Color;
-use crate::cli::github_release::fetch_release_for;
-use crate::cli::progress_bar::ProgressBar;
+use crate::cli::download_report::{ContentHasher, DownloadReport, DownloadedAsset};
+use crate::cli::git_remote::{self, DEFAULT_REMOTE};
+use crate::cli::github_release::{
+    fetch_release_for, fetch_release_graphql, fetch_releases_for, releases_in_range,
+};
+use crate::cli::output::{self, message};
+use crate::cli::progress_bar::{Phase, ProgressBar, ProgressEvents, ProgressFileWriter};
+use crate::cli::rate_limit::RateLimiter;
 use crate::cli::result::{HandlerError, HandlerResult};
+use crate::cli::root_command::{
+    DownloadArgs, IfExists, LatestStrategy, ProgressFormat, SourceArchive,
+};
 use crate::cli::select_assets;
 use crate::cli::spinner::Spinner;
-use crate::github::client::GithubClient;
+use crate::config::selection_state::SelectionState;
+use crate::config::weights::{WeightFile, WeightFileLoader};
+use crate::config::{
+    Config, DRA_CA_BUNDLE, DRA_PREFER_LIBC, DRA_TIMEOUT, bin_dir, find_by_repository,
+};
+use crate::github::asset_cache::AssetCache;
+use crate::github::client::{DownloadStream, GithubClient, resolve_api_url};
 use crate::github::error::GithubError;
-use crate::github::release::{Asset, Release, Tag};
+use crate::github::license;
+use crate::github::metadata_cache::MetadataCache;
+use crate::github::proxy::proxy_from_url;
+use crate::github::release::{
+    Asset, Release, ReleaseFilter, SOURCE_CODE_TAR_GZ, SOURCE_CODE_ZIP, Tag,
+};
 use crate::github::repository::Repository;
+use crate::github::retry::RetryPolicy;
 use crate::github::tagged_asset::TaggedAsset;
+use crate::github::tls::tls_config;
 use crate::installer::destination::Destination;
-use crate::installer::executable::Executable;
-use crate::installer::install;
-use crate::{system, vector};
+use crate::installer::executable::{Executable, set_executable_permissions};
+use crate::installer::options::InstallOptions;
+use crate::installer::{create_symlink, install};
+use crate::system::Libc;
+use crate::{env_var, system, vector};
+use indicatif::{HumanBytes, MultiProgress};
+use itertools::Itertools;
+use log::{debug, info, warn};
+use std::ffi::OsString;
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::{ErrorKind, IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
 
 pub struct DownloadHandler {
     repository: Repository,
+    config: Config,
     mode: DownloadMode,
     tag: Option<Tag>,
     output: Option<PathBuf>,
     install: Install,
+    install_options: InstallOptions,
+    overwrite_if_older: bool,
+    create_symlink: Option<PathBuf>,
+    compress_meta: bool,
+    progress_to_file: Option<PathBuf>,
+    progress: ProgressFormat,
+    limit_rate: Option<u64>,
+    connections: usize,
+    jobs: usize,
+    parallel_install: Option<usize>,
+    retry: RetryPolicy,
+    timeout: Option<Duration>,
+    proxy: Option<String>,
+    cacert: Option<PathBuf>,
+    insecure: bool,
+    github_api_url: Option<String>,
+    no_cache: bool,
+    cache_assets: bool,
+    offline: bool,
+    graphql: bool,
+    multi_progress: Option<MultiProgress>,
+    github_token_command: Option<String>,
+    license_check: bool,
+    asset_weight_file: Option<PathBuf>,
+    with_checksums: bool,
+    asset_filter: AssetFilter,
+    prefer_libc: Option<Libc>,
+    os: Option<String>,
+    arch: Option<String>,
+    content_type: Option<String>,
+    repeat: bool,
+    ask_on_ambiguity: bool,
+    explain: bool,
+    notes: Option<PathBuf>,
+    tag_range: Option<(Tag, Tag)>,
+    pre_release: bool,
+    include_drafts: bool,
+    latest_strategy: LatestStrategy,
+    if_exists: IfExists,
+    executable: bool,
+    create_dirs: bool,
+    json: bool,
+    report: Mutex<DownloadReport>,
+    dry_run: bool,
 }
 
 enum DownloadMode {
     Interactive,
+    InteractiveMultiple,
     Selection(String),
+    MultipleSelection(Vec<String>),
+    FallbackSelection(Vec<String>),
+    AllAssets,
+    Glob(String),
     Automatic,
+    Package(String),
+    Source(SourceArchive),
+    AssetUrl(String),
+    AssetId(u64),
 }
 
 impl DownloadMode {
-    fn new(select: Option<String>, automatic: bool) -> Self {
-        match (select, automatic) {
-            (Some(x), _) => Self::Selection(x),
-            (_, true) => Self::Automatic,
-            (None, false) => Self::Interactive,
+    fn new(args: &DownloadArgs) -> Self {
+        let modes = (
+            args.select.as_ref(),
+            args.select_glob.as_ref(),
+            args.automatic,
+            args.package.as_ref(),
+            args.all_assets,
+            args.source,
+            args.asset_url.as_ref(),
+            args.asset_id,
+        );
+        match modes {
+            (Some(x), _, _, _, _, _, _, _) if x.len() == 1 => Self::Selection(x[0].clone()),
+            (Some(x), _, _, _, _, _, _, _) if args.fallback => {
+                Self::FallbackSelection(vector::unique(x.clone()))
+            }
+            (Some(x), _, _, _, _, _, _, _) => Self::MultipleSelection(vector::unique(x.clone())),
+            (_, Some(x), _, _, _, _, _, _) => Self::Glob(x.clone()),
+            (_, _, true, _, _, _, _, _) => Self::Automatic,
+            (_, _, _, Some(x), _, _, _, _) => Self::Package(x.clone()),
+            (_, _, _, _, true, _, _, _) => Self::AllAssets,
+            (_, _, _, _, _, Some(x), _, _) => Self::Source(x),
+            (_, _, _, _, _, _, Some(x), _) => Self::AssetUrl(x.clone()),
+            (_, _, _, _, _, _, _, Some(x)) => Self::AssetId(x),
+            (None, None, false, None, false, None, None, None) if args.multiple => {
+                Self::InteractiveMultiple
+            }
+            (None, None, false, None, false, None, None, None) => Self::Interactive,
+        }
+    }
+}
+
+/// Wildcard patterns to choose which assets can be downloaded by `--all-assets` and automatic mode
+struct AssetFilter {
+    include: Vec<wildmatch::WildMatch>,
+    exclude: Vec<wildmatch::WildMatch>,
+}
+
+impl AssetFilter {
+    fn new(include: Option<Vec<String>>, exclude: Option<Vec<String>>) -> Self {
+        let patterns = |x: Option<Vec<String>>| {
+            x.unwrap_or_default()
+                .iter()
+                .map(|pattern| wildmatch::WildMatch::new(pattern))
+                .collect()
+        };
+        Self {
+            include: patterns(include),
+            exclude: patterns(exclude),
         }
     }
+
+    fn matches(&self, asset_name: &str) -> bool {
+        let included =
+            self.include.is_empty() || self.include.iter().any(|x| x.matches(asset_name));
+        included && !self.exclude.iter().any(|x| x.matches(asset_name))
+    }
 }
 
 enum Install {
@@ -47,7 +181,15 @@ enum Install {
 }
 
 impl Install {
-    fn new(install: bool, install_file: Option<Vec<String>>, repository: &Repository) -> Self {
+    fn new(
+        install: bool,
+        install_file: Option<Vec<String>>,
+        install_all: bool,
+        repository: &Repository,
+    ) -> Self {
+        if install_all {
+            return Self::Yes(vec![Executable::All]);
+        }
         match (install_file, install) {
             (Some(executable_names), _) => Self::Yes(
                 vector::unique(executable_names)
@@ -70,113 +212,1173 @@ impl Install {
     fn is_more_than_one(&self) -> bool {
         match self {
             Self::No => false,
-            Self::Yes(x) => x.len() > 1,
+            Self::Yes(x) => x.len() > 1 || x.iter().any(|x| matches!(x, Executable::All)),
         }
     }
 }
 
 impl DownloadHandler {
-    pub fn new(
-        repository: Repository,
-        select: Option<String>,
-        automatic: bool,
-        tag: Option<String>,
-        output: Option<PathBuf>,
-        install: bool,
-        install_file: Option<Vec<String>>,
-    ) -> Self {
-        let install = Install::new(install, install_file, &repository);
-        DownloadHandler {
+    pub fn new(args: DownloadArgs) -> Result<Self, HandlerError> {
+        let mode = DownloadMode::new(&args);
+        let repository = match args.repo {
+            Some(repository) => repository,
+            None => git_remote::repository_of(args.remote.as_deref().unwrap_or(DEFAULT_REMOTE))?,
+        };
+        let install = Install::new(
+            args.install,
+            args.install_file,
+            args.install_all,
+            &repository,
+        );
+        let config = Config::load().map_err(|e| HandlerError::new(e.to_string()))?;
+        let install_options = InstallOptions {
+            allow_shell_scripts: args.allow_shell_scripts,
+            self_extract_args: args
+                .self_extract_args
+                .map(|x| x.split_whitespace().map(String::from).collect())
+                .unwrap_or_default(),
+            install_man_pages: args.install_man_page,
+            allow_untrusted_packages: args.allow_untrusted,
+            desktop_entry: args.desktop_entry,
+            remove_quarantine: !args.keep_quarantine,
+            extract_deb: args.extract_deb,
+        };
+        Ok(DownloadHandler {
+            tag: args.tag.or(repository.tag.clone()).map(Tag),
             repository,
-            mode: DownloadMode::new(select.clone(), automatic),
-            tag: tag.map(Tag),
-            output,
+            config,
+            mode,
+            output: args.output,
             install,
-        }
+            install_options,
+            overwrite_if_older: args.overwrite_if_older,
+            create_symlink: args.create_symlink,
+            compress_meta: args.compress_meta,
+            progress_to_file: args.progress_to_file,
+            progress: args.progress,
+            limit_rate: args.limit_rate,
+            connections: usize::from(args.connections),
+            jobs: usize::from(args.jobs),
+            parallel_install: args.parallel_install.map(usize::from),
+            retry: RetryPolicy {
+                retries: args.retries,
+                delay: args.retry_delay,
+                wait_for_rate_limit: args.wait_for_rate_limit,
+            },
+            timeout: args.timeout,
+            proxy: args.proxy.clone(),
+            cacert: args.cacert.clone(),
+            insecure: args.insecure,
+            github_api_url: args.github_api_url.clone(),
+            no_cache: args.no_cache,
+            cache_assets: args.cache_assets,
+            offline: args.offline,
+            graphql: args.graphql,
+            multi_progress: (args.jobs > 1 || args.parallel_install.is_some_and(|x| x > 1))
+                .then(MultiProgress::new),
+            github_token_command: args.github_token_command,
+            license_check: args.license_check,
+            asset_weight_file: args.asset_weight_file,
+            with_checksums: args.with_checksums,
+            asset_filter: AssetFilter::new(args.include, args.exclude),
+            prefer_libc: args.prefer_libc,
+            os: args.os,
+            arch: args.arch,
+            content_type: args.content_type,
+            repeat: args.repeat,
+            ask_on_ambiguity: args.ask_on_ambiguity,
+            explain: args.explain,
+            notes: args.notes,
+            pre_release: args.pre_release,
+            include_drafts: args.include_drafts,
+            latest_strategy: args.latest_strategy,
+            if_exists: args.if_exists.unwrap_or_default(),
+            executable: args.executable,
+            create_dirs: !args.no_create_dirs,
+            json: args.json,
+            report: Mutex::new(DownloadReport::default()),
+            dry_run: args.dry_run,
+            tag_range: args
+                .from_tag
+                .zip(args.to_tag)
+                .map(|(from, to)| (Tag(from), Tag(to))),
+        })
     }
 
     pub fn run(&self) -> HandlerResult {
-        let github = GithubClient::from_environment();
+        if self.progress == ProgressFormat::Json {
+            if self.json || self.is_stdout_output() {
+                return Err(HandlerError::new(
+                    "You can't print progress events when stdout is used by --json or -o -"
+                        .to_string(),
+                ));
+            }
+            output::reserve_stdout();
+        }
+        if self.json {
+            if self.is_stdout_output() {
+                return Err(HandlerError::new(
+                    "You can't print the JSON report when the asset is written to stdout"
+                        .to_string(),
+                ));
+            }
+            output::reserve_stdout();
+        }
+        let result = self.run_download();
+        // The report is printed even when some downloads fail, so CI can see what succeeded
+        if self.json {
+            println!("{}", self.report.lock().unwrap().to_json());
+        }
+        result
+    }
+
+    fn run_download(&self) -> HandlerResult {
+        if self.is_stdout_output() {
+            if self.install.as_bool() {
+                return Err(HandlerError::new(
+                    "You can't install an asset written to stdout".to_string(),
+                ));
+            }
+            output::reserve_stdout();
+        }
+        let github = self.github_client()?;
+        match &self.mode {
+            DownloadMode::Package(artifact_name) => {
+                self.check_license(&github, &[])?;
+                return self.download_package_artifact(&github, artifact_name);
+            }
+            DownloadMode::AssetUrl(url) => {
+                self.check_license(&github, &[])?;
+                let github = if github.is_github_url(url) {
+                    github
+                } else {
+                    github.without_token()
+                };
+                return self.download_direct_asset(&github, asset_from_url(url)?);
+            }
+            DownloadMode::AssetId(id) => {
+                self.check_license(&github, &[])?;
+                let asset = github.get_asset(&self.repository, *id).map_err(|e| {
+                    HandlerError::github(format!("Error fetching asset {}: {}", id, e), &e)
+                })?;
+                return self.download_direct_asset(&github, asset);
+            }
+            _ => {}
+        }
+
+        if let Some((from, to)) = self.tag_range.as_ref() {
+            self.check_license(&github, &[])?;
+            return self.download_tag_range(&github, from, to);
+        }
+
         let release = self.fetch_release(&github)?;
+        self.check_license(&github, &release.assets)?;
+        let release = match self.content_type.as_ref() {
+            Some(content_type) => filter_by_content_type(release, content_type)?,
+            None => release,
+        };
+        let notes = release.body.clone();
+        self.download_release(&github, release)?;
+        match self.notes.as_ref() {
+            Some(path) => self.save_release_notes(path, notes.as_deref()),
+            None => Ok(()),
+        }
+    }
+
+    fn download_release(&self, github: &GithubClient, release: Release) -> HandlerResult {
+        match &self.mode {
+            DownloadMode::MultipleSelection(selections) => {
+                let selections = self.expand_platform(&release.tag, selections)?;
+                return self.download_multiple_assets(github, release, &selections);
+            }
+            DownloadMode::AllAssets => {
+                return self.download_all_assets(github, release);
+            }
+            DownloadMode::InteractiveMultiple => {
+                return self.download_interactive_selection(github, release);
+            }
+            _ => {}
+        }
+
+        let published_at = release.published_at;
+        let tag = release.tag.clone();
+        let checksum_candidates = if self.with_checksums {
+            release.assets.clone()
+        } else {
+            vec![]
+        };
         let selected_asset = self.select_asset(release)?;
-        let output_path = self.choose_output_path(&selected_asset.name);
-        Self::download_asset(&github, &selected_asset, &output_path)?;
-        self.maybe_install(&selected_asset.name, &output_path)?;
+        if let Some(output_path) =
+            self.download_if_outdated(github, &selected_asset, &tag, published_at)?
+        {
+            if self.with_checksums {
+                self.download_checksums(
+                    github,
+                    &selected_asset.name,
+                    &tag,
+                    checksum_candidates,
+                    &output_path,
+                )?;
+            }
+            self.maybe_install(&selected_asset.name, &output_path)?;
+        }
+        Ok(())
+    }
+
+    fn save_release_notes(&self, path: &Path, notes: Option<&str>) -> HandlerResult {
+        let path = notes_path_from(
+            self.output.as_ref(),
+            self.install.as_bool(),
+            path,
+            Path::is_dir,
+        );
+        if self.dry_run {
+            message!(
+                "Would save release notes to {}",
+                Color::new(&format!("{}", path.display())).bold()
+            );
+            return Ok(());
+        }
+        std::fs::write(&path, notes.unwrap_or_default()).map_err(|e| {
+            HandlerError::new(format!(
+                "Failed to save release notes to {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        message!(
+            "Release notes saved to {}",
+            Color::new(&format!("{}", path.display())).bold()
+        );
+        Ok(())
+    }
+
+    /// Checksum files are saved next to the asset, so they can be verified manually or in CI
+    fn download_checksums(
+        &self,
+        github: &GithubClient,
+        asset_name: &str,
+        tag: &Tag,
+        assets: Vec<Asset>,
+        output_path: &Path,
+    ) -> HandlerResult {
+        let checksums = companion_checksums(asset_name, assets);
+        if checksums.is_empty() {
+            message!(
+                "{} No checksum file found for {}",
+                Color::new("!").yellow(),
+                Color::new(asset_name).bold()
+            );
+            return Ok(());
+        }
+
+        let directory = output_path.parent().unwrap_or(Path::new(""));
+        for checksum in checksums.iter() {
+            self.download_asset(github, checksum, Some(tag), &directory.join(&checksum.name))?;
+        }
         Ok(())
     }
 
+    /// Returns the output path, or `None` when the download is skipped
+    fn download_if_outdated(
+        &self,
+        github: &GithubClient,
+        selected_asset: &Asset,
+        tag: &Tag,
+        published_at: Option<SystemTime>,
+    ) -> Result<Option<PathBuf>, HandlerError> {
+        let output_path = self.choose_output_path(&selected_asset.name, Some(tag));
+        if self.overwrite_if_older && is_up_to_date(&output_path, selected_asset, published_at) {
+            message!(
+                "Skipping download: {} is newer than the release",
+                Color::new(&format!("{}", output_path.display())).bold()
+            );
+            return Ok(None);
+        }
+        self.download_asset(github, selected_asset, Some(tag), &output_path)
+    }
+
+    fn download_multiple_assets(
+        &self,
+        github: &GithubClient,
+        release: Release,
+        selections: &[String],
+    ) -> HandlerResult {
+        self.check_multiple_assets_invariants()?;
+        let selected_assets = selections
+            .iter()
+            .map(|selection| {
+                let asset = autoselect_asset(&release.tag, release.assets.clone(), selection);
+                (selection.clone(), asset)
+            })
+            .collect();
+        self.download_many(github, selected_assets, &release)
+    }
+
+    fn download_interactive_selection(
+        &self,
+        github: &GithubClient,
+        release: Release,
+    ) -> HandlerResult {
+        self.check_multiple_assets_invariants()?;
+        let selected_assets = ask_select_assets(release.assets.clone())?
+            .into_iter()
+            .map(|x| (x.name.clone(), Ok(x)))
+            .collect();
+        self.download_many(github, selected_assets, &release)
+    }
+
+    fn download_all_assets(&self, github: &GithubClient, release: Release) -> HandlerResult {
+        if let Some(output) = self
+            .output
+            .as_ref()
+            .filter(|_| self.create_dirs && !self.dry_run)
+            .filter(|x| !x.exists() && !is_output_template(x))
+        {
+            create_directory(output)?;
+        }
+        self.check_multiple_assets_invariants()?;
+
+        let selected_assets: Vec<_> = release
+            .assets
+            .iter()
+            .filter(|x| self.asset_filter.matches(&x.name))
+            .map(|x| (x.name.clone(), Ok(x.clone())))
+            .collect();
+        if selected_assets.is_empty() {
+            return Err(HandlerError::not_found(format!(
+                "No asset found in release {}",
+                release.tag.0
+            )));
+        }
+        self.download_many(github, selected_assets, &release)
+    }
+
+    /// Each asset is saved in a directory named after its release tag, so that assets
+    /// with the same name in different releases are not overwritten
+    fn download_tag_range(&self, github: &GithubClient, from: &Tag, to: &Tag) -> HandlerResult {
+        if matches!(self.mode, DownloadMode::MultipleSelection(_)) {
+            return Err(HandlerError::new(
+                "You can't select multiple assets for a range of tags, use --fallback to try the patterns in order"
+                    .to_string(),
+            ));
+        }
+        self.check_multiple_assets_invariants()?;
+        let filter = ReleaseFilter {
+            pre_release: true,
+            draft: self.include_drafts,
+        };
+        let releases = fetch_releases_for(github, &self.repository, filter)?;
+        let releases = releases_in_range(releases, from, to)?;
+
+        let total = releases.len();
+        let mut failures = vec![];
+        for release in releases {
+            let tag = release.tag.clone();
+            if let Some(message) = self
+                .download_range_release(github, release)
+                .err()
+                .as_ref()
+                .and_then(HandlerError::failure_message)
+            {
+                failures.push((tag.0, message.to_string()));
+            }
+        }
+        print_summary(total, &failures, self.dry_run);
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(HandlerError::new(format!(
+                "{} release(s) failed to download",
+                failures.len()
+            )))
+        }
+    }
+
+    fn download_range_release(&self, github: &GithubClient, release: Release) -> HandlerResult {
+        let release = match self.content_type.as_ref() {
+            Some(content_type) => filter_by_content_type(release, content_type)?,
+            None => release,
+        };
+        let tag = release.tag.clone();
+        let asset = self.select_asset(release)?;
+        let output_path = match self.output.as_ref() {
+            Some(output) if is_output_template(output) => {
+                expand_output_template(output, &self.repository, Some(&tag), &asset.name)
+            }
+            output => output
+                .cloned()
+                .unwrap_or_default()
+                .join(&tag.0)
+                .join(&asset.name),
+        };
+        if let Some(directory) = output_path.parent().filter(|_| !self.dry_run) {
+            create_directory(directory)?;
+        }
+        self.download_asset(github, &asset, Some(&tag), &output_path)
+            .map(|_| ())
+    }
+
+    /// Every asset is downloaded even if some of them fail, then a summary is printed.
+    /// With `--jobs`, assets are downloaded concurrently.
+    /// With `--parallel-install`, the downloaded assets are installed once all the downloads are completed.
+    fn download_many(
+        &self,
+        github: &GithubClient,
+        selected_assets: Vec<(String, Result<Asset, HandlerError>)>,
+        release: &Release,
+    ) -> HandlerResult {
+        let total = selected_assets.len();
+        let mut failures = vec![];
+        let mut downloaded = vec![];
+        let downloads = run_concurrently(selected_assets, self.jobs, |(selection, asset)| {
+            let result = asset.and_then(|asset| {
+                self.download_if_outdated(github, &asset, &release.tag, release.published_at)
+                    .map(|path| path.map(|x| (asset.name, x)))
+            });
+            (selection, result)
+        });
+        for (selection, result) in downloads {
+            match result {
+                Ok(Some(asset)) => downloaded.push((selection, asset)),
+                Ok(None) => {}
+                Err(e) => failures.extend(failure_of(selection, &e)),
+            }
+        }
+
+        let jobs = self.parallel_install.unwrap_or(1);
+        let installs = run_concurrently(downloaded, jobs, |(selection, (name, path))| {
+            (selection, self.maybe_install(&name, &path))
+        });
+        for (selection, result) in installs {
+            if let Err(e) = result {
+                failures.extend(failure_of(selection, &e));
+            }
+        }
+
+        print_summary(total, &failures, self.dry_run);
+
+        if failures.is_empty() {
+            Ok(())
+        } else if self.install.as_bool() {
+            Err(HandlerError::new(format!(
+                "{} asset(s) failed to download or install",
+                failures.len()
+            )))
+        } else {
+            Err(HandlerError::new(format!(
+                "{} asset(s) failed to download",
+                failures.len()
+            )))
+        }
+    }
+
+    fn check_multiple_assets_invariants(&self) -> HandlerResult {
+        if self.install.as_bool() && self.parallel_install.is_none() {
+            return Err(HandlerError::new(
+                "You can't install multiple assets, select only one asset to install or use --parallel-install"
+                    .to_string(),
+            ));
+        }
+        match self.output.as_ref() {
+            Some(output)
+                if !is_directory_path(output, Path::is_dir) && !has_asset_placeholder(output) =>
+            {
+                Err(HandlerError::new(format!(
+                    "{} is not a directory. When you select multiple assets, you must provide a directory path",
+                    output.display()
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn github_client(&self) -> Result<GithubClient, HandlerError> {
+        let forge = self.repository.forge();
+        let github = match self.github_token_command.as_ref() {
+            Some(cmd) => GithubClient::from_credential_command(cmd)
+                .map_err(|x| HandlerError::new(x.to_string()))?,
+            None => {
+                GithubClient::from_environment_for(&self.repository, self.github_api_url.as_deref())
+            }
+        };
+        Ok(github
+            .with_forge(forge)
+            .with_gzip_encoding(self.compress_meta)
+            .with_retry_policy(self.retry)
+            .with_timeout(self.timeout()?)
+            .with_proxy(
+                self.proxy
+                    .as_deref()
+                    .map(proxy_from_url)
+                    .transpose()
+                    .map_err(HandlerError::new)?,
+            )
+            .with_tls_config(self.tls_config()?)
+            .with_api_url(resolve_api_url(
+                self.github_api_url.as_deref(),
+                &self.repository,
+            ))
+            .with_metadata_cache(if self.no_cache {
+                None
+            } else {
+                MetadataCache::from_environment()
+            })
+            .with_asset_cache(if self.cache_assets || self.offline {
+                AssetCache::from_environment()
+            } else {
+                None
+            })
+            .with_offline(self.offline))
+    }
+
+    /// `--cacert` takes precedence over `DRA_CA_BUNDLE`
+    fn tls_config(&self) -> Result<Option<ureq::tls::TlsConfig>, HandlerError> {
+        let ca_bundle = self
+            .cacert
+            .clone()
+            .or_else(|| env_var::string(DRA_CA_BUNDLE).map(PathBuf::from));
+        if ca_bundle.is_none() && !self.insecure {
+            return Ok(None);
+        }
+        if self.insecure {
+            warn!("TLS certificate verification is disabled");
+        }
+        tls_config(ca_bundle.as_deref(), self.insecure)
+            .map(Some)
+            .map_err(HandlerError::new)
+    }
+
+    /// `--timeout` takes precedence over `DRA_TIMEOUT`
+    fn timeout(&self) -> Result<Option<Duration>, HandlerError> {
+        if self.timeout.is_some() {
+            return Ok(self.timeout);
+        }
+        env_var::string(DRA_TIMEOUT)
+            .map(|x| {
+                humantime::parse_duration(&x).map_err(|e| {
+                    HandlerError::new(format!("Invalid {} value {}: {}", DRA_TIMEOUT, x, e))
+                })
+            })
+            .transpose()
+    }
+
+    fn check_license(&self, github: &GithubClient, assets: &[Asset]) -> HandlerResult {
+        if !self.license_check {
+            return Ok(());
+        }
+
+        let config = &self.config;
+        if config.license_filter.is_empty() {
+            return Err(HandlerError::new(
+                "No license_filter found in config file, cannot check the license".to_string(),
+            ));
+        }
+
+        let license = find_license(github, &self.repository, assets)?
+            .ok_or_else(|| unknown_license_error(&self.repository))?;
+        if config.is_license_allowed(&license) {
+            Ok(())
+        } else {
+            Err(HandlerError::new(format!(
+                "License {} of {} is not allowed (allowed licenses: {})",
+                license,
+                self.repository,
+                config.license_filter.join(", ")
+            )))
+        }
+    }
+
+    fn download_package_artifact(
+        &self,
+        github: &GithubClient,
+        artifact_name: &str,
+    ) -> HandlerResult {
+        let tag = self
+            .tag
+            .clone()
+            .unwrap_or_else(|| Tag(LATEST_PACKAGE_TAG.to_string()));
+        let output_path = self.choose_output_path(artifact_name, Some(&tag));
+        let output_path = self.download(artifact_name, Some(&tag), &output_path, || {
+            github.download_ghcr_artifact(&self.repository, &tag, artifact_name)
+        })?;
+        match output_path {
+            Some(output_path) => self.maybe_install(artifact_name, &output_path),
+            None => Ok(()),
+        }
+    }
+
+    fn download_direct_asset(&self, github: &GithubClient, asset: Asset) -> HandlerResult {
+        let output_path = self.choose_output_path(&asset.name, None);
+        match self.download_asset(github, &asset, None, &output_path)? {
+            Some(output_path) => self.maybe_install(&asset.name, &output_path),
+            None => Ok(()),
+        }
+    }
+
     fn fetch_release(&self, github: &GithubClient) -> Result<Release, HandlerError> {
-        fetch_release_for(github, &self.repository, self.tag.as_ref())
+        if self.include_drafts && github.token.is_none() {
+            return Err(HandlerError::new(
+                "--include-drafts requires a GitHub token with push access to the repository"
+                    .to_string(),
+            ));
+        }
+        let pre_release = self.pre_release || self.config.pre_release;
+        let filter = ReleaseFilter {
+            pre_release,
+            draft: self.include_drafts,
+        };
+        let is_exact_tag = self
+            .tag
+            .as_ref()
+            .is_none_or(|x| x.wildcard().is_none() && x.version_req().is_none());
+        let is_graphql_supported = is_exact_tag
+            && !filter.pre_release
+            && !filter.draft
+            && matches!(self.latest_strategy, LatestStrategy::Date);
+        if self.graphql && is_graphql_supported {
+            return fetch_release_graphql(github, &self.repository, self.tag.as_ref());
+        }
+        if self.graphql {
+            debug!("Release options not supported by the GraphQL API, using the REST API");
+        }
+        fetch_release_for(
+            github,
+            &self.repository,
+            self.tag.as_ref(),
+            filter,
+            self.latest_strategy,
+        )
     }
 
     fn select_asset(&self, release: Release) -> Result<Asset, HandlerError> {
         match &self.mode {
-            DownloadMode::Interactive => ask_select_asset(release.assets),
-            DownloadMode::Selection(selection) => autoselect_asset(release, selection),
+            DownloadMode::Interactive => self.interactive_select_asset(release),
+            DownloadMode::Selection(selection) => {
+                let selection =
+                    self.expand_platform(&release.tag, std::slice::from_ref(selection))?;
+                autoselect_asset(&release.tag, release.assets, &selection[0])
+            }
+            DownloadMode::MultipleSelection(_)
+            | DownloadMode::AllAssets
+            | DownloadMode::InteractiveMultiple => {
+                unreachable!("multiple assets are downloaded with download_many")
+            }
+            DownloadMode::FallbackSelection(selections) => {
+                let selections = self.expand_platform(&release.tag, selections)?;
+                fallback_select_asset(release, &selections)
+            }
+            DownloadMode::Glob(glob) => glob_select_asset(release, glob),
+            DownloadMode::Source(archive) => source_code_asset(release, *archive),
+            DownloadMode::Package(_) => unreachable!("package artifacts are not release assets"),
+            DownloadMode::AssetUrl(_) | DownloadMode::AssetId(_) => {
+                unreachable!("direct assets are downloaded without fetching the release")
+            }
             DownloadMode::Automatic => {
-                let system = system::from_environment().map_err(|e| {
-                    automatic_download_system_error(&self.repository, &release.tag, e)
-                })?;
-                system::find_asset_by_system(&system, release.assets).ok_or_else(|| {
-                    automatic_download_error(&self.repository, &release.tag, &system)
-                })
+                let system = self.target_system(&release.tag)?;
+                let system = system::PreferRules::new(
+                    system::PreferLibc::new(system, self.preferred_libc()?),
+                    self.selection_rules()?,
+                );
+                self.automatic_select_asset(&system, release)
+            }
+        }
+    }
+
+    /// The last selected asset of the repository is highlighted, or selected without asking with `--repeat`
+    fn interactive_select_asset(&self, release: Release) -> Result<Asset, HandlerError> {
+        let mut state = SelectionState::load();
+        let mut assets = release.assets;
+        let previous = state.get(&self.repository).and_then(|pattern| {
+            let name = TaggedAsset::tag(&release.tag, pattern);
+            assets.iter().position(|x| x.name == name)
+        });
+
+        let asset = match previous {
+            Some(index) if self.repeat => assets.swap_remove(index),
+            _ => ask_select_asset(assets, previous.unwrap_or(0))?,
+        };
+        state.set(&self.repository, TaggedAsset::untag(&release.tag, &asset));
+        if self.dry_run {
+            return Ok(asset);
+        }
+        if let Err(e) = state.save() {
+            eprintln!(
+                "{} Cannot save the selected asset: {}",
+                Color::new("!").yellow(),
+                e
+            );
+        }
+        Ok(asset)
+    }
+
+    /// The target system is detected only when a pattern contains platform placeholders,
+    /// so that `-s/--select` keeps working on unsupported systems
+    fn expand_platform(
+        &self,
+        tag: &Tag,
+        selections: &[String],
+    ) -> Result<Vec<String>, HandlerError> {
+        if !selections
+            .iter()
+            .any(|x| system::has_platform_placeholders(x))
+        {
+            return Ok(selections.to_vec());
+        }
+        let system = self.target_system(tag)?;
+        let libc = self.preferred_libc()?.unwrap_or(Libc::Musl);
+        Ok(selections
+            .iter()
+            .map(|x| system::expand_platform(x, &system, libc))
+            .collect())
+    }
+
+    fn target_system(&self, tag: &Tag) -> Result<system::SupportedSystem, HandlerError> {
+        let is_overridden = self.os.is_some() || self.arch.is_some();
+        system::from_os_arch(self.os.as_deref(), self.arch.as_deref()).map_err(|e| {
+            if is_overridden {
+                HandlerError::new(format!("{}. Check --os and --arch values", e))
+            } else {
+                automatic_download_system_error(&self.repository, tag, e)
+            }
+        })
+    }
+
+    fn automatic_select_asset(
+        &self,
+        system: &impl system::System,
+        release: Release,
+    ) -> Result<Asset, HandlerError> {
+        let weight_file = self.load_weight_file()?;
+        let weights = weight_file
+            .as_ref()
+            .and_then(|x| x.for_repository(&self.repository));
+        let assets = release
+            .assets
+            .into_iter()
+            .filter(|x| self.asset_filter.matches(&x.name))
+            .collect::<Vec<_>>();
+        if self.explain {
+            print_explanation(system, &assets);
+        }
+        let mut candidates = match weights {
+            Some(weights) => {
+                system::find_candidates_by_weights(system, weights, &release.tag, assets)
             }
+            None => system::find_candidates_by_system(system, assets),
+        };
+        if candidates.is_empty() {
+            return Err(automatic_download_error(
+                &self.repository,
+                &release.tag,
+                system,
+            ));
+        }
+        if candidates.len() == 1 || !self.ask_on_ambiguity {
+            return Ok(candidates.swap_remove(0));
+        }
+        if std::io::stdin().is_terminal() {
+            ask_select_asset(candidates, 0)
+        } else {
+            Err(ambiguous_candidates_error(&candidates))
         }
     }
 
-    fn choose_output_path(&self, asset_name: &str) -> PathBuf {
+    /// `--prefer-libc` takes precedence over `DRA_PREFER_LIBC` and the config file
+    fn preferred_libc(&self) -> Result<Option<Libc>, HandlerError> {
+        if self.prefer_libc.is_some() {
+            return Ok(self.prefer_libc);
+        }
+        env_var::string(DRA_PREFER_LIBC)
+            .or_else(|| self.config.prefer_libc.clone())
+            .map(|x| Libc::try_parse(&x))
+            .transpose()
+            .map_err(HandlerError::new)
+    }
+
+    fn selection_rules(&self) -> Result<Vec<regex::Regex>, HandlerError> {
+        find_by_repository(&self.config.selection_rules, &self.repository)
+            .map(|rules| {
+                rules
+                    .iter()
+                    .map(|x| regex::Regex::new(x))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| HandlerError::new(format!("Invalid selection rule: {}", e)))
+            })
+            .unwrap_or_else(|| Ok(vec![]))
+    }
+
+    fn load_weight_file(&self) -> Result<Option<WeightFile>, HandlerError> {
+        self.asset_weight_file
+            .as_ref()
+            .map(|path| WeightFileLoader::load(path))
+            .transpose()
+            .map_err(|e| HandlerError::new(e.to_string()))
+    }
+
+    /// `tag` is `None` when the asset is downloaded without fetching its release
+    fn choose_output_path(&self, asset_name: &str, tag: Option<&Tag>) -> PathBuf {
+        let output = self
+            .output
+            .as_ref()
+            .map(|x| expand_output_template(x, &self.repository, tag, asset_name));
         choose_output_path_from(
-            self.output.as_ref(),
+            output.as_ref(),
             self.install.as_bool(),
             asset_name,
             Path::is_dir,
         )
     }
 
+    /// Returns the path where the asset is saved, or `None` when the download is skipped
     fn download_asset(
+        &self,
         github: &GithubClient,
         selected_asset: &Asset,
+        tag: Option<&Tag>,
+        output_path: &Path,
+    ) -> Result<Option<PathBuf>, HandlerError> {
+        if let Some(size) = selected_asset
+            .size
+            .filter(|_| !self.dry_run && output_path != Path::new(STDOUT_OUTPUT))
+        {
+            self.check_free_space(&selected_asset.name, size, output_path)?;
+        }
+        let mirror = self.mirror_of(github, selected_asset);
+        let (github, selected_asset) = match mirror.as_ref() {
+            Some((mirror_client, mirror_asset)) => (mirror_client, mirror_asset),
+            None => (github, selected_asset),
+        };
+        let output_path = self.download(&selected_asset.name, tag, output_path, || {
+            if self.connections > 1 {
+                github.download_asset_chunked(selected_asset, self.connections)
+            } else {
+                github.download_asset_stream(selected_asset)
+            }
+        })?;
+        if let (Some(path), Some(updated_at)) = (output_path.as_ref(), selected_asset.updated_at) {
+            set_modified_time(path, updated_at);
+        }
+        Ok(output_path)
+    }
+
+    /// Assets whose URL is rewritten by `url_rewrites` in the config file are downloaded
+    /// from the mirror, without sending the GitHub token to it
+    fn mirror_of(&self, github: &GithubClient, asset: &Asset) -> Option<(GithubClient, Asset)> {
+        self.config.rewrite_url(&asset.download_url).map(|url| {
+            debug!("Rewriting {} to {}", asset.download_url, url);
+            let asset = Asset {
+                download_url: url,
+                api_url: None,
+                ..asset.clone()
+            };
+            (github.without_token(), asset)
+        })
+    }
+
+    /// Fails before the download starts, instead of mid-write, when there is not enough space
+    /// for the asset where it's saved and, when installing, where it's extracted
+    fn check_free_space(
+        &self,
+        asset_name: &str,
+        size: u64,
         output_path: &Path,
     ) -> Result<(), HandlerError> {
-        let progress_bar = ProgressBar::download_layout(&selected_asset.name, output_path);
+        let available_space = |path: &Path| {
+            let directory = existing_ancestor(path, Path::exists)?;
+            fs4::available_space(directory).ok()
+        };
+        let output_directory = output_path.parent().unwrap_or(output_path);
+        check_free_space(
+            asset_name,
+            size,
+            output_directory,
+            available_space(output_directory),
+        )?;
+        if self.install.as_bool() {
+            let temp_dir = std::env::temp_dir();
+            check_free_space(asset_name, size, &temp_dir, available_space(&temp_dir))?;
+        }
+        Ok(())
+    }
+
+    fn download<F>(
+        &self,
+        name: &str,
+        tag: Option<&Tag>,
+        output_path: &Path,
+        open_stream: F,
+    ) -> Result<Option<PathBuf>, HandlerError>
+    where
+        F: Fn() -> Result<DownloadStream, GithubError>,
+    {
+        if self.dry_run {
+            return self.print_dry_run(name, output_path).map(|_| None);
+        }
+        if output_path == Path::new(STDOUT_OUTPUT) {
+            return self.download_to_stdout(name, open_stream).map(|_| None);
+        }
+        if let Some(directory) = output_path
+            .parent()
+            .filter(|x| self.create_dirs && !x.as_os_str().is_empty() && !x.exists())
+        {
+            create_directory(directory)?;
+        }
+        let output_path = match existing_file_path(self.if_exists, output_path, Path::exists)? {
+            Some(path) => path,
+            None => {
+                message!(
+                    "Skipping download: {} already exists",
+                    Color::new(&format!("{}", output_path.display())).bold()
+                );
+                return Ok(None);
+            }
+        };
+        let output_path = output_path.as_path();
+        info!("Downloading {} to {}", name, output_path.display());
+        let mut progress_bar = ProgressBar::download_layout(name, output_path);
+        if let Some(multi_progress) = self.multi_progress.as_ref() {
+            progress_bar = progress_bar.attach(multi_progress);
+        }
+        let progress_events = self.progress_events(name);
+        if progress_events.is_some() {
+            progress_bar.hide();
+        }
         progress_bar.show();
-        let (mut stream, maybe_content_length) = github
-            .download_asset_stream(selected_asset)
-            .map_err(download_asset_error)?;
-        progress_bar.set_length(maybe_content_length);
+        let progress = DownloadProgress {
+            bar: &progress_bar,
+            file: self.progress_to_file.clone().map(ProgressFileWriter::new),
+            events: progress_events,
+        };
+
+        let temporary_path = temporary_download_path(output_path);
+        let mut retry = 0;
+        let (total_bytes, content_length, hasher) = loop {
+            let result = open_stream()
+                .map_err(|e| AttemptError::Fatal(download_asset_error(e)))
+                .and_then(|stream| {
+                    self.save_stream(name, output_path, &temporary_path, stream, &progress)
+                });
+            match result {
+                Ok(downloaded) => break downloaded,
+                Err(AttemptError::Interrupted(message)) if retry < self.retry.retries => {
+                    retry += 1;
+                    let delay = self.retry.delay_before(retry);
+                    warn!(
+                        "Error downloading {}: {}, retrying in {} ({}/{})",
+                        name,
+                        message,
+                        humantime::format_duration(delay),
+                        retry,
+                        self.retry.retries
+                    );
+                    std::thread::sleep(delay);
+                }
+                Err(AttemptError::Interrupted(message)) => {
+                    return Err(HandlerError::new(format!(
+                        "Error downloading {}: {}",
+                        name, message
+                    )));
+                }
+                Err(AttemptError::Fatal(e)) => return Err(e),
+            }
+        };
+        if let Some(progress_file) = progress.file.as_ref() {
+            progress_file.finish(total_bytes, content_length);
+        }
+        if let Some(progress_events) = progress.events.as_ref() {
+            progress_events.finish(total_bytes, content_length);
+        }
+        debug!(
+            "Downloaded {} bytes to {}",
+            total_bytes,
+            temporary_path.display()
+        );
+        rename_file(&temporary_path, output_path)?;
+        if self.executable {
+            set_executable_permissions(output_path)
+                .map_err(|e| HandlerError::new(e.to_string()))?;
+        }
+        if let Some(hasher) = hasher {
+            self.report.lock().unwrap().add(DownloadedAsset {
+                name: name.to_string(),
+                tag: tag.map(|x| x.0.clone()),
+                size: total_bytes,
+                sha256: hasher.finish(),
+                path: Some(output_path.to_path_buf()),
+                executables: vec![],
+            });
+        }
+        progress_bar.finish();
+        Ok(Some(output_path.to_path_buf()))
+    }
 
-        let mut destination = create_file(output_path)?;
+    /// A single attempt to save the stream to the temporary path.
+    /// On failure, the temporary file is removed.
+    fn save_stream(
+        &self,
+        name: &str,
+        output_path: &Path,
+        temporary_path: &Path,
+        stream: DownloadStream,
+        progress: &DownloadProgress,
+    ) -> Result<(u64, Option<u64>, Option<ContentHasher>), AttemptError> {
+        let DownloadStream {
+            reader: mut stream,
+            content_length,
+        } = stream;
+        progress.bar.set_length(content_length);
+        progress.bar.update_progress(0);
+
+        let mut destination = create_file(temporary_path).map_err(AttemptError::Fatal)?;
+        let failed = |error: AttemptError| {
+            let _ = std::fs::remove_file(temporary_path);
+            error
+        };
+        let mut hasher = self.json.then(ContentHasher::default);
+        let rate_limiter = self.limit_rate.map(RateLimiter::new);
         let mut total_bytes = 0;
         let mut buffer = [0; 1024];
-        while let Ok(bytes) = stream.read(&mut buffer) {
-            if bytes == 0 {
-                break;
+        loop {
+            let bytes = match stream.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(failed(AttemptError::Interrupted(e.to_string()))),
+            };
+
+            if let Err(e) = destination.write_all(&buffer[..bytes]) {
+                return Err(failed(AttemptError::Fatal(save_to_file_error(
+                    name,
+                    output_path,
+                    e,
+                ))));
+            }
+            if let Some(hasher) = hasher.as_mut() {
+                hasher.update(&buffer[..bytes]);
             }
-
-            destination
-                .write(&buffer[..bytes])
-                .map_err(|x| save_to_file_error(&selected_asset.name, output_path, x))?;
 
             total_bytes += bytes as u64;
-            progress_bar.update_progress(total_bytes);
+            progress.bar.update_progress(total_bytes);
+            if let Some(progress_file) = progress.file.as_ref() {
+                progress_file.update_progress(total_bytes, content_length);
+            }
+            if let Some(progress_events) = progress.events.as_ref() {
+                progress_events.update_progress(total_bytes, content_length);
+            }
+            if let Some(rate_limiter) = rate_limiter.as_ref() {
+                rate_limiter.throttle(total_bytes);
+            }
         }
+        check_download_size(total_bytes, content_length)
+            .map_err(|e| failed(AttemptError::Interrupted(e)))?;
+        // The content must be on disk before the rename, otherwise a crash could leave
+        // an empty or truncated file at the output path
+        destination.sync_all().map_err(|e| {
+            failed(AttemptError::Fatal(save_to_file_error(
+                name,
+                output_path,
+                e,
+            )))
+        })?;
+        Ok((total_bytes, content_length, hasher))
+    }
+
+    fn download_to_stdout<F>(&self, name: &str, open_stream: F) -> HandlerResult
+    where
+        F: FnOnce() -> Result<DownloadStream, GithubError>,
+    {
+        let progress_bar = ProgressBar::stdout_layout(name);
+        progress_bar.show();
+        let DownloadStream {
+            reader: mut stream,
+            content_length,
+        } = open_stream().map_err(download_asset_error)?;
+        progress_bar.set_length(content_length);
+
+        let mut stdout = std::io::stdout().lock();
+        let rate_limiter = self.limit_rate.map(RateLimiter::new);
+        write_stream(
+            name,
+            &mut stream,
+            &mut stdout,
+            content_length,
+            |total_bytes| {
+                progress_bar.update_progress(total_bytes);
+                if let Some(rate_limiter) = rate_limiter.as_ref() {
+                    rate_limiter.throttle(total_bytes);
+                }
+            },
+        )?;
+        stdout.flush().map_err(|e| write_to_stdout_error(name, e))?;
         progress_bar.finish();
         Ok(())
     }
 
+    /// Nothing is downloaded or written, but the same checks of a real download are done
+    fn print_dry_run(&self, name: &str, output_path: &Path) -> HandlerResult {
+        let name = Color::new(name).bold();
+        if self.install.as_bool() {
+            let destination = match self.install_destination()? {
+                Destination::Directory(x) | Destination::File(x) => x,
+            };
+            message!(
+                "Would download {} and install it to {}",
+                name,
+                Color::new(&format!("{}", destination.display())).bold()
+            );
+            return Ok(());
+        }
+        if output_path == Path::new(STDOUT_OUTPUT) {
+            message!("Would download {} to stdout", name);
+            return Ok(());
+        }
+        match existing_file_path(self.if_exists, output_path, Path::exists)? {
+            Some(path) => message!(
+                "Would download {} to {}",
+                name,
+                Color::new(&format!("{}", path.display())).bold()
+            ),
+            None => message!(
+                "Would skip {}: {} already exists",
+                name,
+                Color::new(&format!("{}", output_path.display())).bold()
+            ),
+        }
+        Ok(())
+    }
+
+    fn progress_events(&self, asset_name: &str) -> Option<ProgressEvents> {
+        (self.progress == ProgressFormat::Json).then(|| ProgressEvents::new(asset_name))
+    }
+
+    fn is_stdout_output(&self) -> bool {
+        self.output
+            .as_ref()
+            .is_some_and(|x| x == Path::new(STDOUT_OUTPUT))
+    }
+
     fn maybe_install(&self, asset_name: &str, path: &Path) -> Result<(), HandlerError> {
         match &self.install {
             Install::No => Ok(()),
             Install::Yes(executables) => {
-                let cwd = cwd()?;
-                let destination = match self.output.as_ref() {
-                    Some(output) if output.is_dir() => Destination::Directory(output.clone()),
-                    Some(output) => Destination::File(output.clone()),
-                    None => Destination::Directory(cwd),
-                };
-                self.check_destination_invariants(&destination)?;
-
-                let spinner = Spinner::install_layout();
+                let destination = self.install_destination()?;
+                if let Destination::Directory(directory) = &destination
+                    && (self.create_dirs || self.output.is_none())
+                    && !directory.exists()
+                {
+                    create_directory(directory)?;
+                }
+
+                let progress_events = self.progress_events(asset_name);
+                if let Some(progress_events) = progress_events.as_ref() {
+                    progress_events.phase(Phase::Install);
+                }
+                let mut spinner = Spinner::install_layout();
+                if let Some(multi_progress) = self.multi_progress.as_ref() {
+                    spinner = spinner.attach(multi_progress);
+                }
                 spinner.show();
 
                 let output = install(
@@ -184,14 +1386,38 @@ impl DownloadHandler {
                     path,
                     destination,
                     executables.clone(),
+                    &self.install_options,
                 )
-                .map_err(|x| HandlerError::new(x.to_string()))?;
+                .map_err(|x| HandlerError::install_failed(x.to_string()))?;
 
                 remove_temporary_file(path)?;
+                if let Some(progress_events) = progress_events.as_ref() {
+                    progress_events.phase(Phase::Installed);
+                }
+                self.report
+                    .lock()
+                    .unwrap()
+                    .installed(path, output.executables());
+
+                let mut message = output.to_string();
+                if let Some(symlink_path) = self.create_symlink.as_ref() {
+                    let symlink = Self::create_symlink_to(output.executables(), symlink_path)?;
+                    message = format!("{}\n{}", message, symlink);
+                }
+
+                if let Some(directory) = self.output.is_none().then(bin_dir).flatten()
+                    && !is_in_path(&directory, std::env::var_os("PATH"))
+                {
+                    let warning = format!(
+                        "{} is not in your PATH, add it to run the installed executables",
+                        directory.display()
+                    );
+                    message = format!("{}\n{}", message, Color::new(&warning).yellow());
+                }
 
                 let message = format!(
                     "{}\n{}",
-                    output,
+                    message,
                     Color::new("Installation completed!").green(),
                 );
                 spinner.finish_with_message(&message);
@@ -200,6 +1426,35 @@ impl DownloadHandler {
         }
     }
 
+    fn create_symlink_to(
+        executables: &[PathBuf],
+        symlink_path: &Path,
+    ) -> Result<String, HandlerError> {
+        match executables {
+            [executable] => create_symlink(executable, symlink_path)
+                .map(|x| x.to_string())
+                .map_err(|x| HandlerError::new(x.to_string())),
+            [] => Err(HandlerError::new(
+                "Cannot create symlink: the installed asset has no executable path".to_string(),
+            )),
+            _ => Err(HandlerError::new(
+                "Cannot create symlink: more than one executable was installed".to_string(),
+            )),
+        }
+    }
+
+    fn install_destination(&self) -> Result<Destination, HandlerError> {
+        let destination = match self.output.as_ref() {
+            Some(output) if is_directory_path(output, Path::is_dir) => {
+                Destination::Directory(output.clone())
+            }
+            Some(output) => Destination::File(output.clone()),
+            None => Destination::Directory(bin_dir().map(Ok).unwrap_or_else(cwd)?),
+        };
+        self.check_destination_invariants(&destination)?;
+        Ok(destination)
+    }
+
     fn check_destination_invariants(&self, destination: &Destination) -> Result<(), HandlerError> {
         if !self.install.is_more_than_one() {
             return Ok(());
@@ -217,31 +1472,204 @@ impl DownloadHandler {
     }
 }
 
-fn ask_select_asset(assets: Vec<Asset>) -> select_assets::AskSelectAssetResult {
-    select_assets::ask_select_asset(
+const LATEST_PACKAGE_TAG: &str = "latest";
+
+fn ask_select_asset(assets: Vec<Asset>, default: usize) -> select_assets::AskSelectAssetResult {
+    select_assets::ask_select_asset_with_default(
         assets,
         select_assets::Messages {
             select_prompt: "Pick the asset to download",
             quit_select: "No asset selected",
         },
+        default,
     )
 }
 
-fn autoselect_asset(release: Release, selection: &str) -> Result<Asset, HandlerError> {
-    let asset_name = TaggedAsset::tag(&release.tag, selection);
+fn autoselect_asset(
+    tag: &Tag,
+    mut assets: Vec<Asset>,
+    selection: &str,
+) -> Result<Asset, HandlerError> {
+    if let Some(regex) = selection.strip_prefix(REGEX_PREFIX) {
+        let regex = TaggedAsset::tag(tag, regex);
+        return autoselect_asset_by_regex(assets, &regex, selection);
+    }
+    let asset_name = TaggedAsset::tag(tag, selection);
     let pattern = wildmatch::WildMatch::new(&asset_name);
 
-    release
-        .assets
+    match assets.iter().position(|x| pattern.matches(&x.name)) {
+        Some(index) => Ok(assets.swap_remove(index)),
+        None => Err(HandlerError::not_found(format!(
+            "No asset found for {}",
+            selection
+        ))),
+    }
+}
+
+/// Selections are regular expressions only with this prefix, so that names with regex special
+/// characters (e.g. `.` or `(`) keep being matched literally
+const REGEX_PREFIX: &str = "regex:";
+
+/// Patterns are tried in order, useful for repositories with inconsistent naming across releases
+fn fallback_select_asset(release: Release, selections: &[String]) -> Result<Asset, HandlerError> {
+    for selection in selections {
+        if let Ok(asset) = autoselect_asset(&release.tag, release.assets.clone(), selection) {
+            return Ok(asset);
+        }
+    }
+    Err(HandlerError::not_found(format!(
+        "No asset found for any of {}",
+        selections.join(", ")
+    )))
+}
+
+/// The regex must match the whole asset name and only one asset,
+/// since it's easy to write a regex that matches unexpected assets
+fn autoselect_asset_by_regex(
+    assets: Vec<Asset>,
+    asset_name: &str,
+    selection: &str,
+) -> Result<Asset, HandlerError> {
+    let regex = regex::Regex::new(&format!("^(?:{})$", asset_name))
+        .map_err(|e| HandlerError::usage(format!("Invalid regex pattern: {}", e)))?;
+
+    let matches = assets
         .into_iter()
-        .find(|x| pattern.matches(&x.name))
-        .ok_or_else(|| HandlerError::new(format!("No asset found for {}", selection)))
+        .filter(|x| regex.is_match(&x.name))
+        .collect();
+    single_asset(matches, selection)
 }
 
-fn automatic_download_system_error(
-    repository: &Repository,
-    release: &Tag,
-    error: system::SystemError,
+/// The asset name is the last segment of the URL path. The decoded name can't be a path
+/// (e.g. `..%2F.bashrc`), so that the asset is never saved outside the output directory
+fn asset_from_url(url: &str) -> Result<Asset, HandlerError> {
+    let no_asset_name = || HandlerError::new(format!("Cannot find the asset name in {}", url));
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let name = path.rsplit('/').next().unwrap_or_default();
+    let name = urlencoding::decode(name)
+        .map(|x| x.into_owned())
+        .unwrap_or_else(|_| name.to_string());
+    let is_path = name.contains(['/', '\\']) || name.contains("..") || name == ".";
+    if name.is_empty() || is_path {
+        return Err(no_asset_name());
+    }
+    Ok(Asset {
+        name,
+        display_name: None,
+        content_type: None,
+        size: None,
+        download_count: None,
+        updated_at: None,
+        download_url: url.to_string(),
+        api_url: None,
+    })
+}
+
+fn single_asset(mut matches: Vec<Asset>, selection: &str) -> Result<Asset, HandlerError> {
+    match matches.len() {
+        0 => Err(HandlerError::not_found(format!(
+            "No asset found for {}",
+            selection
+        ))),
+        1 => Ok(matches.remove(0)),
+        _ => Err(HandlerError::new(format!(
+            "Multiple assets found for {}: {}",
+            selection,
+            matches.iter().map(|x| x.name.as_str()).join(", ")
+        ))),
+    }
+}
+
+const CHECKSUM_EXTENSIONS: [&str; 6] =
+    ["sha256", "sha256sum", "sha512", "sha512sum", "sha1", "md5"];
+
+/// Checksum files of a single asset (e.g. `foo.tar.gz.sha256`) or of all the release assets (e.g. `checksums.txt`)
+fn companion_checksums(asset_name: &str, assets: Vec<Asset>) -> Vec<Asset> {
+    let is_asset_checksum = |name: &str| {
+        name.strip_prefix(asset_name)
+            .and_then(|x| x.strip_prefix('.'))
+            .is_some_and(|extension| {
+                CHECKSUM_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+            })
+    };
+    let is_release_checksums = |name: &str| {
+        let name = name.to_lowercase();
+        name.contains("checksums")
+            || name.starts_with("sha256sums")
+            || name.starts_with("sha512sums")
+    };
+
+    assets
+        .into_iter()
+        .filter(|x| x.name != asset_name)
+        .filter(|x| is_asset_checksum(&x.name) || is_release_checksums(&x.name))
+        .collect()
+}
+
+/// Content type can be a wildcard pattern (e.g. `application/*`)
+fn filter_by_content_type(release: Release, content_type: &str) -> Result<Release, HandlerError> {
+    let pattern = wildmatch::WildMatch::new_case_insensitive(content_type);
+    let (assets, others): (Vec<_>, Vec<_>) = release.assets.into_iter().partition(|x| {
+        x.content_type
+            .as_deref()
+            .is_some_and(|x| pattern.matches(x))
+    });
+    if assets.is_empty() {
+        let available = others
+            .iter()
+            .filter_map(|x| x.content_type.as_deref())
+            .unique()
+            .join(", ");
+        return Err(HandlerError::not_found(format!(
+            "No asset found with content type {}. Available content types: {}",
+            content_type, available
+        )));
+    }
+    Ok(Release { assets, ..release })
+}
+
+fn ask_select_assets(assets: Vec<Asset>) -> Result<Vec<Asset>, HandlerError> {
+    select_assets::ask_select_assets(
+        assets,
+        select_assets::Messages {
+            select_prompt: "Pick the assets to download (space to select, enter to confirm)",
+            quit_select: "No asset selected",
+        },
+    )
+}
+
+fn source_code_asset(release: Release, archive: SourceArchive) -> Result<Asset, HandlerError> {
+    let name = match archive {
+        SourceArchive::Tar => SOURCE_CODE_TAR_GZ,
+        SourceArchive::Zip => SOURCE_CODE_ZIP,
+    };
+    release
+        .assets
+        .into_iter()
+        .find(|x| x.display_name.as_deref() == Some(name))
+        .ok_or_else(|| {
+            HandlerError::not_found(format!("{} not found in release {}", name, release.tag.0))
+        })
+}
+
+/// Unlike `--select`, a glob must match only one asset
+fn glob_select_asset(release: Release, glob: &str) -> Result<Asset, HandlerError> {
+    let asset_name = TaggedAsset::tag(&release.tag, glob);
+    let pattern = glob::Pattern::new(&asset_name)
+        .map_err(|e| HandlerError::new(format!("Invalid glob pattern {}: {}", glob, e)))?;
+
+    let matches = release
+        .assets
+        .into_iter()
+        .filter(|x| pattern.matches(&x.name))
+        .collect();
+    single_asset(matches, glob)
+}
+
+fn automatic_download_system_error(
+    repository: &Repository,
+    release: &Tag,
+    error: system::SystemError,
 ) -> HandlerError {
     let title = urlencoding::encode("Error: System error automatic download of asset");
     let body = format!(
@@ -262,6 +1690,25 @@ fn automatic_download_system_error(
     ))
 }
 
+fn print_explanation(system: &impl system::System, assets: &[Asset]) {
+    message!(
+        "Target system: {} {}",
+        Color::new(system.os().as_str()).bold(),
+        Color::new(system.arch().as_str()).bold()
+    );
+    for explanation in system::explain(system, assets) {
+        message!("  {}", explanation);
+    }
+}
+
+fn ambiguous_candidates_error(candidates: &[Asset]) -> HandlerError {
+    let names: Vec<_> = candidates.iter().map(|x| format!("  {}", x.name)).collect();
+    HandlerError::new(format!(
+        "Multiple assets match your system equally well:\n{}\nUse -s/--select to choose one of them",
+        names.join("\n")
+    ))
+}
+
 fn automatic_download_error(
     repository: &Repository,
     release: &Tag,
@@ -281,7 +1728,7 @@ fn automatic_download_error(
         "https://github.com/devmatteini/dra/issues/new?title={}&body={}",
         title, body
     );
-    HandlerError::new(format!(
+    HandlerError::not_found(format!(
         "Cannot find asset that matches your system {} {}\nIf you think this is a bug, please report the issue: {}",
         system.os(),
         system.arch(),
@@ -289,6 +1736,40 @@ fn automatic_download_error(
     ))
 }
 
+const OUTPUT_PLACEHOLDERS: [&str; 4] = ["{owner}", "{repo}", "{tag}", "{asset}"];
+
+fn is_output_template(output: &Path) -> bool {
+    let output = output.to_string_lossy();
+    OUTPUT_PLACEHOLDERS.iter().any(|x| output.contains(x))
+}
+
+/// Each asset gets its own path, so the output can be used for multiple assets
+fn has_asset_placeholder(output: &Path) -> bool {
+    output.to_string_lossy().contains("{asset}")
+}
+
+/// `{tag}` is kept as is when the release tag is unknown
+fn expand_output_template(
+    output: &Path,
+    repository: &Repository,
+    tag: Option<&Tag>,
+    asset_name: &str,
+) -> PathBuf {
+    if !is_output_template(output) {
+        return output.to_path_buf();
+    }
+    let output = output
+        .to_string_lossy()
+        .replace("{owner}", &repository.owner)
+        .replace("{repo}", &repository.repo)
+        .replace("{asset}", asset_name);
+    let output = match tag {
+        Some(tag) => output.replace("{tag}", &tag.0),
+        None => output,
+    };
+    PathBuf::from(output)
+}
+
 fn choose_output_path_from<IsDir>(
     output: Option<&PathBuf>,
     install: bool,
@@ -304,7 +1785,7 @@ where
 
     output
         .map(|path| {
-            if is_dir(path) {
+            if is_directory_path(path, is_dir) {
                 path.join(asset_name)
             } else {
                 path.to_path_buf()
@@ -313,8 +1794,202 @@ where
         .unwrap_or_else(|| PathBuf::from(asset_name))
 }
 
+/// A path ending with a separator (e.g. `./artifacts/v1.2.3/`) is a directory even when it doesn't exist yet
+fn is_directory_path<IsDir>(path: &Path, is_dir: IsDir) -> bool
+where
+    IsDir: FnOnce(&Path) -> bool,
+{
+    let has_trailing_separator = path.to_string_lossy().ends_with(std::path::is_separator);
+    has_trailing_separator || is_dir(path)
+}
+
+/// Relative notes paths are saved in the same directory of the downloaded assets
+fn notes_path_from<IsDir>(
+    output: Option<&PathBuf>,
+    install: bool,
+    notes: &Path,
+    is_dir: IsDir,
+) -> PathBuf
+where
+    IsDir: FnOnce(&Path) -> bool,
+{
+    if notes.is_absolute() || install {
+        return notes.to_path_buf();
+    }
+    let directory = output.map(|path| {
+        if is_dir(path) {
+            path.as_path()
+        } else {
+            path.parent().unwrap_or(Path::new(""))
+        }
+    });
+    match directory {
+        Some(directory) => directory.join(notes),
+        None => notes.to_path_buf(),
+    }
+}
+
+/// Downloaded files have the modified time of their asset (see `set_modified_time`), which is
+/// earlier than the publication date when the asset was uploaded to a draft release
+fn is_up_to_date(path: &Path, asset: &Asset, published_at: Option<SystemTime>) -> bool {
+    let modified = std::fs::metadata(path).and_then(|x| x.modified()).ok();
+    is_newer_than_release(modified, asset.updated_at.or(published_at))
+}
+
+fn is_newer_than_release(modified: Option<SystemTime>, released_at: Option<SystemTime>) -> bool {
+    match (modified, released_at) {
+        (Some(modified), Some(released_at)) => modified >= released_at,
+        _ => false,
+    }
+}
+
+/// A license file attached to the release takes precedence over the repository license
+fn find_license(
+    github: &GithubClient,
+    repository: &Repository,
+    assets: &[Asset],
+) -> Result<Option<String>, HandlerError> {
+    let from_asset = match assets.iter().find(|x| license::is_license_file(&x.name)) {
+        Some(asset) => {
+            let mut text = String::new();
+            github
+                .download_asset_stream(asset)
+                .map_err(download_asset_error)?
+                .reader
+                .take(MAX_LICENSE_FILE_SIZE)
+                .read_to_string(&mut text)
+                .map_err(|e| {
+                    HandlerError::new(format!("Error reading license file {}: {}", asset.name, e))
+                })?;
+            license::detect_license(&text)
+        }
+        None => None,
+    };
+    match from_asset {
+        Some(x) => Ok(Some(x)),
+        None => github.get_license(repository).map_err(|e| {
+            HandlerError::new(format!("Error fetching license of {}: {}", repository, e))
+        }),
+    }
+}
+
+const MAX_LICENSE_FILE_SIZE: u64 = 1024 * 1024;
+
+fn unknown_license_error(repository: &Repository) -> HandlerError {
+    HandlerError::new(format!(
+        "Cannot determine the license of {}, refusing to download",
+        repository
+    ))
+}
+
 fn download_asset_error(e: GithubError) -> HandlerError {
-    HandlerError::new(format!("Error downloading asset: {}", e))
+    HandlerError::github(format!("Error downloading asset: {}", e), &e)
+}
+
+/// Where to save a file according to the policy when the path already exists,
+/// or `None` when the download must be skipped
+fn existing_file_path<Exists>(
+    policy: IfExists,
+    path: &Path,
+    exists: Exists,
+) -> Result<Option<PathBuf>, HandlerError>
+where
+    Exists: Fn(&Path) -> bool,
+{
+    if !exists(path) {
+        return Ok(Some(path.to_path_buf()));
+    }
+    match policy {
+        IfExists::Overwrite => Ok(Some(path.to_path_buf())),
+        IfExists::Skip => Ok(None),
+        IfExists::Error => Err(HandlerError::new(format!(
+            "{} already exists",
+            path.display()
+        ))),
+        IfExists::Rename => Ok((1..).map(|n| numbered_path(path, n)).find(|x| !exists(x))),
+    }
+}
+
+/// The number is added before the extensions, e.g. `helloworld-1.tar.gz`
+fn numbered_path(path: &Path, n: u32) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|x| x.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file_name = match file_name.char_indices().skip(1).find(|(_, c)| *c == '.') {
+        Some((index, _)) => format!("{}-{}{}", &file_name[..index], n, &file_name[index..]),
+        None => format!("{}-{}", file_name, n),
+    };
+    path.with_file_name(file_name)
+}
+
+/// Results are in the same order of `items`, regardless of which job completes first
+fn run_concurrently<T, R, F>(items: Vec<T>, jobs: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    if jobs <= 1 {
+        return items.into_iter().map(f).collect();
+    }
+    let total = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results = Mutex::new(Vec::with_capacity(total));
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(total) {
+            scope.spawn(|| {
+                loop {
+                    let Some((index, item)) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+                    let result = f(item);
+                    results.lock().unwrap().push((index, result));
+                }
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Cancelled operations are not failures of the summary
+fn failure_of(selection: String, error: &HandlerError) -> Option<(String, String)> {
+    error
+        .failure_message()
+        .map(|message| (selection, message.to_string()))
+}
+
+fn print_summary(total: usize, failures: &[(String, String)], dry_run: bool) {
+    let verb = if dry_run {
+        "Would download"
+    } else {
+        "Downloaded"
+    };
+    message!(
+        "\n{}",
+        Color::new(&format!(
+            "{} {} of {} assets",
+            verb,
+            total - failures.len(),
+            total
+        ))
+        .bold()
+    );
+    for (selection, message) in failures.iter() {
+        message!("{} {}: {}", Color::new("✗").red(), selection, message);
+    }
+}
+
+fn create_directory(path: &Path) -> HandlerResult {
+    std::fs::create_dir_all(path).map_err(|e| {
+        HandlerError::new(format!(
+            "Failed to create directory {}: {}",
+            path.display(),
+            e
+        ))
+    })
 }
 
 fn create_file(path: &Path) -> Result<File, HandlerError> {
@@ -322,6 +1997,142 @@ fn create_file(path: &Path) -> Result<File, HandlerError> {
         .map_err(|e| HandlerError::new(format!("Failed to create file {}: {}", path.display(), e)))
 }
 
+/// `-o -` writes the asset to stdout
+const STDOUT_OUTPUT: &str = "-";
+
+const TEMPORARY_DOWNLOAD_SUFFIX: &str = ".part";
+
+/// The asset is downloaded next to the final path and then renamed,
+/// so that nobody can read an incomplete file at the final path.
+fn temporary_download_path(output_path: &Path) -> PathBuf {
+    let mut path = output_path.as_os_str().to_os_string();
+    path.push(TEMPORARY_DOWNLOAD_SUFFIX);
+    PathBuf::from(path)
+}
+
+// NOTE: rename is atomic on POSIX when both paths are on the same filesystem,
+// while on Windows it uses MoveFileEx with MOVEFILE_REPLACE_EXISTING
+fn rename_file(from: &Path, to: &Path) -> Result<(), HandlerError> {
+    std::fs::rename(from, to).map_err(|e| {
+        let _ = std::fs::remove_file(from);
+        HandlerError::new(format!(
+            "Failed to move {} to {}: {}",
+            from.display(),
+            to.display(),
+            e
+        ))
+    })
+}
+
+/// Content already written to stdout can't be taken back, so the download is never retried and a
+/// read error (or a stream shorter than its Content-Length) fails it, instead of ending the output early
+fn write_stream<R, W, P>(
+    name: &str,
+    stream: &mut R,
+    writer: &mut W,
+    content_length: Option<u64>,
+    mut on_progress: P,
+) -> Result<u64, HandlerError>
+where
+    R: Read + ?Sized,
+    W: Write,
+    P: FnMut(u64),
+{
+    let mut total_bytes = 0;
+    let mut buffer = [0; 1024];
+    loop {
+        let bytes = match stream.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => {
+                return Err(HandlerError::new(format!(
+                    "Error downloading {}: {}",
+                    name, e
+                )));
+            }
+        };
+        writer
+            .write_all(&buffer[..bytes])
+            .map_err(|e| write_to_stdout_error(name, e))?;
+        total_bytes += bytes as u64;
+        on_progress(total_bytes);
+    }
+    check_download_size(total_bytes, content_length)
+        .map_err(|e| HandlerError::new(format!("Error downloading {}: {}", name, e)))?;
+    Ok(total_bytes)
+}
+
+fn write_to_stdout_error(asset_name: &str, error: std::io::Error) -> HandlerError {
+    HandlerError::new(format!("Error writing {} to stdout: {}", asset_name, error))
+}
+
+// NOTE: the timestamp is best effort, so errors must never fail the download
+fn set_modified_time(path: &Path, time: SystemTime) {
+    if let Ok(file) = File::options().write(true).open(path) {
+        let _ = file.set_modified(time);
+    }
+}
+
+/// Failure of a single download attempt
+enum AttemptError {
+    /// The stream ended early or with an error, the download can be retried
+    Interrupted(String),
+    Fatal(HandlerError),
+}
+
+struct DownloadProgress<'a> {
+    bar: &'a ProgressBar,
+    file: Option<ProgressFileWriter>,
+    events: Option<ProgressEvents>,
+}
+
+/// A closed connection ends the stream early without any read error
+fn check_download_size(total_bytes: u64, content_length: Option<u64>) -> Result<(), String> {
+    match content_length {
+        Some(expected) if expected != total_bytes => Err(format!(
+            "incomplete download, received {} of {} bytes",
+            total_bytes, expected
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The nearest directory that exists, where the path will be created
+fn existing_ancestor<F>(path: &Path, exists: F) -> Option<&Path>
+where
+    F: Fn(&Path) -> bool,
+{
+    path.ancestors()
+        .map(|x| {
+            if x.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                x
+            }
+        })
+        .find(|x| exists(x))
+}
+
+/// The check is skipped when the available space is unknown
+fn check_free_space(
+    asset_name: &str,
+    size: u64,
+    directory: &Path,
+    available: Option<u64>,
+) -> Result<(), HandlerError> {
+    match available {
+        Some(available) if available < size => Err(HandlerError::new(format!(
+            "Not enough disk space to download {}: {} required, {} available in {}",
+            asset_name,
+            HumanBytes(size),
+            HumanBytes(available),
+            directory.display()
+        ))),
+        _ => Ok(()),
+    }
+}
+
 fn save_to_file_error(asset_name: &str, output_path: &Path, error: std::io::Error) -> HandlerError {
     HandlerError::new(format!(
         "Error saving {} to {}: {}",
@@ -336,6 +2147,17 @@ fn cwd() -> Result<PathBuf, HandlerError> {
         .map_err(|x| HandlerError::new(format!("Error retrieving current directory: {}", x)))
 }
 
+/// Windows paths are case insensitive, and entries of `PATH` may have a trailing separator
+fn is_in_path(directory: &Path, path: Option<OsString>) -> bool {
+    let normalize = |x: &Path| {
+        x.to_string_lossy()
+            .trim_end_matches(std::path::is_separator)
+            .to_lowercase()
+    };
+    let directory = normalize(directory);
+    path.is_some_and(|x| std::env::split_paths(&x).any(|entry| normalize(&entry) == directory))
+}
+
 fn remove_temporary_file(path: &Path) -> Result<(), HandlerError> {
     std::fs::remove_file(path)
         .map_err(|x| HandlerError::new(format!("Unable to delete temporary file: {}", x)))
@@ -402,6 +2224,19 @@ mod choose_output_path {
         assert_eq!(expected, result);
     }
 
+    /// CLI command:
+    /// dra download -s my_asset.tar.gz -o ./artifacts/v1.2.3/ <REPO>
+    /// output: ./artifacts/v1.2.3/my_asset.tar.gz (the directory doesn't exist yet)
+    #[test]
+    fn missing_directory_path() {
+        let output = PathBuf::from("./artifacts/v1.2.3/");
+        let asset_name = "my_asset.tar.gz";
+
+        let result = choose_output_path_from(Some(&output), NO_INSTALL, asset_name, not_dir);
+
+        assert_eq!(PathBuf::from("./artifacts/v1.2.3/my_asset.tar.gz"), result);
+    }
+
     fn is_dir(_: &Path) -> bool {
         true
     }
@@ -412,112 +2247,635 @@ mod choose_output_path {
 }
 
 #[cfg(test)]
-mod autoselect_asset {
+mod run_concurrently {
     use super::*;
     use test_case::test_case;
 
-    #[test]
-    fn untagged_selection() {
-        let release = any_release(
-            "v1.0.0",
-            vec![
-                "my_asset_v1.0.0.deb",
-                "my_asset_v1.0.0.zip",
-                "my_asset_v1.0.0.tar.gz",
-            ],
-        );
+    #[test_case(1; "sequential")]
+    #[test_case(3; "concurrent")]
+    #[test_case(10; "more jobs than items")]
+    fn results_in_order(jobs: usize) {
+        let items = vec![5, 1, 4, 2, 3];
 
-        let result = autoselect_asset(release, "my_asset_v{tag}.zip");
+        let result = run_concurrently(items, jobs, |x| {
+            std::thread::sleep(std::time::Duration::from_millis(x));
+            x * 10
+        });
 
-        assert_ok_and_equal(result, "my_asset_v1.0.0.zip");
+        assert_eq!(vec![50, 10, 40, 20, 30], result);
+    }
+}
+
+#[cfg(test)]
+mod expand_output_template {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("downloads/{repo}-{tag}-{asset}", "downloads/dra-tests-v1.0.0-helloworld.tar.gz"; "repo tag and asset")]
+    #[test_case("{owner}/{repo}/{asset}", "devmatteini/dra-tests/helloworld.tar.gz"; "owner")]
+    #[test_case("/some/path.tar.gz", "/some/path.tar.gz"; "no placeholders")]
+    fn expanded(output: &str, expected: &str) {
+        let result = expand_output_template(
+            Path::new(output),
+            &repository(),
+            Some(&Tag("v1.0.0".into())),
+            "helloworld.tar.gz",
+        );
+
+        assert_eq!(PathBuf::from(expected), result);
     }
 
     #[test]
-    fn literal_selection() {
-        let release = any_release(
-            "v1.0.0",
-            vec![
-                "my_asset_v1.0.0.deb",
-                "my_asset_v1.0.0.zip",
-                "my_asset.tar.gz",
-            ],
+    fn unknown_tag() {
+        let result = expand_output_template(
+            Path::new("{tag}/{asset}"),
+            &repository(),
+            None,
+            "helloworld.tar.gz",
         );
 
-        let result = autoselect_asset(release, "my_asset.tar.gz");
+        assert_eq!(PathBuf::from("{tag}/helloworld.tar.gz"), result);
+    }
 
-        assert_ok_and_equal(result, "my_asset.tar.gz");
+    fn repository() -> Repository {
+        Repository::try_parse("devmatteini/dra-tests").unwrap()
     }
+}
+
+#[cfg(test)]
+mod notes_path {
+    use super::*;
 
+    const NO_INSTALL: bool = false;
+
+    /// CLI command:
+    /// dra download -s my_asset.tar.gz --notes NOTES.md <REPO>
+    /// output: $PWD/NOTES.md
     #[test]
-    fn wildcard_selection() {
-        let release = any_release(
-            "v1.0.0",
-            vec![
-                "my_asset_abcd.deb",
-                "my_asset_abcd.zip",
-                "my_asset_efgh.zip",
-            ],
-        );
+    fn default_path() {
+        let result = notes_path_from(None, NO_INSTALL, Path::new("NOTES.md"), not_dir);
 
-        let result = autoselect_asset(release, "my_asset_*.zip");
+        assert_eq!(PathBuf::from("NOTES.md"), result)
+    }
 
-        assert_ok_and_equal(result, "my_asset_abcd.zip");
+    /// CLI command:
+    /// dra download -s my_asset.tar.gz -o /my/custom-dir/ --notes NOTES.md <REPO>
+    /// output: /my/custom-dir/NOTES.md
+    #[test]
+    fn custom_directory_path() {
+        let output = PathBuf::from("/my/custom-dir/");
+
+        let result = notes_path_from(Some(&output), NO_INSTALL, Path::new("NOTES.md"), is_dir);
+
+        assert_eq!(PathBuf::from("/my/custom-dir/NOTES.md"), result)
     }
 
+    /// CLI command:
+    /// dra download -s my_asset.tar.gz -o /some/path.tar.gz --notes NOTES.md <REPO>
+    /// output: /some/NOTES.md
     #[test]
-    fn untagged_and_wildcard_selection() {
-        let release = any_release(
-            "v1.0.0",
-            vec![
-                "my_asset-v1.0.0_abcd.deb",
-                "my_asset-v1.0.0_abcd.zip",
-                "my_asset-v1.0.0_efgh.zip",
-            ],
-        );
+    fn custom_file_path() {
+        let output = PathBuf::from("/some/path.tar.gz");
 
-        let result = autoselect_asset(release, "my_asset-v{tag}_*.zip");
+        let result = notes_path_from(Some(&output), NO_INSTALL, Path::new("NOTES.md"), not_dir);
 
-        assert_ok_and_equal(result, "my_asset-v1.0.0_abcd.zip");
+        assert_eq!(PathBuf::from("/some/NOTES.md"), result)
     }
 
-    #[test_case("my_asset_v1.0.0-musl.tar.gz"; "literal")]
-    #[test_case("my_asset_v{tag}-musl.tar.gz"; "untagged")]
-    #[test_case("my_asset_*-musl.tar.gz"; "wildcard")]
-    #[test_case("my_asset_v{tag}-*.tar.gz"; "untagged_and_wildcard")]
-    fn nothing_matches(selection: &str) {
-        let release = any_release(
-            "v1.0.0",
-            vec![
-                "my_asset_v1.0.0.deb",
-                "my_asset_v1.0.0.zip",
-                "my_asset.tar.gz",
-            ],
+    #[test]
+    fn absolute_notes_path() {
+        let output = PathBuf::from("/my/custom-dir/");
+
+        let result = notes_path_from(
+            Some(&output),
+            NO_INSTALL,
+            Path::new("/notes/NOTES.md"),
+            is_dir,
         );
 
-        let result = autoselect_asset(release, selection);
+        assert_eq!(PathBuf::from("/notes/NOTES.md"), result)
+    }
 
-        assert_err(result);
+    fn is_dir(_: &Path) -> bool {
+        true
     }
 
-    fn any_release(tag: &str, asset_names: Vec<&str>) -> Release {
-        Release {
-            tag: Tag(tag.into()),
-            assets: asset_names
-                .into_iter()
-                .map(|name| Asset {
-                    name: name.into(),
-                    display_name: None,
-                    download_url: "any".into(),
-                })
-                .collect(),
-        }
+    fn not_dir(_: &Path) -> bool {
+        false
     }
+}
 
-    fn assert_ok_and_equal(result: Result<Asset, HandlerError>, expected_name: &str) {
-        match result {
-            Ok(asset) => assert_eq!(asset.name, expected_name),
-            Err(e) => panic!("Expected Ok, got Err: {:?}", e),
-        }
+#[cfg(test)]
+mod existing_file_path {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(IfExists::Overwrite; "overwrite")]
+    #[test_case(IfExists::Skip; "skip")]
+    #[test_case(IfExists::Error; "error")]
+    #[test_case(IfExists::Rename; "rename")]
+    fn missing_file(policy: IfExists) {
+        let result = existing_file_path(policy, Path::new("/some/path/my_asset.tar.gz"), |_| false);
+
+        assert_ok("/some/path/my_asset.tar.gz", result);
+    }
+
+    #[test]
+    fn overwrite() {
+        let result =
+            existing_file_path(IfExists::Overwrite, Path::new("my_asset.tar.gz"), |_| true);
+
+        assert_ok("my_asset.tar.gz", result);
+    }
+
+    #[test]
+    fn skip() {
+        let result = existing_file_path(IfExists::Skip, Path::new("my_asset.tar.gz"), |_| true);
+
+        assert!(matches!(result, Ok(None)));
+    }
+
+    #[test]
+    fn error() {
+        let result = existing_file_path(IfExists::Error, Path::new("my_asset.tar.gz"), |_| true);
+
+        assert!(result.is_err());
+    }
+
+    #[test_case("/some/path/my_asset.tar.gz", "/some/path/my_asset-2.tar.gz"; "with extensions")]
+    #[test_case("my_asset", "my_asset-2"; "without extension")]
+    #[test_case(".my_asset", ".my_asset-2"; "hidden file")]
+    fn rename(path: &str, expected: &str) {
+        let existing = [PathBuf::from(path), numbered_path(Path::new(path), 1)];
+
+        let result = existing_file_path(IfExists::Rename, Path::new(path), |x| {
+            existing.iter().any(|e| e == x)
+        });
+
+        assert_ok(expected, result);
+    }
+
+    fn assert_ok(expected: &str, result: Result<Option<PathBuf>, HandlerError>) {
+        match result {
+            Ok(Some(path)) => assert_eq!(PathBuf::from(expected), path),
+            other => panic!("Expected Ok(Some), got {:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod check_free_space {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Some(4096); "enough space")]
+    #[test_case(Some(2048); "exact space")]
+    #[test_case(None; "unknown space")]
+    fn ok(available: Option<u64>) {
+        let result = check_free_space("my_asset.tar.gz", 2048, Path::new("/tmp"), available);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn not_enough_space() {
+        let result = check_free_space("my_asset.tar.gz", 2048, Path::new("/tmp"), Some(1024));
+
+        match result {
+            Err(HandlerError::Default(message)) => assert_eq!(
+                "Not enough disk space to download my_asset.tar.gz: 2.00 KiB required, 1.00 KiB available in /tmp",
+                message
+            ),
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
+
+    #[test_case("/home/user/new/dir", "/home/user"; "missing directories")]
+    #[test_case("/home/user", "/home/user"; "existing directory")]
+    #[test_case("new/dir", "."; "relative path")]
+    fn nearest_existing_directory(path: &str, expected: &str) {
+        let existing = ["/", "/home", "/home/user", "."];
+
+        let result =
+            existing_ancestor(Path::new(path), |x| existing.contains(&x.to_str().unwrap()));
+
+        assert_eq!(Some(Path::new(expected)), result);
+    }
+}
+
+#[cfg(test)]
+mod check_download_size {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(2048, Some(2048); "complete")]
+    #[test_case(2048, None; "unknown content length")]
+    fn ok(total_bytes: u64, content_length: Option<u64>) {
+        assert!(check_download_size(total_bytes, content_length).is_ok());
+    }
+
+    #[test]
+    fn incomplete() {
+        let result = check_download_size(512, Some(2048));
+
+        assert_eq!(
+            Err("incomplete download, received 512 of 2048 bytes".to_string()),
+            result
+        );
+    }
+}
+
+#[cfg(test)]
+mod is_in_path {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("/home/user/bin"; "same path")]
+    #[test_case("/home/user/bin/"; "trailing separator")]
+    #[test_case("/HOME/user/bin"; "different case")]
+    fn found(entry: &str) {
+        let path = std::env::join_paths(["/usr/bin", entry]).unwrap();
+
+        assert!(is_in_path(Path::new("/home/user/bin"), Some(path)))
+    }
+
+    #[test]
+    fn not_found() {
+        let path = std::env::join_paths(["/usr/bin"]).unwrap();
+
+        assert!(!is_in_path(Path::new("/home/user/bin"), Some(path)))
+    }
+
+    #[test]
+    fn no_path() {
+        assert!(!is_in_path(Path::new("/home/user/bin"), None))
+    }
+}
+
+#[cfg(test)]
+mod write_stream {
+    use super::*;
+
+    #[test]
+    fn whole_stream() {
+        let mut output = vec![];
+
+        let result = write_stream(
+            "my_asset",
+            &mut b"content".as_slice(),
+            &mut output,
+            Some(7),
+            |_| {},
+        );
+
+        assert_eq!(Some(7), result.ok());
+        assert_eq!(b"content".to_vec(), output);
+    }
+
+    #[test]
+    fn stream_shorter_than_content_length() {
+        let mut output = vec![];
+
+        let result = write_stream(
+            "my_asset",
+            &mut b"content".as_slice(),
+            &mut output,
+            Some(2048),
+            |_| {},
+        );
+
+        assert_eq!(
+            Some("Error downloading my_asset: incomplete download, received 7 of 2048 bytes"),
+            result.as_ref().err().and_then(|x| x.failure_message())
+        );
+    }
+
+    #[test]
+    fn read_error() {
+        let mut stream = FailingReader {
+            content: b"partial",
+            interrupted: true,
+        };
+        let mut output = vec![];
+
+        let result = write_stream("my_asset", &mut stream, &mut output, None, |_| {});
+
+        assert_eq!(
+            Some("Error downloading my_asset: connection reset"),
+            result.as_ref().err().and_then(|x| x.failure_message())
+        );
+        assert_eq!(b"partial".to_vec(), output);
+    }
+
+    /// Interrupted once, then returns the content and fails
+    struct FailingReader {
+        content: &'static [u8],
+        interrupted: bool,
+    }
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if std::mem::take(&mut self.interrupted) {
+                return Err(std::io::Error::from(ErrorKind::Interrupted));
+            }
+            if self.content.is_empty() {
+                return Err(std::io::Error::new(
+                    ErrorKind::ConnectionReset,
+                    "connection reset",
+                ));
+            }
+            let bytes = self.content.len().min(buf.len());
+            buf[..bytes].copy_from_slice(&self.content[..bytes]);
+            self.content = &self.content[bytes..];
+            Ok(bytes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod temporary_download_path {
+    use super::*;
+
+    #[test]
+    fn suffix_is_appended_to_file_name() {
+        let result = temporary_download_path(Path::new("/some/path/my_asset.tar.gz"));
+
+        assert_eq!(PathBuf::from("/some/path/my_asset.tar.gz.part"), result);
+    }
+}
+
+#[cfg(test)]
+mod is_newer_than_release {
+    use super::*;
+    use std::time::Duration;
+
+    const RELEASE: SystemTime = SystemTime::UNIX_EPOCH;
+
+    #[test]
+    fn file_newer_than_release() {
+        let modified = RELEASE + Duration::from_secs(60);
+
+        assert!(is_newer_than_release(Some(modified), Some(RELEASE)))
+    }
+
+    #[test]
+    fn file_older_than_release() {
+        let modified = RELEASE + Duration::from_secs(60);
+        let published_at = modified + Duration::from_secs(60);
+
+        assert!(!is_newer_than_release(Some(modified), Some(published_at)))
+    }
+
+    #[test]
+    fn file_not_exists() {
+        assert!(!is_newer_than_release(None, Some(RELEASE)))
+    }
+
+    #[test]
+    fn release_without_publication_date() {
+        assert!(!is_newer_than_release(Some(RELEASE), None))
+    }
+
+    #[test]
+    fn asset_uploaded_before_publication() {
+        let updated_at = RELEASE + Duration::from_secs(60);
+        let published_at = updated_at + Duration::from_secs(3600);
+        let asset = Asset {
+            updated_at: Some(updated_at),
+            ..super::autoselect_asset::asset("tool.tar.gz")
+        };
+        let path = std::env::temp_dir().join("dra-asset-uploaded-before-publication");
+        std::fs::write(&path, "content").unwrap();
+        set_modified_time(&path, updated_at);
+
+        let result = is_up_to_date(&path, &asset, Some(published_at));
+
+        let _ = std::fs::remove_file(&path);
+        assert!(result)
+    }
+}
+
+#[cfg(test)]
+mod autoselect_asset {
+    use super::*;
+    use crate::cli::result::ErrorKind;
+    use test_case::test_case;
+
+    #[test]
+    fn untagged_selection() {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset_v1.0.0.deb",
+                "my_asset_v1.0.0.zip",
+                "my_asset_v1.0.0.tar.gz",
+            ],
+        );
+
+        let result = autoselect_asset(&release.tag, release.assets, "my_asset_v{tag}.zip");
+
+        assert_ok_and_equal(result, "my_asset_v1.0.0.zip");
+    }
+
+    #[test]
+    fn literal_selection() {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset_v1.0.0.deb",
+                "my_asset_v1.0.0.zip",
+                "my_asset.tar.gz",
+            ],
+        );
+
+        let result = autoselect_asset(&release.tag, release.assets, "my_asset.tar.gz");
+
+        assert_ok_and_equal(result, "my_asset.tar.gz");
+    }
+
+    #[test]
+    fn wildcard_selection() {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset_abcd.deb",
+                "my_asset_abcd.zip",
+                "my_asset_efgh.zip",
+            ],
+        );
+
+        let result = autoselect_asset(&release.tag, release.assets, "my_asset_*.zip");
+
+        assert_ok_and_equal(result, "my_asset_abcd.zip");
+    }
+
+    #[test]
+    fn untagged_and_wildcard_selection() {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset-v1.0.0_abcd.deb",
+                "my_asset-v1.0.0_abcd.zip",
+                "my_asset-v1.0.0_efgh.zip",
+            ],
+        );
+
+        let result = autoselect_asset(&release.tag, release.assets, "my_asset-v{tag}_*.zip");
+
+        assert_ok_and_equal(result, "my_asset-v1.0.0_abcd.zip");
+    }
+
+    #[test_case("my_asset_v1.0.0-musl.tar.gz"; "literal")]
+    #[test_case("my_asset_v{tag}-musl.tar.gz"; "untagged")]
+    #[test_case("my_asset_*-musl.tar.gz"; "wildcard")]
+    #[test_case("my_asset_v{tag}-*.tar.gz"; "untagged_and_wildcard")]
+    #[test_case(r"regex:.*-musl\.tar\.gz"; "regex")]
+    fn nothing_matches(selection: &str) {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset_v1.0.0.deb",
+                "my_asset_v1.0.0.zip",
+                "my_asset.tar.gz",
+            ],
+        );
+
+        let result = autoselect_asset(&release.tag, release.assets, selection);
+
+        assert_err(result);
+    }
+
+    #[test_case("my_asset_(v1).zip"; "parenthesis")]
+    #[test_case("my_asset[v1].zip"; "brackets")]
+    #[test_case("my_asset_v1.0.0.tar.gz"; "dot")]
+    fn regex_characters_are_literal(selection: &str) {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset_(v1)_zip",
+                "my_asset_v1.0.0-tar.gz",
+                "my_assetv.zip",
+            ],
+        );
+
+        let result = autoselect_asset(&release.tag, release.assets, selection);
+
+        match result {
+            Err(HandlerError::Failure(ErrorKind::NotFound, message)) => {
+                assert_eq!(format!("No asset found for {}", selection), message)
+            }
+            other => panic!("Expected not found error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_regex() {
+        let release = any_release("v1.0.0", vec!["my_asset_v1.0.0.zip"]);
+
+        let result = autoselect_asset(&release.tag, release.assets, "regex:my_asset_(v1");
+
+        match result {
+            Err(HandlerError::Failure(ErrorKind::Usage, message)) => {
+                assert!(
+                    message.starts_with("Invalid regex pattern: "),
+                    "{}",
+                    message
+                )
+            }
+            other => panic!("Expected invalid regex error, got {:?}", other),
+        }
+    }
+
+    #[test_case(r"regex:.*linux.*musl.*\.tar\.gz"; "regex")]
+    #[test_case(r"regex:my_asset_v{tag}-x86_64-linux-(musl|static)\.tar\.gz"; "untagged_and_regex")]
+    fn regex_selection(selection: &str) {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset_v1.0.0-x86_64-linux-gnu.tar.gz",
+                "my_asset_v1.0.0-x86_64-linux-musl.tar.gz",
+                "my_asset_v1.0.0-x86_64-linux-musl.tar.gz.sha256",
+            ],
+        );
+
+        let result = autoselect_asset(&release.tag, release.assets, selection);
+
+        assert_ok_and_equal(result, "my_asset_v1.0.0-x86_64-linux-musl.tar.gz");
+    }
+
+    #[test]
+    fn regex_selection_multiple_matches() {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset_v1.0.0-x86_64-linux-gnu.tar.gz",
+                "my_asset_v1.0.0-x86_64-linux-musl.tar.gz",
+            ],
+        );
+
+        let result = autoselect_asset(&release.tag, release.assets, r"regex:.*linux.*\.tar\.gz");
+
+        match result {
+            Err(HandlerError::Default(message)) => assert_eq!(
+                message,
+                r"Multiple assets found for regex:.*linux.*\.tar\.gz: my_asset_v1.0.0-x86_64-linux-gnu.tar.gz, my_asset_v1.0.0-x86_64-linux-musl.tar.gz"
+            ),
+            other => panic!("Expected multiple assets error, got {:?}", other),
+        }
+    }
+
+    #[test_case(vec!["my_asset_v{tag}.zip", "my_asset_v{tag}.tar.gz"], "my_asset_v1.0.0.tar.gz"; "second pattern")]
+    #[test_case(vec!["my_asset_v{tag}.deb", "my_asset_v{tag}.tar.gz"], "my_asset_v1.0.0.deb"; "first pattern wins")]
+    fn fallback_selection(selections: Vec<&str>, expected: &str) {
+        let release = any_release(
+            "v1.0.0",
+            vec!["my_asset_v1.0.0.deb", "my_asset_v1.0.0.tar.gz"],
+        );
+        let selections: Vec<_> = selections.into_iter().map(String::from).collect();
+
+        let result = fallback_select_asset(release, &selections);
+
+        assert_ok_and_equal(result, expected);
+    }
+
+    #[test]
+    fn fallback_selection_nothing_matches() {
+        let release = any_release("v1.0.0", vec!["my_asset_v1.0.0.deb"]);
+        let selections = vec!["a.zip".to_string(), "b.tar.gz".to_string()];
+
+        let result = fallback_select_asset(release, &selections);
+
+        assert_err(result);
+    }
+
+    pub(super) fn any_release(tag: &str, asset_names: Vec<&str>) -> Release {
+        Release {
+            published_at: None,
+            body: None,
+            pre_release: false,
+            tag: Tag(tag.into()),
+            assets: asset_names.into_iter().map(asset).collect(),
+        }
+    }
+
+    pub(super) fn asset(name: &str) -> Asset {
+        Asset {
+            name: name.into(),
+            display_name: None,
+            content_type: None,
+            size: None,
+            download_count: None,
+            updated_at: None,
+            download_url: "any".into(),
+            api_url: None,
+        }
+    }
+
+    pub(super) fn assert_ok_and_equal(result: Result<Asset, HandlerError>, expected_name: &str) {
+        match result {
+            Ok(asset) => assert_eq!(asset.name, expected_name),
+            Err(e) => panic!("Expected Ok, got Err: {:?}", e),
+        }
     }
 
     fn assert_err(result: Result<Asset, HandlerError>) {
@@ -526,3 +2884,284 @@ mod autoselect_asset {
         }
     }
 }
+
+#[cfg(test)]
+mod glob_select_asset {
+    use super::autoselect_asset::{any_release, assert_ok_and_equal};
+    use super::*;
+    use crate::cli::result::ErrorKind;
+    use test_case::test_case;
+
+    #[test_case("*linux-x86_64*.tar.gz"; "wildcard")]
+    #[test_case("my_asset_v{tag}-linux-x86_64?.tar.gz"; "untagged")]
+    #[test_case("my_asset_*-linux-[xy]86_64*.tar.gz"; "character class")]
+    fn single_match(glob: &str) {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset_v1.0.0-linux-aarch64.tar.gz",
+                "my_asset_v1.0.0-linux-x86_64.zip",
+                "my_asset_v1.0.0-linux-x86_64_.tar.gz",
+            ],
+        );
+
+        let result = glob_select_asset(release, glob);
+
+        assert_ok_and_equal(result, "my_asset_v1.0.0-linux-x86_64_.tar.gz");
+    }
+
+    #[test]
+    fn ambiguous_matches() {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset-linux-x86_64-gnu.tar.gz",
+                "my_asset-linux-x86_64-musl.tar.gz",
+                "my_asset-linux-aarch64-musl.tar.gz",
+            ],
+        );
+
+        let result = glob_select_asset(release, "*linux-x86_64*.tar.gz");
+
+        assert_error_message(
+            result,
+            "Multiple assets found for *linux-x86_64*.tar.gz: my_asset-linux-x86_64-gnu.tar.gz, my_asset-linux-x86_64-musl.tar.gz",
+        );
+    }
+
+    #[test]
+    fn nothing_matches() {
+        let release = any_release("v1.0.0", vec!["my_asset-linux-aarch64.tar.gz"]);
+
+        let result = glob_select_asset(release, "*linux-x86_64*.tar.gz");
+
+        match result {
+            Err(HandlerError::Failure(ErrorKind::NotFound, message)) => {
+                assert_eq!(message, "No asset found for *linux-x86_64*.tar.gz")
+            }
+            other => panic!("Expected not found error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_glob() {
+        let release = any_release("v1.0.0", vec!["my_asset-linux-aarch64.tar.gz"]);
+
+        let result = glob_select_asset(release, "my_asset-[linux");
+
+        assert!(result.is_err());
+    }
+
+    fn assert_error_message(result: Result<Asset, HandlerError>, expected: &str) {
+        match result {
+            Err(HandlerError::Default(message)) => assert_eq!(message, expected),
+            other => panic!("Expected error {}, got {:?}", expected, other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod asset_filter {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(None, None, "helloworld.tar.gz", true; "no filters")]
+    #[test_case(Some(vec!["*.tar.gz"]), None, "helloworld.tar.gz", true; "included")]
+    #[test_case(Some(vec!["*.zip", "*.deb"]), None, "helloworld.tar.gz", false; "not included")]
+    #[test_case(None, Some(vec!["*.sha256"]), "helloworld.tar.gz.sha256", false; "excluded")]
+    #[test_case(Some(vec!["helloworld*"]), Some(vec!["*.sha256"]), "helloworld.tar.gz.sha256", false; "exclude wins")]
+    fn matches(
+        include: Option<Vec<&str>>,
+        exclude: Option<Vec<&str>>,
+        asset_name: &str,
+        expected: bool,
+    ) {
+        let to_strings =
+            |x: Option<Vec<&str>>| x.map(|x| x.into_iter().map(String::from).collect());
+        let filter = AssetFilter::new(to_strings(include), to_strings(exclude));
+
+        assert_eq!(expected, filter.matches(asset_name));
+    }
+}
+
+#[cfg(test)]
+mod source_code_asset {
+    use super::autoselect_asset::asset;
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(SourceArchive::Tar, "dra-tests-1.0.0-source-code.tar.gz"; "tar")]
+    #[test_case(SourceArchive::Zip, "dra-tests-1.0.0-source-code.zip"; "zip")]
+    fn found(archive: SourceArchive, expected: &str) {
+        let release = Release {
+            published_at: None,
+            body: None,
+            pre_release: false,
+            tag: Tag("1.0.0".into()),
+            assets: vec![
+                Asset {
+                    display_name: Some(SOURCE_CODE_TAR_GZ.into()),
+                    ..asset("dra-tests-1.0.0-source-code.tar.gz")
+                },
+                Asset {
+                    display_name: Some(SOURCE_CODE_ZIP.into()),
+                    ..asset("dra-tests-1.0.0-source-code.zip")
+                },
+            ],
+        };
+
+        let result = source_code_asset(release, archive);
+
+        match result {
+            Ok(asset) => assert_eq!(asset.name, expected),
+            Err(e) => panic!("Expected Ok, got Err: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn asset_with_same_name_is_not_source_code() {
+        let release = Release {
+            published_at: None,
+            body: None,
+            pre_release: false,
+            tag: Tag("1.0.0".into()),
+            assets: vec![asset("Source code (zip)")],
+        };
+
+        let result = source_code_asset(release, SourceArchive::Zip);
+
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod companion_checksums {
+    use super::autoselect_asset::asset;
+    use super::*;
+
+    #[test]
+    fn asset_and_release_checksums() {
+        let assets = assets(vec![
+            "foo-linux.tar.gz",
+            "foo-linux.tar.gz.sha256",
+            "foo-linux.tar.gz.SHA512",
+            "foo-linux.tar.gz.sig",
+            "foo-macos.tar.gz.sha256",
+            "foo_1.0.0_checksums.txt",
+            "SHA256SUMS",
+        ]);
+
+        let result = companion_checksums("foo-linux.tar.gz", assets);
+
+        assert_eq!(
+            vec![
+                "foo-linux.tar.gz.sha256",
+                "foo-linux.tar.gz.SHA512",
+                "foo_1.0.0_checksums.txt",
+                "SHA256SUMS"
+            ],
+            result.iter().map(|x| x.name.as_str()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn no_checksums() {
+        let assets = assets(vec!["foo-linux.tar.gz", "foo-linux.tar.gz.sig"]);
+
+        let result = companion_checksums("foo-linux.tar.gz", assets);
+
+        assert!(result.is_empty());
+    }
+
+    fn assets(names: Vec<&str>) -> Vec<Asset> {
+        names.into_iter().map(asset).collect()
+    }
+}
+
+#[cfg(test)]
+mod filter_by_content_type {
+    use super::autoselect_asset::asset;
+    use super::*;
+    use crate::cli::result::ErrorKind;
+    use test_case::test_case;
+
+    #[test_case("application/gzip", vec!["helloworld.tar.gz"]; "exact")]
+    #[test_case("APPLICATION/GZIP", vec!["helloworld.tar.gz"]; "case insensitive")]
+    #[test_case("application/*", vec!["helloworld.tar.gz", "helloworld.zip"]; "wildcard")]
+    fn found(content_type: &str, expected: Vec<&str>) {
+        let result = filter_by_content_type(any_release(), content_type);
+
+        match result {
+            Ok(release) => assert_eq!(
+                expected,
+                release
+                    .assets
+                    .iter()
+                    .map(|x| x.name.as_str())
+                    .collect::<Vec<_>>()
+            ),
+            Err(e) => panic!("Expected Ok, got Err: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn not_found() {
+        let result = filter_by_content_type(any_release(), "application/x-debian-package");
+
+        match result {
+            Err(HandlerError::Failure(ErrorKind::NotFound, message)) => assert_eq!(
+                message,
+                "No asset found with content type application/x-debian-package. Available content types: application/gzip, application/zip"
+            ),
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
+
+    fn any_release() -> Release {
+        let with_content_type = |name: &str, content_type: &str| Asset {
+            content_type: Some(content_type.into()),
+            ..asset(name)
+        };
+        Release {
+            published_at: None,
+            body: None,
+            pre_release: false,
+            tag: Tag("v1.0.0".into()),
+            assets: vec![
+                with_content_type("helloworld.tar.gz", "application/gzip"),
+                with_content_type("helloworld.zip", "application/zip"),
+                asset("helloworld"),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod asset_from_url {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("https://github.com/devmatteini/dra-tests/releases/download/0.1.5/helloworld.tar.gz", "helloworld.tar.gz"; "browser download url")]
+    #[test_case("https://example.com/helloworld%201.0.zip?token=abc", "helloworld 1.0.zip"; "encoded name and query")]
+    fn found(url: &str, expected: &str) {
+        let result = asset_from_url(url);
+
+        match result {
+            Ok(asset) => {
+                assert_eq!(expected, asset.name);
+                assert_eq!(url, asset.download_url);
+            }
+            Err(e) => panic!("Expected Ok, got Err: {:?}", e),
+        }
+    }
+
+    #[test_case("https://example.com/"; "empty")]
+    #[test_case("https://example.com/..%2F..%2F.bashrc"; "encoded parent directory")]
+    #[test_case("https://example.com/dir%5Ctool.exe"; "encoded backslash")]
+    #[test_case("https://example.com/%2E%2E"; "encoded dots")]
+    fn no_asset_name(url: &str) {
+        let result = asset_from_url(url);
+
+        assert!(result.is_err());
+    }
+}
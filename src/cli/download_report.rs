@@ -0,0 +1,108 @@
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Result of `dra download --json`, printed on stdout when the command ends
+#[derive(Debug, Default, Serialize)]
+pub struct DownloadReport {
+    pub assets: Vec<DownloadedAsset>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DownloadedAsset {
+    pub name: String,
+    /// `None` when the asset is downloaded without fetching its release (e.g. `--asset-url`)
+    pub tag: Option<String>,
+    pub size: u64,
+    pub sha256: String,
+    /// `None` when the asset is removed after the installation
+    pub path: Option<PathBuf>,
+    pub executables: Vec<PathBuf>,
+}
+
+impl DownloadReport {
+    pub fn add(&mut self, asset: DownloadedAsset) {
+        self.assets.push(asset);
+    }
+
+    /// The downloaded file is a temporary file that is removed after the installation
+    pub fn installed(&mut self, path: &Path, executables: &[PathBuf]) {
+        let asset = self
+            .assets
+            .iter_mut()
+            .find(|x| x.path.as_deref() == Some(path));
+        if let Some(asset) = asset {
+            asset.path = None;
+            asset.executables = executables.to_vec();
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Hash of the content while it's being downloaded, so the file is never read twice
+#[derive(Default)]
+pub struct ContentHasher(Sha256);
+
+impl ContentHasher {
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    pub fn finish(self) -> String {
+        self.0
+            .finalize()
+            .iter()
+            .map(|x| format!("{:02x}", x))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256() {
+        let mut hasher = ContentHasher::default();
+        hasher.update(b"hello ");
+        hasher.update(b"world");
+
+        assert_eq!(
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+            hasher.finish()
+        );
+    }
+
+    #[test]
+    fn installed_asset() {
+        let mut report = DownloadReport::default();
+        report.add(any_asset("/tmp/dra-1234"));
+        report.add(any_asset("/tmp/other"));
+
+        report.installed(
+            Path::new("/tmp/dra-1234"),
+            &[PathBuf::from("/usr/bin/tool")],
+        );
+
+        assert_eq!(None, report.assets[0].path);
+        assert_eq!(
+            vec![PathBuf::from("/usr/bin/tool")],
+            report.assets[0].executables
+        );
+        assert_eq!(Some(PathBuf::from("/tmp/other")), report.assets[1].path);
+    }
+
+    fn any_asset(path: &str) -> DownloadedAsset {
+        DownloadedAsset {
+            name: "tool.tar.gz".to_string(),
+            tag: Some("v1.0.0".to_string()),
+            size: 1024,
+            sha256: "ANY_SHA256".to_string(),
+            path: Some(PathBuf::from(path)),
+            executables: vec![],
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::cli::result::HandlerError;
+use crate::github::repository::Repository;
+use std::process::Command;
+
+pub const DEFAULT_REMOTE: &str = "origin";
+
+/// Repository of a remote of the git checkout in the current directory.
+/// `git remote get-url` applies the `url.<base>.insteadOf` rewrites of the git configuration
+pub fn repository_of(remote: &str) -> Result<Repository, HandlerError> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", remote])
+        .output()
+        .map_err(|e| {
+            HandlerError::new(format!(
+                "No repository given and git is not available to read the '{}' remote: {}",
+                remote, e
+            ))
+        })?;
+    if !output.status.success() {
+        return Err(HandlerError::new(format!(
+            "No repository given and the '{}' git remote of the current directory is not available: {}",
+            remote,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let url = String::from_utf8_lossy(&output.stdout);
+    let url = url.trim();
+    Repository::try_parse(url).map_err(|e| {
+        HandlerError::new(format!(
+            "The '{}' git remote ({}) is not a repository: {}",
+            remote, url, e
+        ))
+    })
+}
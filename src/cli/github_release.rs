@@ -1,34 +1,319 @@
 use crate::cli::color::Color;
 use crate::cli::result::HandlerError;
+use crate::cli::root_command::LatestStrategy;
 use crate::cli::spinner::Spinner;
-use crate::github::client::GithubClient;
+use crate::github::client::{GithubClient, resolve_api_url};
 use crate::github::error::GithubError;
-use crate::github::release::{Release, Tag};
+use crate::github::graphql::ReleaseQuery;
+use crate::github::metadata_cache::MetadataCache;
+use crate::github::release::{Release, ReleaseFilter, Tag};
 use crate::github::repository::Repository;
+use log::debug;
+use semver::VersionReq;
+use wildmatch::WildMatch;
 
+/// Client of the commands that only read release information (e.g. `dra untag`)
+pub fn github_client_for(repository: &Repository) -> GithubClient {
+    let forge = repository.forge();
+    GithubClient::from_environment_for(repository, None)
+        .with_forge(forge)
+        .with_api_url(resolve_api_url(None, repository))
+        .with_metadata_cache(MetadataCache::from_environment())
+}
+
+/// Without a tag, the latest release is resolved by the strategy (the newest one accepted by
+/// the filter when pre-releases or drafts are considered)
 pub fn fetch_release_for(
     github: &GithubClient,
     repository: &Repository,
     tag: Option<&Tag>,
+    filter: ReleaseFilter,
+    strategy: LatestStrategy,
 ) -> Result<Release, HandlerError> {
     let spinner = Spinner::empty_layout();
     spinner.show();
 
-    let release = github.get_release(repository, tag).map_err(release_error)?;
+    let version_req = tag.and_then(Tag::version_req);
+    let wildcard = tag.and_then(Tag::wildcard);
+    debug!(
+        "Fetching release of {} (tag: {:?}, filter: {:?}, strategy: {:?})",
+        repository,
+        tag.map(|x| x.0.as_str()),
+        filter,
+        strategy
+    );
+    // Releases from the list may miss some assets
+    let complete = |release| github.complete_release(repository, release);
+    let release = match (tag, strategy) {
+        (Some(_), _) if wildcard.is_some() => github
+            .list_releases(repository, filter)
+            .and_then(|x| {
+                newest_matching(x, wildcard.as_ref())
+                    .ok_or(GithubError::RepositoryOrReleaseNotFound)
+            })
+            .and_then(complete),
+        (Some(_), _) if version_req.is_some() => github
+            .list_releases(repository, filter)
+            .and_then(|x| {
+                highest_semver(x, version_req.as_ref())
+                    .ok_or(GithubError::RepositoryOrReleaseNotFound)
+            })
+            .and_then(complete),
+        (None, LatestStrategy::Semver) => github
+            .list_releases(repository, filter)
+            .and_then(|x| highest_semver(x, None).ok_or(GithubError::RepositoryOrReleaseNotFound))
+            .and_then(complete),
+        (None, LatestStrategy::Date) if filter.pre_release || filter.draft => {
+            github.get_newest_release(repository, filter)
+        }
+        (Some(tag), _) if filter.draft => github
+            .find_release_by_tag(repository, tag)
+            .and_then(complete),
+        (tag, _) => github.get_release(repository, tag),
+    }
+    .map_err(release_error)?;
 
     let message = format!("Release tag is {}", Color::new(&release.tag.0).bold());
     spinner.finish_with_message(&message);
     Ok(release)
 }
 
+/// Release (the latest one without a tag) fetched with a single GraphQL request
+pub fn fetch_release_graphql(
+    github: &GithubClient,
+    repository: &Repository,
+    tag: Option<&Tag>,
+) -> Result<Release, HandlerError> {
+    let spinner = Spinner::empty_layout();
+    spinner.show();
+
+    let query = ReleaseQuery {
+        repository: repository.clone(),
+        tag: tag.cloned(),
+    };
+    let result = github
+        .graphql_releases(&[query])
+        .and_then(|x| {
+            x.into_iter()
+                .next()
+                .ok_or(GithubError::RepositoryOrReleaseNotFound)
+        })
+        .map_err(release_error)?;
+    debug!(
+        "Latest release of {} is {:?}",
+        repository,
+        result.latest_tag.as_ref().map(|x| x.0.as_str())
+    );
+    let release = result
+        .release
+        .ok_or_else(|| release_error(GithubError::RepositoryOrReleaseNotFound))?;
+
+    let message = format!("Release tag is {}", Color::new(&release.tag.0).bold());
+    spinner.finish_with_message(&message);
+    Ok(release)
+}
+
+pub fn fetch_releases_for(
+    github: &GithubClient,
+    repository: &Repository,
+    filter: ReleaseFilter,
+) -> Result<Vec<Release>, HandlerError> {
+    let spinner = Spinner::empty_layout();
+    spinner.show();
+
+    let releases = github
+        .list_releases(repository, filter)
+        .map_err(release_error)?;
+
+    let message = format!(
+        "Found {} releases",
+        Color::new(&releases.len().to_string()).bold()
+    );
+    spinner.finish_with_message(&message);
+    Ok(releases)
+}
+
+/// `releases` must be sorted from the newest to the oldest, like GitHub does
+fn newest_matching(releases: Vec<Release>, wildcard: Option<&WildMatch>) -> Option<Release> {
+    releases
+        .into_iter()
+        .find(|x| wildcard.is_some_and(|pattern| pattern.matches(&x.tag.0)))
+}
+
+/// Tags that are not semantic versions, or don't match the range (if any), are ignored
+fn highest_semver(releases: Vec<Release>, range: Option<&VersionReq>) -> Option<Release> {
+    releases
+        .into_iter()
+        .filter_map(|x| x.tag.semver().map(|version| (version, x)))
+        .filter(|(version, _)| range.is_none_or(|x| x.matches(version)))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, release)| release)
+}
+
+/// Releases between the two tags (included), from the oldest to the newest.
+/// `releases` must be sorted from the newest to the oldest, like GitHub does
+pub fn releases_in_range(
+    releases: Vec<Release>,
+    from: &Tag,
+    to: &Tag,
+) -> Result<Vec<Release>, HandlerError> {
+    let position = |tag: &Tag| {
+        releases
+            .iter()
+            .position(|x| x.tag.0 == tag.0)
+            .ok_or_else(|| HandlerError::not_found(format!("Release {} not found", tag.0)))
+    };
+    let (from, to) = (position(from)?, position(to)?);
+    let (newest, oldest) = (from.min(to), from.max(to));
+    Ok(releases
+        .into_iter()
+        .skip(newest)
+        .take(oldest - newest + 1)
+        .rev()
+        .collect())
+}
+
 fn release_error(e: GithubError) -> HandlerError {
-    HandlerError::new(format!("Error fetching release: {}", e))
+    HandlerError::github(format!("Error fetching release: {}", e), &e)
 }
 
 pub fn check_has_assets(release: &Release) -> Result<(), HandlerError> {
     if release.assets.is_empty() {
-        Err(HandlerError::new("No assets found for this release".into()))
+        Err(HandlerError::not_found(
+            "No assets found for this release".into(),
+        ))
     } else {
         Ok(())
     }
 }
+
+#[cfg(test)]
+fn releases(tags: &[&str]) -> Vec<Release> {
+    tags.iter()
+        .map(|tag| Release {
+            tag: Tag(tag.to_string()),
+            assets: vec![],
+            published_at: None,
+            body: None,
+            pre_release: false,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod releases_in_range {
+    use super::*;
+    use crate::cli::result::ErrorKind;
+    use test_case::test_case;
+
+    #[test_case("v1.0.0", "v1.2.0"; "oldest to newest")]
+    #[test_case("v1.2.0", "v1.0.0"; "newest to oldest")]
+    fn found(from: &str, to: &str) {
+        let result = releases_in_range(any_releases(), &Tag(from.into()), &Tag(to.into()));
+
+        match result {
+            Ok(releases) => assert_eq!(
+                vec!["v1.0.0", "v1.1.0", "v1.2.0"],
+                releases
+                    .iter()
+                    .map(|x| x.tag.0.as_str())
+                    .collect::<Vec<_>>()
+            ),
+            Err(e) => panic!("Expected Ok, got Err: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn single_release() {
+        let result =
+            releases_in_range(any_releases(), &Tag("v1.1.0".into()), &Tag("v1.1.0".into()));
+
+        match result {
+            Ok(releases) => assert_eq!(1, releases.len()),
+            Err(e) => panic!("Expected Ok, got Err: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn tag_not_found() {
+        let result =
+            releases_in_range(any_releases(), &Tag("v0.1.0".into()), &Tag("v1.1.0".into()));
+
+        match result {
+            Err(HandlerError::Failure(ErrorKind::NotFound, message)) => {
+                assert_eq!("Release v0.1.0 not found", message)
+            }
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
+
+    fn any_releases() -> Vec<Release> {
+        releases(&["v1.3.0", "v1.2.0", "v1.1.0", "v1.0.0"])
+    }
+}
+
+#[cfg(test)]
+mod highest_semver {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn found() {
+        let releases = releases(&["v1.4.3", "v2.1.0", "2.0.5", "nightly", "v1.10.0"]);
+
+        let result = highest_semver(releases, None);
+
+        assert_eq!(Some("v2.1.0".to_string()), result.map(|x| x.tag.0));
+    }
+
+    #[test]
+    fn pre_release_is_lower_than_release() {
+        let releases = releases(&["v2.0.0-rc.1", "v2.0.0", "v1.9.0"]);
+
+        let result = highest_semver(releases, None);
+
+        assert_eq!(Some("v2.0.0".to_string()), result.map(|x| x.tag.0));
+    }
+
+    #[test]
+    fn no_semantic_version() {
+        let result = highest_semver(releases(&["nightly", "latest"]), None);
+
+        assert!(result.is_none());
+    }
+
+    #[test_case("^1.4", Some("v1.10.0"); "caret")]
+    #[test_case(">=2, <2.1", Some("2.0.5"); "comparators")]
+    #[test_case("^3", None; "no match")]
+    fn found_in_range(range: &str, expected: Option<&str>) {
+        let releases = releases(&["v1.4.3", "v2.1.0", "2.0.5", "nightly", "v1.10.0"]);
+        let range = VersionReq::parse(range).unwrap();
+
+        let result = highest_semver(releases, Some(&range));
+
+        assert_eq!(expected.map(String::from), result.map(|x| x.tag.0));
+    }
+}
+
+#[cfg(test)]
+mod newest_matching {
+    use super::*;
+
+    #[test]
+    fn found() {
+        let releases = releases(&["v1.3.0", "v1.2.10", "v1.2.9"]);
+
+        let result = newest_matching(releases, Some(&WildMatch::new("v1.2.*")));
+
+        assert_eq!(Some("v1.2.10".to_string()), result.map(|x| x.tag.0));
+    }
+
+    #[test]
+    fn not_found() {
+        let releases = releases(&["v1.3.0", "v1.2.10"]);
+
+        let result = newest_matching(releases, Some(&WildMatch::new("v2.*")));
+
+        assert!(result.is_none());
+    }
+}
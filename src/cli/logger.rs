@@ -0,0 +1,61 @@
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Logs are written to stderr, so they never mix with the output of `-o -` or `--json`
+struct StderrLogger {
+    /// Logs of dependencies (e.g. the HTTP client) are shown only with the highest verbosity
+    dependencies: bool,
+}
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+            && (self.dependencies || metadata.target().starts_with(env!("CARGO_CRATE_NAME")))
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!(
+                "[{}] {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+pub fn init(verbose: u8, quiet: bool) {
+    let logger = StderrLogger {
+        dependencies: verbose >= 3,
+    };
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(level_filter(verbose, quiet));
+    }
+}
+
+fn level_filter(verbose: u8, quiet: bool) -> LevelFilter {
+    match (quiet, verbose) {
+        (true, _) => LevelFilter::Error,
+        (false, 0) => LevelFilter::Warn,
+        (false, 1) => LevelFilter::Info,
+        (false, 2) => LevelFilter::Debug,
+        (false, _) => LevelFilter::Trace,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0, false, LevelFilter::Warn; "default")]
+    #[test_case(1, false, LevelFilter::Info; "verbose")]
+    #[test_case(2, false, LevelFilter::Debug; "very verbose")]
+    #[test_case(5, false, LevelFilter::Trace; "trace")]
+    #[test_case(0, true, LevelFilter::Error; "quiet")]
+    fn level(verbose: u8, quiet: bool, expected: LevelFilter) {
+        assert_eq!(expected, level_filter(verbose, quiet));
+    }
+}
@@ -1,10 +1,22 @@
+pub mod assets_handler;
+pub mod auth_handler;
 pub mod color;
 pub mod completion_handler;
+pub mod doctor_handler;
 pub mod download_handler;
+mod download_report;
+mod git_remote;
 mod github_release;
+pub mod logger;
+pub mod notes_handler;
+pub mod output;
 mod progress_bar;
+mod rate_limit;
+pub mod releases_handler;
 pub mod result;
 pub mod root_command;
 mod select_assets;
 mod spinner;
+mod table;
+pub mod tags_handler;
 pub mod untag_handler;
@@ -0,0 +1,133 @@
+use crate::cli::color::Color;
+use crate::cli::github_release::{fetch_release_for, github_client_for};
+use crate::cli::output;
+use crate::cli::output::message;
+use crate::cli::result::HandlerResult;
+use crate::cli::root_command::LatestStrategy;
+use crate::github::release::{ReleaseFilter, Tag};
+use crate::github::repository::Repository;
+use regex::{Captures, Regex};
+use std::sync::LazyLock;
+
+pub struct NotesHandler {
+    repository: Repository,
+    tag: Option<Tag>,
+    raw: bool,
+}
+
+impl NotesHandler {
+    pub fn new(repository: Repository, tag: Option<String>, raw: bool) -> Self {
+        Self {
+            tag: tag.or(repository.tag.clone()).map(Tag),
+            repository,
+            raw,
+        }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        if self.raw {
+            output::reserve_stdout();
+        }
+        let github = github_client_for(&self.repository);
+        let release = fetch_release_for(
+            &github,
+            &self.repository,
+            self.tag.as_ref(),
+            ReleaseFilter::default(),
+            LatestStrategy::default(),
+        )?;
+        let notes = release.body.as_deref().unwrap_or_default();
+        if notes.trim().is_empty() {
+            message!("Release {} has no release notes", release.tag.0);
+        } else if self.raw {
+            println!("{}", notes);
+        } else {
+            print!("{}", render(notes));
+        }
+        Ok(())
+    }
+}
+
+static HEADING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^#{1,6}\s+(.*?)\s*#*$").unwrap());
+static LIST_ITEM: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\s*)[-*+]\s+(.*)$").unwrap());
+static RULE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*([-*_]\s*){3,}$").unwrap());
+static INLINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*\*([^*]+)\*\*|__([^_]+)__|`([^`]+)`|!?\[([^\]]*)\]\(([^)\s]+)[^)]*\)").unwrap()
+});
+
+/// Basic terminal rendering of the markdown release notes: headings and bold text are bold,
+/// inline code and code blocks are highlighted, list items get a bullet and links show their url
+fn render(markdown: &str) -> String {
+    let mut code_block = false;
+    markdown
+        .lines()
+        .filter_map(|line| {
+            if line.trim_start().starts_with("```") {
+                code_block = !code_block;
+                return None;
+            }
+            let line = if code_block {
+                format!("    {}", Color::new(line).yellow())
+            } else if let Some(heading) = HEADING.captures(line) {
+                Color::new(&inline(&heading[1])).bold().to_string()
+            } else if RULE.is_match(line) {
+                "─".repeat(40)
+            } else if let Some(item) = LIST_ITEM.captures(line) {
+                format!("{}• {}", &item[1], inline(&item[2]))
+            } else {
+                inline(line)
+            };
+            Some(format!("{}\n", line))
+        })
+        .collect()
+}
+
+fn inline(text: &str) -> String {
+    INLINE
+        .replace_all(text, |x: &Captures| {
+            if let Some(bold) = x.get(1).or(x.get(2)) {
+                Color::new(bold.as_str()).bold().to_string()
+            } else if let Some(code) = x.get(3) {
+                Color::new(code.as_str()).yellow().to_string()
+            } else {
+                let text = &x[4];
+                let url = &x[5];
+                if text.is_empty() || text == url {
+                    url.to_string()
+                } else {
+                    format!("{} ({})", text, url)
+                }
+            }
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("## What's Changed", "What's Changed\n"; "heading")]
+    #[test_case("# v1.0.0 #", "v1.0.0\n"; "closed heading")]
+    #[test_case("* Fix **crash** on start", "• Fix crash on start\n"; "list item")]
+    #[test_case("  - nested", "  • nested\n"; "nested list item")]
+    #[test_case("Use `dra download`", "Use dra download\n"; "inline code")]
+    #[test_case("See [#12](https://github.com/o/r/pull/12)", "See #12 (https://github.com/o/r/pull/12)\n"; "link")]
+    #[test_case("<https://example.com>", "<https://example.com>\n"; "autolink")]
+    #[test_case("---", format!("{}\n", "─".repeat(40)); "horizontal rule")]
+    fn render_line(markdown: &str, expected: impl Into<String>) {
+        dialoguer::console::set_colors_enabled(false);
+
+        assert_eq!(expected.into(), render(markdown));
+    }
+
+    #[test]
+    fn code_block() {
+        dialoguer::console::set_colors_enabled(false);
+        let markdown = "Install with:\n```shell\ndra download -i o/r\n```\nDone";
+
+        let result = render(markdown);
+
+        assert_eq!("Install with:\n    dra download -i o/r\nDone\n", result);
+    }
+}
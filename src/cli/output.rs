@@ -0,0 +1,38 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Reserve stdout for the downloaded asset (`-o -`) or the JSON report (`--json`),
+/// so that messages are written to stderr
+pub fn reserve_stdout() {
+    STDOUT_RESERVED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_stdout_reserved() -> bool {
+    STDOUT_RESERVED.load(Ordering::Relaxed)
+}
+
+/// With `--quiet`, messages and progress bars are hidden
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Like `println!`, but it writes to stderr when stdout is reserved and nothing with `--quiet`
+macro_rules! message {
+    ($($arg:tt)*) => {
+        if !$crate::cli::output::is_quiet() {
+            if $crate::cli::output::is_stdout_reserved() {
+                eprintln!($($arg)*)
+            } else {
+                println!($($arg)*)
+            }
+        }
+    };
+}
+
+pub(crate) use message;
@@ -1,12 +1,17 @@
-use indicatif::ProgressStyle;
-use std::path::Path;
+use indicatif::{MultiProgress, ProgressDrawTarget, ProgressStyle};
+use serde::Serialize;
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use crate::cli::color::Color;
+use crate::cli::output::{self, message};
 use crate::cli::spinner;
 
 pub struct ProgressBar {
     pb: indicatif::ProgressBar,
     end_message: String,
+    attached: bool,
 }
 
 impl ProgressBar {
@@ -19,7 +24,26 @@ impl ProgressBar {
                 .unwrap(),
         );
         pb.set_message(message);
-        Self { pb, end_message }
+        if output::is_quiet() {
+            pb.set_draw_target(ProgressDrawTarget::hidden());
+        }
+        Self {
+            pb,
+            end_message,
+            attached: false,
+        }
+    }
+
+    /// Show the progress bar together with the other ones of concurrent downloads
+    pub fn attach(self, multi_progress: &MultiProgress) -> Self {
+        if output::is_quiet() {
+            return self;
+        }
+        Self {
+            pb: multi_progress.add(self.pb),
+            attached: true,
+            ..self
+        }
     }
 
     pub fn show(&self) {
@@ -27,8 +51,15 @@ impl ProgressBar {
     }
 
     pub fn finish(&self) {
-        self.pb.finish_and_clear();
-        println!("{}", &self.end_message);
+        if self.attached {
+            // Printing a message would break the other progress bars, so the end message replaces this one
+            self.pb
+                .set_style(ProgressStyle::default_spinner().template("{msg}").unwrap());
+            self.pb.finish_with_message(self.end_message.clone());
+        } else {
+            self.pb.finish_and_clear();
+            message!("{}", &self.end_message);
+        }
     }
 
     pub fn set_length(&self, max_length: Option<u64>) {
@@ -62,6 +93,11 @@ impl ProgressBar {
         self.pb.set_position(progress);
     }
 
+    /// The progress is reported in another way (e.g. `--progress json`)
+    pub fn hide(&self) {
+        self.pb.set_draw_target(ProgressDrawTarget::hidden());
+    }
+
     pub fn download_layout(download_asset: &str, output_path: &Path) -> ProgressBar {
         ProgressBar::new(
             format!("Downloading {}", Color::new(download_asset).bold()),
@@ -71,4 +107,232 @@ impl ProgressBar {
             ),
         )
     }
+
+    pub fn stdout_layout(download_asset: &str) -> ProgressBar {
+        ProgressBar::new(
+            format!("Downloading {}", Color::new(download_asset).bold()),
+            "Written to stdout".to_string(),
+        )
+    }
+}
+
+/// Writes the download progress as a JSON object to a file, so that external tools can monitor it.
+///
+/// The file is replaced atomically at most once per second.
+pub struct ProgressFileWriter {
+    path: PathBuf,
+    started_at: Instant,
+    last_write: Cell<Option<Instant>>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct ProgressSnapshot {
+    bytes_downloaded: u64,
+    total_bytes: Option<u64>,
+    percent: Option<u64>,
+    speed_bps: u64,
+}
+
+impl ProgressSnapshot {
+    fn new(bytes_downloaded: u64, total_bytes: Option<u64>, elapsed: Duration) -> Self {
+        let percent = total_bytes
+            .filter(|total| *total > 0)
+            .map(|total| bytes_downloaded * 100 / total);
+        let elapsed_millis = elapsed.as_millis().max(1) as u64;
+        Self {
+            bytes_downloaded,
+            total_bytes,
+            percent,
+            speed_bps: bytes_downloaded * 1000 / elapsed_millis,
+        }
+    }
+}
+
+const PROGRESS_FILE_INTERVAL: Duration = Duration::from_secs(1);
+
+impl ProgressFileWriter {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            started_at: Instant::now(),
+            last_write: Cell::new(None),
+        }
+    }
+
+    pub fn update_progress(&self, bytes_downloaded: u64, total_bytes: Option<u64>) {
+        let now = Instant::now();
+        let should_write = self
+            .last_write
+            .get()
+            .is_none_or(|x| now.duration_since(x) >= PROGRESS_FILE_INTERVAL);
+        if should_write {
+            self.write(bytes_downloaded, total_bytes);
+            self.last_write.set(Some(now));
+        }
+    }
+
+    pub fn finish(&self, bytes_downloaded: u64, total_bytes: Option<u64>) {
+        self.write(bytes_downloaded, total_bytes);
+    }
+
+    // NOTE: progress is best effort, so errors must never interrupt the download
+    fn write(&self, bytes_downloaded: u64, total_bytes: Option<u64>) {
+        let snapshot =
+            ProgressSnapshot::new(bytes_downloaded, total_bytes, self.started_at.elapsed());
+        let Ok(json) = serde_json::to_vec(&snapshot) else {
+            return;
+        };
+
+        let mut temporary_path = self.path.as_os_str().to_os_string();
+        temporary_path.push(".tmp");
+        if std::fs::write(&temporary_path, json).is_ok() {
+            let _ = std::fs::rename(&temporary_path, &self.path);
+        }
+    }
+}
+
+/// Writes the progress as newline delimited JSON events on stdout (`--progress json`),
+/// so that GUIs and wrappers can follow the download.
+///
+/// Download events are written at most every 100 milliseconds.
+pub struct ProgressEvents {
+    asset: String,
+    last_write: Cell<Option<Instant>>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Download,
+    Downloaded,
+    Install,
+    Installed,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct ProgressEvent<'a> {
+    phase: Phase,
+    asset: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total: Option<u64>,
+}
+
+const PROGRESS_EVENT_INTERVAL: Duration = Duration::from_millis(100);
+
+impl ProgressEvents {
+    pub fn new(asset: &str) -> Self {
+        Self {
+            asset: asset.to_string(),
+            last_write: Cell::new(None),
+        }
+    }
+
+    pub fn update_progress(&self, bytes_downloaded: u64, total_bytes: Option<u64>) {
+        let now = Instant::now();
+        let should_write = self
+            .last_write
+            .get()
+            .is_none_or(|x| now.duration_since(x) >= PROGRESS_EVENT_INTERVAL);
+        if should_write {
+            self.write(Phase::Download, Some(bytes_downloaded), total_bytes);
+            self.last_write.set(Some(now));
+        }
+    }
+
+    pub fn finish(&self, bytes_downloaded: u64, total_bytes: Option<u64>) {
+        self.write(Phase::Downloaded, Some(bytes_downloaded), total_bytes);
+    }
+
+    pub fn phase(&self, phase: Phase) {
+        self.write(phase, None, None);
+    }
+
+    fn write(&self, phase: Phase, bytes: Option<u64>, total: Option<u64>) {
+        let event = ProgressEvent {
+            phase,
+            asset: &self.asset,
+            bytes,
+            total,
+        };
+        if let Ok(json) = serde_json::to_string(&event) {
+            println!("{}", json);
+        }
+    }
+}
+
+#[cfg(test)]
+mod progress_event {
+    use super::*;
+
+    #[test]
+    fn download_event() {
+        let event = ProgressEvent {
+            phase: Phase::Download,
+            asset: "helloworld.tar.gz",
+            bytes: Some(512),
+            total: Some(2048),
+        };
+
+        let result = serde_json::to_string(&event).unwrap();
+
+        assert_eq!(
+            r#"{"phase":"download","asset":"helloworld.tar.gz","bytes":512,"total":2048}"#,
+            result
+        );
+    }
+
+    #[test]
+    fn install_event() {
+        let event = ProgressEvent {
+            phase: Phase::Install,
+            asset: "helloworld.tar.gz",
+            bytes: None,
+            total: None,
+        };
+
+        let result = serde_json::to_string(&event).unwrap();
+
+        assert_eq!(r#"{"phase":"install","asset":"helloworld.tar.gz"}"#, result);
+    }
+}
+
+#[cfg(test)]
+mod progress_snapshot {
+    use super::*;
+
+    #[test]
+    fn known_total_bytes() {
+        let result = ProgressSnapshot::new(512, Some(2048), Duration::from_secs(2));
+
+        assert_eq!(
+            ProgressSnapshot {
+                bytes_downloaded: 512,
+                total_bytes: Some(2048),
+                percent: Some(25),
+                speed_bps: 256,
+            },
+            result
+        );
+    }
+
+    #[test]
+    fn unknown_total_bytes() {
+        let result = ProgressSnapshot::new(512, None, Duration::from_secs(2));
+
+        assert_eq!(None, result.percent);
+    }
+
+    #[test]
+    fn json_format() {
+        let snapshot = ProgressSnapshot::new(512, Some(2048), Duration::from_secs(2));
+
+        let result = serde_json::to_string(&snapshot).unwrap();
+
+        assert_eq!(
+            r#"{"bytes_downloaded":512,"total_bytes":2048,"percent":25,"speed_bps":256}"#,
+            result
+        );
+    }
 }
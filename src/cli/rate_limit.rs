@@ -0,0 +1,92 @@
+use std::time::{Duration, Instant};
+
+/// Parse a rate in bytes per second, with an optional `K`, `M` or `G` suffix (e.g. `500K` or `2M`)
+pub fn parse_rate(src: &str) -> Result<u64, String> {
+    let invalid = || {
+        format!(
+            "Invalid rate {}. Use bytes per second with an optional K, M or G suffix (e.g. 2M)",
+            src
+        )
+    };
+    let src = src.trim();
+    let (number, multiplier) = match src.chars().last().map(|x| x.to_ascii_uppercase()) {
+        Some('K') => (&src[..src.len() - 1], 1024),
+        Some('M') => (&src[..src.len() - 1], 1024 * 1024),
+        Some('G') => (&src[..src.len() - 1], 1024 * 1024 * 1024),
+        _ => (src, 1),
+    };
+    number
+        .parse::<u64>()
+        .ok()
+        .filter(|x| *x > 0)
+        .and_then(|x| x.checked_mul(multiplier))
+        .ok_or_else(invalid)
+}
+
+/// Slows down a download so that its average speed doesn't exceed the rate
+pub struct RateLimiter {
+    bytes_per_second: u64,
+    started_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second,
+            started_at: Instant::now(),
+        }
+    }
+
+    pub fn throttle(&self, total_bytes: u64) {
+        let delay = throttle_delay(
+            total_bytes,
+            self.started_at.elapsed(),
+            self.bytes_per_second,
+        );
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+/// Time to wait so that `total_bytes` are downloaded in no less than `total_bytes / bytes_per_second`
+fn throttle_delay(total_bytes: u64, elapsed: Duration, bytes_per_second: u64) -> Duration {
+    let expected = Duration::from_secs_f64(total_bytes as f64 / bytes_per_second as f64);
+    expected.saturating_sub(elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("1024", 1024; "bytes")]
+    #[test_case("500K", 500 * 1024; "kilobytes")]
+    #[test_case("2M", 2 * 1024 * 1024; "megabytes")]
+    #[test_case("1g", 1024 * 1024 * 1024; "lowercase suffix")]
+    fn valid_rate(src: &str, expected: u64) {
+        assert_eq!(Ok(expected), parse_rate(src));
+    }
+
+    #[test_case("0"; "zero")]
+    #[test_case("2MB"; "unknown suffix")]
+    #[test_case("M"; "no number")]
+    #[test_case("-1K"; "negative")]
+    fn invalid_rate(src: &str) {
+        assert!(parse_rate(src).is_err());
+    }
+
+    #[test]
+    fn faster_than_rate() {
+        let result = throttle_delay(2048, Duration::from_millis(500), 1024);
+
+        assert_eq!(Duration::from_millis(1500), result);
+    }
+
+    #[test]
+    fn slower_than_rate() {
+        let result = throttle_delay(1024, Duration::from_secs(2), 1024);
+
+        assert_eq!(Duration::ZERO, result);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::cli::github_release::{fetch_releases_for, github_client_for};
+use crate::cli::output;
+use crate::cli::result::HandlerResult;
+use crate::cli::table::table;
+use crate::github::release::{Release, ReleaseFilter};
+use crate::github::repository::Repository;
+use serde::Serialize;
+use std::time::SystemTime;
+
+pub struct ReleasesHandler {
+    repository: Repository,
+    limit: Option<usize>,
+    json: bool,
+}
+
+/// Item of `dra releases --json`
+#[derive(Debug, Serialize, PartialEq)]
+struct ReleaseItem {
+    tag: String,
+    published_at: Option<String>,
+    pre_release: bool,
+    assets: usize,
+}
+
+impl ReleasesHandler {
+    pub fn new(repository: Repository, limit: Option<usize>, json: bool) -> Self {
+        Self {
+            repository,
+            limit,
+            json,
+        }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        if self.json {
+            output::reserve_stdout();
+        }
+        let github = github_client_for(&self.repository);
+        let filter = ReleaseFilter {
+            pre_release: true,
+            draft: false,
+        };
+        let releases = fetch_releases_for(&github, &self.repository, filter)?;
+        let items = releases
+            .iter()
+            .take(self.limit.unwrap_or(usize::MAX))
+            .map(ReleaseItem::from)
+            .collect::<Vec<_>>();
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&items).unwrap_or_default()
+            );
+        } else {
+            let header = ["TAG", "PUBLISHED", "PRE-RELEASE", "ASSETS"];
+            print!("{}", table(header, &rows(&items)));
+        }
+        Ok(())
+    }
+}
+
+impl From<&Release> for ReleaseItem {
+    /// Source code archives are not counted as assets
+    fn from(release: &Release) -> Self {
+        Self {
+            tag: release.tag.0.clone(),
+            published_at: release.published_at.map(format_date),
+            pre_release: release.pre_release,
+            assets: release
+                .assets
+                .iter()
+                .filter(|x| !x.is_source_code())
+                .count(),
+        }
+    }
+}
+
+fn format_date(date: SystemTime) -> String {
+    humantime::format_rfc3339_seconds(date).to_string()
+}
+
+fn rows(items: &[ReleaseItem]) -> Vec<[String; 4]> {
+    items
+        .iter()
+        .map(|x| {
+            [
+                x.tag.clone(),
+                // Only the date of the RFC 3339 timestamp (e.g. 2024-05-31)
+                x.published_at
+                    .as_deref()
+                    .and_then(|x| x.get(..10))
+                    .unwrap_or("-")
+                    .to_string(),
+                if x.pre_release { "yes" } else { "no" }.to_string(),
+                x.assets.to_string(),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn release_rows() {
+        let items = vec![
+            ReleaseItem {
+                tag: "v1.0.0-rc.1".to_string(),
+                published_at: Some("2024-05-31T10:00:00Z".to_string()),
+                pre_release: true,
+                assets: 12,
+            },
+            ReleaseItem {
+                tag: "v0.9.0".to_string(),
+                published_at: None,
+                pre_release: false,
+                assets: 3,
+            },
+        ];
+
+        let result = rows(&items);
+
+        assert_eq!(
+            vec![
+                ["v1.0.0-rc.1", "2024-05-31", "yes", "12"].map(String::from),
+                ["v0.9.0", "-", "no", "3"].map(String::from),
+            ],
+            result
+        );
+    }
+}
@@ -1,11 +1,55 @@
+use crate::github::error::GithubError;
+
 #[derive(Debug)]
 pub enum HandlerError {
     Default(String),
     OperationCancelled(String),
+    /// Failures with a dedicated exit code, so that scripts can branch on the failure type
+    Failure(ErrorKind, String),
 }
 
 pub type HandlerResult = Result<(), HandlerError>;
 
+/// Exit codes are part of the public interface, never change the existing ones.
+/// `1` is used for any other error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorKind {
+    /// Invalid command line arguments, same exit code of clap parsing errors
+    Usage,
+    /// Repository, release or asset not found
+    NotFound,
+    Network,
+    Authentication,
+    RateLimit,
+    InstallFailed,
+}
+
+impl ErrorKind {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorKind::Usage => 2,
+            ErrorKind::NotFound => 3,
+            ErrorKind::Network => 4,
+            ErrorKind::Authentication => 5,
+            ErrorKind::RateLimit => 6,
+            ErrorKind::InstallFailed => 7,
+        }
+    }
+
+    /// `None` when the GitHub error doesn't have a dedicated exit code
+    pub fn from_github(error: &GithubError) -> Option<Self> {
+        match error {
+            GithubError::Http(_) => Some(ErrorKind::Network),
+            GithubError::RepositoryOrReleaseNotFound
+            | GithubError::PackageArtifactNotFound(_)
+            | GithubError::NotCached(_) => Some(ErrorKind::NotFound),
+            GithubError::Unauthorized => Some(ErrorKind::Authentication),
+            GithubError::RateLimitExceeded(_) => Some(ErrorKind::RateLimit),
+            GithubError::JsonDeserialization(_) | GithubError::Unsupported(_) => None,
+        }
+    }
+}
+
 impl HandlerError {
     pub fn new(message: String) -> Self {
         Self::Default(message)
@@ -14,4 +58,56 @@ impl HandlerError {
     pub fn op_cancelled(message: &str) -> Self {
         Self::OperationCancelled(message.to_string())
     }
+
+    pub fn usage(message: String) -> Self {
+        Self::Failure(ErrorKind::Usage, message)
+    }
+
+    pub fn not_found(message: String) -> Self {
+        Self::Failure(ErrorKind::NotFound, message)
+    }
+
+    pub fn install_failed(message: String) -> Self {
+        Self::Failure(ErrorKind::InstallFailed, message)
+    }
+
+    pub fn github(message: String, error: &GithubError) -> Self {
+        match ErrorKind::from_github(error) {
+            Some(kind) => Self::Failure(kind, message),
+            None => Self::Default(message),
+        }
+    }
+
+    /// Message of the errors that make the command fail (a cancelled operation is not a failure)
+    pub fn failure_message(&self) -> Option<&str> {
+        match self {
+            HandlerError::Default(message) | HandlerError::Failure(_, message) => Some(message),
+            HandlerError::OperationCancelled(_) => None,
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            HandlerError::Default(_) => 1,
+            HandlerError::OperationCancelled(_) => 0,
+            HandlerError::Failure(kind, _) => kind.exit_code(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(GithubError::RepositoryOrReleaseNotFound, 3; "not found")]
+    #[test_case(GithubError::PackageArtifactNotFound("any".into()), 3; "package artifact not found")]
+    #[test_case(GithubError::Unauthorized, 5; "unauthorized")]
+    #[test_case(GithubError::RateLimitExceeded(None), 6; "rate limit")]
+    #[test_case(GithubError::JsonDeserialization("any".into()), 1; "other")]
+    fn github_error_exit_code(error: GithubError, expected: i32) {
+        let result = HandlerError::github("any".into(), &error);
+
+        assert_eq!(expected, result.exit_code());
+    }
 }
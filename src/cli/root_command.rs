@@ -1,8 +1,12 @@
 use std::path::PathBuf;
 
-use clap::{Parser, ValueHint};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum, ValueHint};
 
+use crate::cli::rate_limit::parse_rate;
+use crate::config::Config;
 use crate::github::repository::Repository;
+use crate::github::retry::DEFAULT_RETRIES;
+use crate::system::Libc;
 
 /// A command line tool to download release assets from GitHub
 ///
@@ -33,88 +37,732 @@ use crate::github::repository::Repository;
 pub struct Cli {
     #[command(subcommand)]
     pub cmd: Command,
+
+    /// Show more logs on stderr (-v: info, -vv: debug, -vvv: trace, including HTTP client logs)
+    #[arg(short, long, action = ArgAction::Count, global = true, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Only show errors. Interactive prompts are still shown
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
 }
 
 #[derive(Debug, Parser)]
 pub enum Command {
     /// Select and download an asset
-    Download {
-        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}
-        #[arg(value_parser = Repository::try_parse)]
+    Download(Box<DownloadArgs>),
+
+    /// Select an asset and generate an untagged version of it
+    Untag {
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}.
+        /// For GitHub Enterprise Server, GitLab, Gitea or Bitbucket, include the host (e.g. gitlab.com/{group}/{project})
+        #[arg(value_parser = parse_repository)]
         repo: Repository,
+    },
 
-        /// Select and download the first asset that matches a given pattern.
-        ///
-        /// Supported patterns are:
-        /// - Literal, the exact name of the asset (e.g. helloworld.tar.gz)
-        /// - Untagged, a version-free pattern of your asset, generated by `untag` command (e.g. helloworld_{tag}.tar.gz)
-        /// - Wildcard, a pattern that uses `*` and/or `?` special characters (e.g. helloworld*_amd64.deb)
-        #[arg(
-            short,
-            long,
-            group = "non-interactive",
-            value_name = "PATTERN",
-            verbatim_doc_comment
-        )]
-        select: Option<String>,
-
-        /// Automatically select and download an asset based on your operating system and architecture
-        #[arg(short, long, group = "non-interactive")]
-        automatic: bool,
-
-        /// Set the tag name for fetching a specific release.
+    /// Generate shell completion
+    Completion {
+        /// Shell to generate completion for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
+    /// Check your environment for common configuration problems
+    Doctor,
+
+    /// List the releases of a repository (tag, publication date, pre-release and number of assets),
+    /// from the newest to the oldest
+    Releases {
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}.
+        /// For GitHub Enterprise Server, GitLab, Gitea or Bitbucket, include the host (e.g. gitlab.com/{group}/{project})
+        #[arg(value_parser = parse_repository)]
+        repo: Repository,
+
+        /// Show only the newest N releases
+        #[arg(short = 'n', long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Print the releases as JSON on stdout
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List the assets of a release (name, size, content type and download count) without downloading them
+    Assets {
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}.
+        /// For GitHub Enterprise Server, GitLab, Gitea or Bitbucket, include the host (e.g. gitlab.com/{group}/{project})
+        #[arg(value_parser = parse_repository)]
+        repo: Repository,
+
+        /// Release tag (e.g. 'v1.2.3'), a semver range (e.g. '^1.2') or a wildcard pattern (e.g. 'v1.2.*').
         /// Default value is the latest release
-        #[arg(short, long, verbatim_doc_comment)]
+        #[arg(short, long)]
         tag: Option<String>,
 
-        /// Save asset to custom path (file or directory).
-        /// Default path is current working directory and the name of the asset.
-        /// When used with install feature, it will save the executable to the specified path when applicable.
-        /// If you install multiple executables, it must be a directory path.
-        #[arg(short, long, value_hint = ValueHint::AnyPath, verbatim_doc_comment)]
-        output: Option<PathBuf>,
+        /// Print the assets as JSON on stdout
+        #[arg(long)]
+        json: bool,
+    },
 
-        /// Install downloaded asset
-        ///
-        /// Supported assets are:
-        /// - Debian packages (requires elevated privileges)
-        /// - RPM packages (requires elevated privileges)
-        /// - Tar archives with executable(s)
-        /// - Zip files with executable(s)
-        /// - 7-Zip files with executable(s) (requires `7z` cli to be installed and in your `PATH`)
-        /// - Compressed executable files
-        /// - Executable files
-        /// - AppImage files
-        ///
-        /// If a tar/zip archive contains many executables and cannot automatically detect which one to install, use `--install-file <INSTALL_FILE>`.
-        #[arg(short, long, group = "install-feature", verbatim_doc_comment)]
-        install: bool,
+    /// Show the release notes of a release, with basic markdown formatting
+    Notes {
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}.
+        /// For GitHub Enterprise Server, GitLab, Gitea or Bitbucket, include the host (e.g. gitlab.com/{group}/{project})
+        #[arg(value_parser = parse_repository)]
+        repo: Repository,
 
-        /// Install downloaded asset and select which executable to install from a tar/zip archive.
-        /// You can install multiple executables by specifying `-I/--install-file` multiple times.
-        ///
-        /// If you use this option for other types of assets, it will be treated as the default install.
-        #[arg(
-            short = 'I',
-            long,
-            num_args = 1,
-            group = "install-feature",
-            verbatim_doc_comment
-        )]
-        install_file: Option<Vec<String>>,
+        /// Release tag (e.g. 'v1.2.3'), a semver range (e.g. '^1.2') or a wildcard pattern (e.g. 'v1.2.*').
+        /// Default value is the latest release
+        #[arg(short, long)]
+        tag: Option<String>,
+
+        /// Print the release notes as they are (markdown), without formatting
+        #[arg(long)]
+        raw: bool,
     },
 
-    /// Select an asset and generate an untagged version of it
-    Untag {
-        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}
-        #[arg(value_parser = Repository::try_parse)]
+    /// List the release tags of a repository, one per line from the newest to the oldest
+    Tags {
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}.
+        /// For GitHub Enterprise Server, GitLab, Gitea or Bitbucket, include the host (e.g. gitlab.com/{group}/{project})
+        #[arg(value_parser = parse_repository)]
         repo: Repository,
+
+        /// Show only the tags matching a semver range (e.g. '^1.2'), a wildcard pattern (e.g. 'v1.2.*')
+        /// or starting with the pattern (e.g. 'v1.2')
+        pattern: Option<String>,
     },
 
-    /// Generate shell completion
-    Completion {
-        /// Shell to generate completion for
-        #[arg(value_enum)]
-        shell: clap_complete::Shell,
+    /// Manage the GitHub authentication of dra
+    Auth {
+        #[command(subcommand)]
+        cmd: AuthCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AuthCommand {
+    /// Login to GitHub in the browser (OAuth device flow) and save the token for the next commands.
+    ///
+    /// The token is saved in the system keyring (Secret Service, macOS Keychain or Windows Credential Manager).
+    ///
+    /// Tokens in environment variables (e.g. GITHUB_TOKEN) take precedence over the saved token.
+    Login {
+        /// Client id of the GitHub OAuth app (with device flow enabled) used to login.
+        ///
+        /// It can also be set with DRA_GITHUB_CLIENT_ID environment variable.
+        #[arg(long)]
+        client_id: Option<String>,
+
+        /// OAuth scopes to request, separated by spaces (e.g. "repo" to access private repositories)
+        #[arg(long, default_value = "repo")]
+        scope: String,
+
+        /// Save the token in a file readable only by the current user, instead of the system keyring.
+        ///
+        /// The file is also used when the system keyring is not available.
+        #[arg(long)]
+        insecure_storage: bool,
     },
+
+    /// Show the GitHub token in use and where the saved token is stored
+    Status,
+
+    /// Remove the token saved by 'dra auth login'
+    Logout,
+}
+
+#[derive(Debug, Args)]
+pub struct DownloadArgs {
+    /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}.
+    /// For GitHub Enterprise Server, GitLab, Gitea or Bitbucket, include the host (e.g. gitlab.com/{group}/{project}).
+    /// Git remotes (e.g. git@github.com:{owner}/{repo}.git) are accepted too, and the tag of a release page URL
+    /// (e.g. https://github.com/{owner}/{repo}/releases/tag/v1.2.3) is used when --tag is not set.
+    /// Default value is the repository of the `origin` git remote of the current directory
+    #[arg(value_parser = parse_repository, verbatim_doc_comment)]
+    pub repo: Option<Repository>,
+
+    /// Git remote of the current directory used when the repository is not given
+    #[arg(long, value_name = "NAME", conflicts_with = "repo")]
+    pub remote: Option<String>,
+
+    /// Select and download the first asset that matches a given pattern.
+    ///
+    /// Supported patterns are:
+    /// - Literal, the exact name of the asset (e.g. helloworld.tar.gz)
+    /// - Untagged, a version-free pattern of your asset, generated by `untag` command (e.g. helloworld_{tag}.tar.gz)
+    /// - Wildcard, a pattern that uses `*` and/or `?` special characters (e.g. helloworld*_amd64.deb)
+    /// - Regex, a regular expression with the `regex:` prefix that matches the whole name of only one asset
+    ///   (e.g. 'regex:.*linux.*musl.*\.tar\.gz')
+    ///
+    /// `{os}`, `{arch}` and `{target}` (Rust target triple) placeholders are replaced by the current
+    /// platform, or the one set by --os and --arch (e.g. 'tool-{target}.tar.gz').
+    ///
+    /// You can download multiple assets by specifying `-s/--select` multiple times
+    /// (or use `--fallback` to try the patterns in order and download only the first match).
+    #[arg(
+        short,
+        long,
+        num_args = 1,
+        group = "non-interactive",
+        group = "platform-feature",
+        value_name = "PATTERN",
+        verbatim_doc_comment
+    )]
+    pub select: Option<Vec<String>>,
+
+    /// Try the `-s/--select` patterns in order and download the asset of the first one that matches.
+    /// Useful for repositories with inconsistent asset naming across releases
+    #[arg(long, requires = "select", verbatim_doc_comment)]
+    pub fallback: bool,
+
+    /// Select and download the only asset that matches a shell-style glob pattern
+    /// (e.g. '*linux-x86_64*.tar.gz'). `{tag}` placeholder is supported.
+    /// Unlike `--select`, it fails when more than one asset matches
+    #[arg(
+        long,
+        group = "non-interactive",
+        value_name = "GLOB",
+        verbatim_doc_comment
+    )]
+    pub select_glob: Option<String>,
+
+    /// Automatically select and download an asset based on your operating system and architecture
+    #[arg(
+        short,
+        long,
+        group = "non-interactive",
+        group = "exclude-feature",
+        group = "platform-feature"
+    )]
+    pub automatic: bool,
+
+    /// Select the asset for this operating system instead of the current one in automatic mode
+    /// or in `-s/--select` placeholders (linux, macos, windows)
+    #[arg(long, requires = "platform-feature", verbatim_doc_comment)]
+    pub os: Option<String>,
+
+    /// Select the asset for this architecture instead of the current one in automatic mode
+    /// or in `-s/--select` placeholders (x86_64, aarch64, arm, powerpc64le)
+    #[arg(long, requires = "platform-feature", verbatim_doc_comment)]
+    pub arch: Option<String>,
+
+    /// Print each asset with the OS, architecture and libc detected in its name
+    /// and the priority assigned by automatic mode (lower is better)
+    #[arg(long, requires = "automatic", verbatim_doc_comment)]
+    pub explain: bool,
+
+    /// When multiple assets match your system equally well in automatic mode, ask which one to download.
+    /// Without a terminal, it fails listing the candidates instead
+    #[arg(long, requires = "automatic", verbatim_doc_comment)]
+    pub ask_on_ambiguity: bool,
+
+    /// Prefer assets built with this libc (musl or gnu) in automatic mode.
+    /// It can also be set with DRA_PREFER_LIBC environment variable or `prefer_libc` in the config file.
+    /// Default behavior prefers musl builds. It's also used by the `{target}` placeholder of `-s/--select`
+    #[arg(
+        long,
+        requires = "platform-feature",
+        value_name = "LIBC",
+        value_parser = Libc::try_parse,
+        verbatim_doc_comment
+    )]
+    pub prefer_libc: Option<Libc>,
+
+    /// TOML file with per repository asset patterns and scores, used by automatic mode
+    /// before the built-in rules. See https://github.com/devmatteini/dra#asset-weight-file
+    #[arg(
+        long,
+        requires = "automatic",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        verbatim_doc_comment
+    )]
+    pub asset_weight_file: Option<PathBuf>,
+
+    /// Download the source code archive of the release, useful when a project publishes no binary assets
+    #[arg(long, group = "non-interactive", value_enum, value_name = "FORMAT")]
+    pub source: Option<SourceArchive>,
+
+    /// Select the same asset chosen the last time in interactive mode for this repository,
+    /// without asking. If it's not found, the asset selection is shown
+    #[arg(long, conflicts_with = "non-interactive", verbatim_doc_comment)]
+    pub repeat: bool,
+
+    /// Select multiple assets to download in interactive mode (space to select, enter to confirm)
+    #[arg(short, long, conflicts_with = "non-interactive")]
+    pub multiple: bool,
+
+    /// Download all assets of the release (including source code archives) into the output directory.
+    /// Use `--include`/`--exclude` to filter them
+    #[arg(
+        long,
+        group = "non-interactive",
+        group = "exclude-feature",
+        verbatim_doc_comment
+    )]
+    pub all_assets: bool,
+
+    /// Only download assets matching this wildcard pattern with `--all-assets` (e.g. '*.tar.gz').
+    /// Can be specified multiple times
+    #[arg(
+        long,
+        num_args = 1,
+        requires = "all_assets",
+        value_name = "PATTERN",
+        verbatim_doc_comment
+    )]
+    pub include: Option<Vec<String>>,
+
+    /// Skip assets matching this wildcard pattern with `--all-assets` or `--automatic` (e.g. '*.sig').
+    /// Can be specified multiple times
+    #[arg(
+        long,
+        num_args = 1,
+        requires = "exclude-feature",
+        value_name = "PATTERN",
+        verbatim_doc_comment
+    )]
+    pub exclude: Option<Vec<String>>,
+
+    /// Download an artifact from the GitHub Container Registry package of the repository
+    /// instead of a release asset (e.g. an artifact pushed with `oras push ghcr.io/{owner}/{repo}:{tag} <ARTIFACT>`).
+    /// The package tag is set with `--tag` and its default value is `latest`
+    #[arg(
+        long,
+        group = "non-interactive",
+        value_name = "ARTIFACT",
+        verbatim_doc_comment
+    )]
+    pub package: Option<String>,
+
+    /// Download the asset from its URL (e.g. https://github.com/{owner}/{repo}/releases/download/{tag}/{asset}),
+    /// without fetching the release
+    #[arg(
+        long,
+        group = "non-interactive",
+        value_name = "URL",
+        conflicts_with = "tag",
+        verbatim_doc_comment
+    )]
+    pub asset_url: Option<String>,
+
+    /// Download the release asset with this id, without fetching the release
+    #[arg(
+        long,
+        group = "non-interactive",
+        value_name = "ID",
+        conflicts_with = "tag",
+        verbatim_doc_comment
+    )]
+    pub asset_id: Option<u64>,
+
+    /// Only consider release assets with this content type (e.g. application/gzip).
+    /// Wildcards are supported (e.g. 'application/*')
+    #[arg(
+        long,
+        value_name = "CONTENT_TYPE",
+        conflicts_with_all = ["package", "asset_url", "asset_id"],
+        verbatim_doc_comment
+    )]
+    pub content_type: Option<String>,
+
+    /// Set the tag name for fetching a specific release.
+    /// A semantic version range (e.g. '^1.4' or '>=2,<3') selects the highest matching release,
+    /// while a wildcard pattern (e.g. 'v1.2.*' or '2024.05.*') selects the newest matching one.
+    /// Default value is the latest release
+    #[arg(short, long, verbatim_doc_comment)]
+    pub tag: Option<String>,
+
+    /// How the latest release is resolved. Use `semver` for repositories that publish
+    /// patch releases of old versions after the newest one
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        value_name = "STRATEGY",
+        conflicts_with = "tag",
+        verbatim_doc_comment
+    )]
+    pub latest_strategy: LatestStrategy,
+
+    /// Consider pre-releases when resolving the latest release.
+    /// It can also be set with `pre_release` in the config file
+    #[arg(long, conflicts_with = "tag", verbatim_doc_comment)]
+    pub pre_release: bool,
+
+    /// Consider draft releases too, when resolving the latest release or a release by `--tag`.
+    /// It requires a GitHub token with push access to the repository
+    #[arg(long, verbatim_doc_comment)]
+    pub include_drafts: bool,
+
+    /// Download the selected asset of each release from this tag to `--to-tag` (both included).
+    /// Each asset is saved in a directory named after its release tag
+    #[arg(
+        long,
+        value_name = "TAG",
+        requires = "to_tag",
+        conflicts_with_all = [
+            "tag",
+            "install-feature",
+            "package",
+            "asset_url",
+            "asset_id",
+            "all_assets",
+            "multiple",
+            "notes",
+            "with_checksums"
+        ],
+        verbatim_doc_comment
+    )]
+    pub from_tag: Option<String>,
+
+    /// Last tag of the range started by `--from-tag`
+    #[arg(long, value_name = "TAG", requires = "from_tag")]
+    pub to_tag: Option<String>,
+
+    /// Save asset to custom path (file or directory).
+    /// Default path is current working directory and the name of the asset.
+    /// When used with install feature, it will save the executable to the specified path when applicable
+    /// (default directory is `%LOCALAPPDATA%\dra\bin` on Windows).
+    /// If you install multiple executables, it must be a directory path.
+    /// `{owner}`, `{repo}`, `{tag}` and `{asset}` placeholders are replaced by the repository, the release tag
+    /// and the asset name (e.g. 'downloads/{repo}-{tag}-{asset}'). Missing directories are created.
+    /// Use `-` to write the asset to stdout (e.g. `dra download -s foo.tar.gz -o - <REPO> | tar xz`).
+    #[arg(short, long, value_hint = ValueHint::AnyPath, verbatim_doc_comment)]
+    pub output: Option<PathBuf>,
+
+    /// Install downloaded asset
+    ///
+    /// Supported assets are:
+    /// - Debian packages (requires elevated privileges, unless `--extract-deb` is used)
+    /// - RPM packages, with `dnf` when available or `rpm` (requires elevated privileges)
+    /// - Arch Linux packages (requires elevated privileges)
+    /// - Windows Installer packages (`.msi`), only on Windows
+    /// - Alpine packages (requires elevated privileges, and `--allow-untrusted` for unsigned packages)
+    /// - Tar archives with executable(s)
+    /// - Zip files with executable(s)
+    /// - 7-Zip files with executable(s) (requires `7z`, `7zz` or `7za` cli to be installed and in your `PATH`)
+    /// - macOS disk images with an application or executable(s), only on macOS
+    /// - Compressed executable files
+    /// - Executable files
+    /// - AppImage files (use `--desktop-entry` to add them to the applications)
+    /// - Self-extracting shell scripts (requires `--allow-shell-scripts`)
+    ///
+    /// If a tar/zip archive contains many executables and cannot automatically detect which one to install, use `--install-file <INSTALL_FILE>`.
+    #[arg(short, long, group = "install-feature", verbatim_doc_comment)]
+    pub install: bool,
+
+    /// Install downloaded asset and select which executable to install from a tar/zip archive.
+    /// You can install multiple executables by specifying `-I/--install-file` multiple times.
+    ///
+    /// If you use this option for other types of assets, it will be treated as the default install.
+    #[arg(
+        short = 'I',
+        long,
+        num_args = 1,
+        group = "install-feature",
+        verbatim_doc_comment
+    )]
+    pub install_file: Option<Vec<String>>,
+
+    /// Install downloaded asset and all the executables of a tar/zip archive (e.g. a release with many tools).
+    /// If you use this option for other types of assets, it will be treated as the default install.
+    #[arg(long, group = "install-feature", verbatim_doc_comment)]
+    pub install_all: bool,
+
+    /// Fail before downloading if the project license is not in the
+    /// `license_filter` allowlist of the config file
+    #[arg(long, verbatim_doc_comment)]
+    pub license_check: bool,
+
+    /// Run a command and use its output as GitHub token (e.g. "pass show github-token").
+    /// It takes precedence over the authentication environment variables
+    #[arg(long, value_name = "CMD", verbatim_doc_comment)]
+    pub github_token_command: Option<String>,
+
+    /// Write the download progress to a file as a JSON object, updated every second.
+    /// Useful for external monitoring, e.g. {"bytes_downloaded":512,"total_bytes":2048,"percent":25,"speed_bps":256}
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath, verbatim_doc_comment)]
+    pub progress_to_file: Option<PathBuf>,
+
+    /// Limit the download speed, in bytes per second with an optional K, M or G suffix (e.g. 500K or 2M).
+    /// Useful on shared CI runners or metered connections.
+    #[arg(long, value_name = "RATE", value_parser = parse_rate, verbatim_doc_comment)]
+    pub limit_rate: Option<u64>,
+
+    /// Download large assets with multiple concurrent connections, each fetching a range of 8 MiB.
+    /// It improves throughput on high-latency links. Small assets always use one connection.
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u16).range(1..=32),
+        verbatim_doc_comment
+    )]
+    pub connections: u16,
+
+    /// Number of assets downloaded concurrently when multiple assets are selected (e.g. --all-assets)
+    #[arg(
+        short,
+        long,
+        value_name = "N",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u16).range(1..=32),
+        verbatim_doc_comment
+    )]
+    pub jobs: u16,
+
+    /// Install multiple selected assets (e.g. `-s a.tar.gz -s b.tar.gz -i`), up to N at a time.
+    /// Packages of system package managers (e.g. dpkg, rpm) are still installed one at a time.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u16).range(1..=32),
+        requires = "install-feature",
+        conflicts_with = "create_symlink",
+        verbatim_doc_comment
+    )]
+    pub parallel_install: Option<u16>,
+
+    /// Number of retries of release requests and downloads that fail with a transient error
+    /// (server errors, timeouts and connection resets)
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_RETRIES, verbatim_doc_comment)]
+    pub retries: u32,
+
+    /// Delay before the first retry (e.g. 500ms or 2s), doubled at each following retry
+    #[arg(
+        long,
+        value_name = "DURATION",
+        default_value = "1s",
+        value_parser = humantime::parse_duration,
+        verbatim_doc_comment
+    )]
+    pub retry_delay: std::time::Duration,
+
+    /// When the GitHub API rate limit is exceeded, wait until it resets and retry,
+    /// instead of failing (at most twice, then the download fails)
+    #[arg(long)]
+    pub wait_for_rate_limit: bool,
+
+    /// Don't cache release information. By default, responses are cached in <cache dir>/dra/metadata
+    /// (e.g. ~/.cache/dra/metadata) with their ETag, so that unchanged releases are not downloaded again
+    #[arg(long, verbatim_doc_comment)]
+    pub no_cache: bool,
+
+    /// Save downloaded assets in <cache dir>/dra/assets, so that they are available with --offline.
+    /// Cached assets are not downloaded again
+    #[arg(long, verbatim_doc_comment, conflicts_with = "no_cache")]
+    pub cache_assets: bool,
+
+    /// Resolve releases and assets only from the cache, without network requests.
+    /// Release information is cached by default, while assets only with --cache-assets
+    #[arg(long, verbatim_doc_comment, conflicts_with = "no_cache")]
+    pub offline: bool,
+
+    /// Fetch the release and its assets with a single GitHub GraphQL API request (a token is required).
+    /// Pre-releases, drafts, semver ranges, wildcards and the semver strategy still use the REST API
+    #[arg(long, verbatim_doc_comment, conflicts_with = "offline")]
+    pub graphql: bool,
+
+    /// Maximum time to connect and to receive the response of each request (e.g. 10s), so that
+    /// slow servers fail fast. Release information requests must complete within it (default 5s).
+    /// It can also be set with DRA_TIMEOUT environment variable.
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        verbatim_doc_comment
+    )]
+    pub timeout: Option<std::time::Duration>,
+
+    /// Proxy used for API requests and downloads (e.g. http://proxy:8080 or socks5://proxy:1080).
+    /// It takes precedence over ALL_PROXY, HTTPS_PROXY and HTTP_PROXY environment variables,
+    /// while the hosts in NO_PROXY are still reached directly
+    #[arg(long, value_name = "URL", verbatim_doc_comment)]
+    pub proxy: Option<String>,
+
+    /// PEM bundle of the root certificates used to verify servers, instead of the built-in ones.
+    /// Useful behind TLS-intercepting proxies or with servers using a private CA.
+    /// It can also be set with DRA_CA_BUNDLE environment variable.
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath, verbatim_doc_comment)]
+    pub cacert: Option<PathBuf>,
+
+    /// Don't verify the TLS certificates of servers. Use it only as a last resort,
+    /// since the downloaded assets can be tampered with
+    #[arg(long, conflicts_with = "cacert", verbatim_doc_comment)]
+    pub insecure: bool,
+
+    /// Base URL of the GitHub REST API, for GitHub Enterprise Server (e.g. https://github.example.com/api/v3).
+    /// It can also be set with DRA_GITHUB_API environment variable. Otherwise, it's
+    /// https://{host}/api/v3 when the repository includes the host (e.g. github.example.com/{owner}/{repo})
+    #[arg(long, value_name = "URL", verbatim_doc_comment)]
+    pub github_api_url: Option<String>,
+
+    /// How the download progress is shown: an interactive progress bar (default) or newline delimited
+    /// JSON events on stdout, e.g. {"phase":"download","asset":"helloworld.tar.gz","bytes":512,"total":2048}.
+    /// Phases are download, downloaded, install and installed. All other output is written to stderr.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t,
+        verbatim_doc_comment
+    )]
+    pub progress: ProgressFormat,
+
+    /// Request gzip compressed responses when fetching release information to reduce transfer size.
+    /// Asset downloads are not affected
+    #[arg(long, verbatim_doc_comment)]
+    pub compress_meta: bool,
+
+    /// Install the man pages (e.g. `tool.1`) found in a tar/zip archive to the first directory of `$MANPATH`
+    /// (default value is `/usr/local/share/man`) and update the man database when `mandb` is available
+    #[arg(long, requires = "install-feature", verbatim_doc_comment)]
+    pub install_man_page: bool,
+
+    /// Add the desktop entry of an AppImage to the applications of the user (`$XDG_DATA_HOME/applications`,
+    /// default value is `~/.local/share/applications`), so that it can be launched from the desktop environment
+    #[arg(long, requires = "install-feature", verbatim_doc_comment)]
+    pub desktop_entry: bool,
+
+    /// Keep the quarantine attribute (`com.apple.quarantine`) of the installed executables on macOS.
+    /// By default it is removed, so that Gatekeeper doesn't block them.
+    #[arg(long, requires = "install-feature", verbatim_doc_comment)]
+    pub keep_quarantine: bool,
+
+    /// Extract the executables of a Debian package (the ones in `bin` and `sbin` directories) instead of installing it,
+    /// so elevated privileges are not required. This is the default behavior when `dpkg` is not available.
+    #[arg(long, requires = "install-feature", verbatim_doc_comment)]
+    pub extract_deb: bool,
+
+    /// Create a symlink at the given path pointing to the installed executable.
+    /// An existing symlink is replaced, but other existing files are never overwritten.
+    #[arg(
+        long,
+        value_name = "SYMLINK_PATH",
+        value_hint = ValueHint::FilePath,
+        requires = "install-feature",
+        verbatim_doc_comment
+    )]
+    pub create_symlink: Option<PathBuf>,
+
+    /// Save the release notes (markdown) to this file.
+    /// A relative path is saved in the same directory of the downloaded assets
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["package", "asset_url", "asset_id"],
+        verbatim_doc_comment
+    )]
+    pub notes: Option<PathBuf>,
+
+    /// Also download the checksum files of the asset (e.g. `<asset>.sha256` or `checksums.txt`)
+    /// and save them next to it
+    #[arg(
+        long,
+        conflicts_with_all = ["install-feature", "asset_url", "asset_id"],
+        verbatim_doc_comment
+    )]
+    pub with_checksums: bool,
+
+    /// What to do when the output file already exists: overwrite it (default), skip the download,
+    /// fail with an error or save the asset with a numbered name (e.g. `helloworld-1.tar.gz`)
+    #[arg(
+        long,
+        value_enum,
+        value_name = "POLICY",
+        conflicts_with_all = ["install-feature", "overwrite_if_older"],
+        verbatim_doc_comment
+    )]
+    pub if_exists: Option<IfExists>,
+
+    /// Skip the download when the output file already exists and is newer than the release.
+    /// The existing file is replaced only when it's older than the asset update date
+    /// (or the release publication date, when the asset one is unknown).
+    #[arg(long, conflicts_with = "install-feature", verbatim_doc_comment)]
+    pub overwrite_if_older: bool,
+
+    /// Resolve the release and select the assets, then print what would be downloaded (or installed) and where.
+    /// Nothing is downloaded or written to disk.
+    #[arg(long, verbatim_doc_comment)]
+    pub dry_run: bool,
+
+    /// Print a JSON report of the downloaded assets on stdout (name, tag, size, sha256, path and
+    /// installed executables). All other output is written to stderr.
+    #[arg(long, verbatim_doc_comment)]
+    pub json: bool,
+
+    /// Don't create missing directories of the output path.
+    /// By default they are created, like `mkdir -p` (e.g. '-o ./artifacts/v1.2.3/').
+    #[arg(long, verbatim_doc_comment)]
+    pub no_create_dirs: bool,
+
+    /// Make the downloaded file executable (0755), without installing it.
+    /// Useful for assets that are plain binaries. It has no effect on Windows.
+    #[arg(long, conflicts_with = "install-feature", verbatim_doc_comment)]
+    pub executable: bool,
+
+    /// Allow installing self-extracting shell scripts (e.g. `installer.sh`) by running them.
+    /// Only use this option with assets you trust.
+    #[arg(long, requires = "install-feature", verbatim_doc_comment)]
+    pub allow_shell_scripts: bool,
+
+    /// Arguments passed to a self-extracting shell script when it is installed (e.g. "--prefix=/opt/tool")
+    #[arg(
+        long,
+        value_name = "ARGS",
+        allow_hyphen_values = true,
+        requires = "install-feature",
+        requires = "allow_shell_scripts"
+    )]
+    pub self_extract_args: Option<String>,
+
+    /// Allow installing Alpine packages (`.apk`) that are not signed with a trusted key (`apk add --allow-untrusted`).
+    /// Only use this option with assets you trust.
+    #[arg(long, verbatim_doc_comment)]
+    pub allow_untrusted: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum ProgressFormat {
+    #[default]
+    Bar,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum LatestStrategy {
+    /// The latest release by publish date, as defined by GitHub
+    #[default]
+    Date,
+    /// The release with the highest semantic version tag
+    Semver,
+}
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum IfExists {
+    #[default]
+    Overwrite,
+    Skip,
+    Error,
+    Rename,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SourceArchive {
+    /// Source code (tar.gz)
+    Tar,
+    /// Source code (zip)
+    Zip,
+}
+
+/// Names without `/` can't be repositories, so they are looked up in the aliases of the
+/// configuration file (e.g. `rg = "BurntSushi/ripgrep"`) before parsing
+fn parse_repository(src: &str) -> Result<Repository, String> {
+    if src.contains('/') {
+        return Repository::try_parse(src);
+    }
+    let config = Config::load().map_err(|e| e.to_string())?;
+    Repository::try_parse(config.expand_alias(src))
 }
@@ -1,7 +1,9 @@
 use crate::cli::result::HandlerError;
 use crate::github::release::Asset;
-use dialoguer::Select;
 use dialoguer::theme::ColorfulTheme;
+use dialoguer::{FuzzySelect, MultiSelect};
+use indicatif::HumanBytes;
+use itertools::Itertools;
 
 pub struct Messages<'a> {
     pub select_prompt: &'a str,
@@ -10,25 +12,96 @@ pub struct Messages<'a> {
 
 pub type AskSelectAssetResult = Result<Asset, HandlerError>;
 
+/// Type to filter the assets, useful when a release has many assets (e.g. cross-compiled projects)
 pub fn ask_select_asset(assets: Vec<Asset>, messages: Messages) -> AskSelectAssetResult {
+    ask_select_asset_with_default(assets, messages, 0)
+}
+
+/// `default` is the index of the highlighted asset
+pub fn ask_select_asset_with_default(
+    assets: Vec<Asset>,
+    messages: Messages,
+    default: usize,
+) -> AskSelectAssetResult {
     let items = assets_names(&assets);
-    let index = Select::with_theme(&ColorfulTheme::default())
+    let index = FuzzySelect::with_theme(&ColorfulTheme::default())
         .with_prompt(messages.select_prompt)
-        .default(0)
+        .default(default)
+        .max_length(MAX_VISIBLE_ASSETS)
         .items(&items)
         .interact_opt()
         .map_err(|e| HandlerError::new(e.to_string()))?;
-    if index.is_none() {
-        return Err(HandlerError::op_cancelled(messages.quit_select));
+    match index {
+        Some(index) => Ok(assets.into_iter().nth(index).unwrap()),
+        None => Err(HandlerError::op_cancelled(messages.quit_select)),
     }
-    let selected_name = &items[index.unwrap()];
-    Ok(find_asset_by_name(selected_name, assets))
 }
 
+/// Space to toggle an asset, enter to confirm
+pub fn ask_select_assets(
+    assets: Vec<Asset>,
+    messages: Messages,
+) -> Result<Vec<Asset>, HandlerError> {
+    let items = assets_names(&assets);
+    let indexes = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(messages.select_prompt)
+        .max_length(MAX_VISIBLE_ASSETS)
+        .items(&items)
+        .interact_opt()
+        .map_err(|e| HandlerError::new(e.to_string()))?;
+    match indexes {
+        Some(indexes) if !indexes.is_empty() => Ok(assets
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| indexes.contains(index))
+            .map(|(_, asset)| asset)
+            .collect()),
+        _ => Err(HandlerError::op_cancelled(messages.quit_select)),
+    }
+}
+
+const MAX_VISIBLE_ASSETS: usize = 15;
+
 fn assets_names(assets: &[Asset]) -> Vec<String> {
-    assets.iter().map(|x| x.show_name().to_string()).collect()
+    assets.iter().map(asset_item).collect()
+}
+
+/// Size and download count help to spot the "official" asset
+fn asset_item(asset: &Asset) -> String {
+    let size = asset.size.map(|x| HumanBytes(x).to_string());
+    let downloads = asset.download_count.map(|x| match x {
+        1 => "1 download".to_string(),
+        x => format!("{} downloads", x),
+    });
+    let details = [size, downloads].into_iter().flatten().join(", ");
+    if details.is_empty() {
+        asset.show_name().to_string()
+    } else {
+        format!("{} ({})", asset.show_name(), details)
+    }
 }
 
-fn find_asset_by_name(name: &str, assets: Vec<Asset>) -> Asset {
-    assets.into_iter().find(|x| x.is_same_name(name)).unwrap()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Some(1_258_291), Some(42), "helloworld.tar.gz (1.20 MiB, 42 downloads)"; "size and downloads")]
+    #[test_case(Some(512), Some(1), "helloworld.tar.gz (512 B, 1 download)"; "one download")]
+    #[test_case(Some(512), None, "helloworld.tar.gz (512 B)"; "only size")]
+    #[test_case(None, None, "helloworld.tar.gz"; "no details")]
+    fn item(size: Option<u64>, download_count: Option<u64>, expected: &str) {
+        let asset = Asset {
+            name: "helloworld.tar.gz".into(),
+            display_name: None,
+            download_url: "any".into(),
+            api_url: None,
+            content_type: None,
+            size,
+            download_count,
+            updated_at: None,
+        };
+
+        assert_eq!(expected, asset_item(&asset));
+    }
 }
@@ -1,8 +1,9 @@
 use std::time::Duration;
 
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 
 use crate::cli::color::Color;
+use crate::cli::output::{self, message};
 
 // NOTE: spinner ticks and duration are from
 // https://github.com/sindresorhus/cli-spinners/blob/00de8fbeee16fa49502fa4f687449f70f2c8ca2c/spinners.json#L2-L16
@@ -13,6 +14,7 @@ pub struct Spinner {
     pb: ProgressBar,
     #[allow(dead_code)]
     end_message: String,
+    attached: bool,
 }
 
 impl Spinner {
@@ -25,21 +27,46 @@ impl Spinner {
                 .unwrap(),
         );
         pb.set_message(message);
-        Self { pb, end_message }
+        if output::is_quiet() {
+            pb.set_draw_target(ProgressDrawTarget::hidden());
+        }
+        Self {
+            pb,
+            end_message,
+            attached: false,
+        }
+    }
+
+    /// Show the spinner together with the other ones of concurrent installations
+    pub fn attach(self, multi_progress: &MultiProgress) -> Self {
+        if output::is_quiet() {
+            return self;
+        }
+        Self {
+            pb: multi_progress.add(self.pb),
+            attached: true,
+            ..self
+        }
     }
 
     pub fn show(&self) {
         self.pb.enable_steady_tick(TICK_DURATION);
     }
 
-    #[allow(dead_code)]
     pub fn finish(&self) {
         self.pb.finish_and_clear();
     }
 
     pub fn finish_with_message(&self, message: &str) {
+        if self.attached {
+            // Printing a message would break the other spinners, so the message replaces this one
+            self.pb
+                .set_style(ProgressStyle::default_spinner().template("{msg}").unwrap());
+            self.pb.finish_with_message(message.to_string());
+            return;
+        }
         self.pb.finish_and_clear();
-        println!("{}", message);
+        message!("{}", message);
     }
 
     #[allow(dead_code)]
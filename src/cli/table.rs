@@ -0,0 +1,54 @@
+/// Plain text table with left aligned columns separated by two spaces, one line per row
+pub fn table<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    let header = header.map(String::from);
+    let lines = std::iter::once(&header).chain(rows.iter());
+    let widths: [usize; N] = std::array::from_fn(|column| {
+        lines
+            .clone()
+            .map(|row| row[column].len())
+            .max()
+            .unwrap_or_default()
+    });
+    lines
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:width$}", cell))
+                .collect::<Vec<_>>()
+                .join("  ");
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_are_aligned() {
+        let rows = [
+            ["v1.0.0-rc.1", "2024-05-31", "yes"].map(String::from),
+            ["v0.9.0", "-", "no"].map(String::from),
+        ];
+
+        let result = table(["TAG", "PUBLISHED", "PRE-RELEASE"], &rows);
+
+        assert_eq!(
+            "\
+TAG          PUBLISHED   PRE-RELEASE
+v1.0.0-rc.1  2024-05-31  yes
+v0.9.0       -           no
+",
+            result
+        );
+    }
+
+    #[test]
+    fn no_rows() {
+        let result = table(["NAME", "SIZE"], &[]);
+
+        assert_eq!("NAME  SIZE\n", result);
+    }
+}
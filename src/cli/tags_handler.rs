@@ -0,0 +1,72 @@
+use crate::cli::github_release::{fetch_releases_for, github_client_for};
+use crate::cli::output;
+use crate::cli::result::HandlerResult;
+use crate::github::release::{ReleaseFilter, Tag};
+use crate::github::repository::Repository;
+
+pub struct TagsHandler {
+    repository: Repository,
+    pattern: Option<Tag>,
+}
+
+impl TagsHandler {
+    pub fn new(repository: Repository, pattern: Option<String>) -> Self {
+        Self {
+            repository,
+            pattern: pattern.map(Tag),
+        }
+    }
+
+    /// Only the tags are written to stdout (one per line), so that they can be used by scripts
+    /// and shell completions
+    pub fn run(&self) -> HandlerResult {
+        output::reserve_stdout();
+        let github = github_client_for(&self.repository);
+        let filter = ReleaseFilter {
+            pre_release: true,
+            draft: false,
+        };
+        let releases = fetch_releases_for(&github, &self.repository, filter)?;
+        releases
+            .iter()
+            .map(|x| &x.tag)
+            .filter(|x| {
+                self.pattern
+                    .as_ref()
+                    .is_none_or(|pattern| matches(pattern, x))
+            })
+            .for_each(|x| println!("{}", x.0));
+        Ok(())
+    }
+}
+
+/// Like `--tag`, the pattern can be a semver range (e.g. `^1.2`) or a wildcard (e.g. `v1.2.*`).
+/// Any other pattern is a prefix of the tags (e.g. `v1.2`)
+fn matches(pattern: &Tag, tag: &Tag) -> bool {
+    if let Some(range) = pattern.version_req() {
+        return tag.semver().is_some_and(|x| range.matches(&x));
+    }
+    match pattern.wildcard() {
+        Some(wildcard) => wildcard.matches(&tag.0),
+        None => tag.0.starts_with(&pattern.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("v1.2", "v1.2.3", true; "prefix")]
+    #[test_case("v1.2", "v1.3.0", false; "other prefix")]
+    #[test_case("v1.*.0", "v1.4.0", true; "wildcard")]
+    #[test_case("v1.*.0", "v1.4.1", false; "wildcard not matching")]
+    #[test_case("^1.2", "v1.9.0", true; "semver range")]
+    #[test_case("^1.2", "v2.0.0", false; "semver range not matching")]
+    #[test_case("^1.2", "nightly", false; "semver range with non semver tag")]
+    fn tag_matches_pattern(pattern: &str, tag: &str, expected: bool) {
+        let result = matches(&Tag(pattern.to_string()), &Tag(tag.to_string()));
+
+        assert_eq!(expected, result);
+    }
+}
@@ -1,8 +1,9 @@
-use crate::cli::github_release::{check_has_assets, fetch_release_for};
+use crate::cli::github_release::{check_has_assets, fetch_release_for, github_client_for};
 use crate::cli::result::{HandlerError, HandlerResult};
+use crate::cli::root_command::LatestStrategy;
 use crate::cli::select_assets;
 use crate::github::client::GithubClient;
-use crate::github::release::{Asset, Release};
+use crate::github::release::{Asset, Release, ReleaseFilter, Tag};
 use crate::github::repository::Repository;
 use crate::github::tagged_asset::TaggedAsset;
 
@@ -16,8 +17,8 @@ impl UntagHandler {
     }
 
     pub fn run(&self) -> HandlerResult {
-        let github = GithubClient::from_environment();
-        let release = Self::fetch_latest_release(&github, &self.repository)?;
+        let github = github_client_for(&self.repository);
+        let release = Self::fetch_release(&github, &self.repository)?;
         check_has_assets(&release)?;
         let selected_asset = Self::ask_select_asset(release.assets)?;
         let untagged = TaggedAsset::untag(&release.tag, &selected_asset);
@@ -25,11 +26,17 @@ impl UntagHandler {
         Ok(())
     }
 
-    fn fetch_latest_release(
+    fn fetch_release(
         github: &GithubClient,
         repository: &Repository,
     ) -> Result<Release, HandlerError> {
-        fetch_release_for(github, repository, None)
+        fetch_release_for(
+            github,
+            repository,
+            repository.tag.clone().map(Tag).as_ref(),
+            ReleaseFilter::default(),
+            LatestStrategy::default(),
+        )
     }
 
     fn ask_select_asset(assets: Vec<Asset>) -> select_assets::AskSelectAssetResult {
@@ -0,0 +1,256 @@
+use crate::env_var;
+use crate::github::repository::Repository;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Formatter;
+use std::path::{Path, PathBuf};
+
+pub mod selection_state;
+pub mod weights;
+
+pub const DRA_CONFIG: &str = "DRA_CONFIG";
+pub const DRA_PREFER_LIBC: &str = "DRA_PREFER_LIBC";
+pub const DRA_TIMEOUT: &str = "DRA_TIMEOUT";
+pub const DRA_CA_BUNDLE: &str = "DRA_CA_BUNDLE";
+pub const DRA_GITHUB_CLIENT_ID: &str = "DRA_GITHUB_CLIENT_ID";
+
+/// User configuration, read from `$DRA_CONFIG` or `<config dir>/dra/config.toml`
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    /// SPDX identifiers of the allowed licenses (case insensitive).
+    /// `*` and `?` wildcards are supported (e.g. `BSD-*`)
+    #[serde(alias = "LICENSE_FILTER")]
+    pub license_filter: Vec<String>,
+    /// Libc preferred by automatic mode (`musl` or `gnu`)
+    pub prefer_libc: Option<String>,
+    /// Repository patterns (e.g. `devmatteini/*`) mapped to ordered regex rules
+    /// that automatic mode consults before its built-in rules
+    pub selection_rules: HashMap<String, Vec<String>>,
+    /// Consider pre-releases when resolving the latest release
+    pub pre_release: bool,
+    /// URL prefixes (e.g. `https://github.com/`) mapped to their replacement (e.g. a mirror),
+    /// applied to asset URLs before downloading
+    pub url_rewrites: HashMap<String, String>,
+    /// Short names (e.g. `rg`) of repositories (e.g. `BurntSushi/ripgrep`)
+    pub aliases: HashMap<String, String>,
+}
+
+impl Config {
+    /// Load the configuration file, or the default configuration if it doesn't exist
+    pub fn load() -> Result<Self, ConfigError> {
+        match config_path() {
+            Some(path) if path.is_file() => Self::from_file(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| ConfigError::Read(path.to_path_buf(), e))?;
+        toml::from_str(&content).map_err(|e| ConfigError::Parse(path.to_path_buf(), e.to_string()))
+    }
+
+    pub fn is_license_allowed(&self, license: &str) -> bool {
+        self.license_filter
+            .iter()
+            .any(|pattern| wildmatch::WildMatch::new_case_insensitive(pattern).matches(license))
+    }
+
+    /// The repository of the alias, or the input itself when it isn't an alias
+    pub fn expand_alias<'a>(&'a self, input: &'a str) -> &'a str {
+        self.aliases.get(input).map(String::as_str).unwrap_or(input)
+    }
+
+    /// The URL with the longest matching prefix of `url_rewrites` replaced, if any
+    pub fn rewrite_url(&self, url: &str) -> Option<String> {
+        self.url_rewrites
+            .iter()
+            .filter(|(prefix, _)| url.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, replacement)| format!("{}{}", replacement, &url[prefix.len()..]))
+    }
+}
+
+/// An exact repository match takes precedence over wildcard patterns (e.g. `devmatteini/*`),
+/// otherwise the longest matching pattern is used
+pub fn find_by_repository<'a, T>(
+    values: &'a HashMap<String, T>,
+    repository: &Repository,
+) -> Option<&'a T> {
+    let name = repository.to_string().to_lowercase();
+    values
+        .iter()
+        .filter(|(pattern, _)| wildmatch::WildMatch::new_case_insensitive(pattern).matches(&name))
+        .max_by_key(|(pattern, _)| (pattern.to_lowercase() == name, pattern.len()))
+        .map(|(_, value)| value)
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env_var::string(DRA_CONFIG) {
+        return Some(PathBuf::from(path));
+    }
+    config_dir().map(|dir| dir.join("dra").join("config.toml"))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn config_dir() -> Option<PathBuf> {
+    env_var::string("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env_var::string("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+#[cfg(target_os = "windows")]
+pub fn config_dir() -> Option<PathBuf> {
+    env_var::string("APPDATA").map(PathBuf::from)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn cache_dir() -> Option<PathBuf> {
+    env_var::string("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env_var::string("HOME").map(|home| PathBuf::from(home).join(".cache")))
+}
+
+#[cfg(target_os = "windows")]
+pub fn cache_dir() -> Option<PathBuf> {
+    env_var::string("LOCALAPPDATA").map(|x| PathBuf::from(x).join("cache"))
+}
+
+/// Default install directory, `None` to install in the current directory
+#[cfg(not(target_os = "windows"))]
+pub fn bin_dir() -> Option<PathBuf> {
+    None
+}
+
+/// Windows has no standard directory for the executables of the user, like `~/.local/bin`
+#[cfg(target_os = "windows")]
+pub fn bin_dir() -> Option<PathBuf> {
+    env_var::string("LOCALAPPDATA").map(|x| PathBuf::from(x).join("dra").join("bin"))
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(PathBuf, std::io::Error),
+    Parse(PathBuf, String),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Read(path, e) => {
+                write!(f, "Error reading config file {}: {}", path.display(), e)
+            }
+            ConfigError::Parse(path, e) => {
+                write!(f, "Invalid config file {}: {}", path.display(), e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn parse_license_filter() {
+        let config: Config = toml::from_str(r#"license_filter = ["MIT", "BSD-*"]"#).unwrap();
+
+        assert_eq!(vec!["MIT", "BSD-*"], config.license_filter);
+    }
+
+    #[test]
+    fn parse_uppercase_license_filter() {
+        let config: Config = toml::from_str(r#"LICENSE_FILTER = ["Apache-2.0"]"#).unwrap();
+
+        assert_eq!(vec!["Apache-2.0"], config.license_filter);
+    }
+
+    #[test]
+    fn parse_empty() {
+        let config: Config = toml::from_str("").unwrap();
+
+        assert_eq!(Config::default(), config);
+    }
+
+    #[test]
+    fn parse_pre_release() {
+        let config: Config = toml::from_str("pre_release = true").unwrap();
+
+        assert!(config.pre_release);
+    }
+
+    #[test]
+    fn parse_selection_rules() {
+        let config: Config = toml::from_str(
+            r#"
+[selection_rules]
+"devmatteini/*" = ["musl", "static", "gnu"]
+"#,
+        )
+        .unwrap();
+        let repository = Repository::try_parse("devmatteini/dra").unwrap();
+
+        assert_eq!(
+            Some(&vec![
+                "musl".to_string(),
+                "static".to_string(),
+                "gnu".to_string()
+            ]),
+            find_by_repository(&config.selection_rules, &repository)
+        );
+    }
+
+    #[test_case("rg", "BurntSushi/ripgrep"; "alias")]
+    #[test_case("bat", "bat"; "not an alias")]
+    #[test_case("devmatteini/dra", "devmatteini/dra"; "repository")]
+    fn expand_alias(input: &str, expected: &str) {
+        let config: Config = toml::from_str(
+            r#"
+[aliases]
+rg = "BurntSushi/ripgrep"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(expected, config.expand_alias(input));
+    }
+
+    #[test_case(
+        "https://github.com/devmatteini/dra/releases/download/0.1.0/dra.tar.gz",
+        Some("https://mirror.corp/github/devmatteini/dra/releases/download/0.1.0/dra.tar.gz");
+        "rewritten"
+    )]
+    #[test_case(
+        "https://github.com/devmatteini/dra-tests/releases/download/0.1.0/dra.tar.gz",
+        Some("https://tests.mirror.corp/releases/download/0.1.0/dra.tar.gz");
+        "longest prefix"
+    )]
+    #[test_case("https://example.com/dra.tar.gz", None; "no match")]
+    fn rewrite_url(url: &str, expected: Option<&str>) {
+        let config: Config = toml::from_str(
+            r#"
+[url_rewrites]
+"https://github.com/" = "https://mirror.corp/github/"
+"https://github.com/devmatteini/dra-tests/" = "https://tests.mirror.corp/"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(expected.map(String::from), config.rewrite_url(url));
+    }
+
+    #[test_case("MIT", true; "literal")]
+    #[test_case("BSD-3-Clause", true; "wildcard")]
+    #[test_case("GPL-3.0-only", false; "not allowed")]
+    #[test_case("mit", true; "case insensitive")]
+    fn is_license_allowed(license: &str, expected: bool) {
+        let config = Config {
+            license_filter: vec!["MIT".into(), "Apache-2.0".into(), "BSD-*".into()],
+            ..Default::default()
+        };
+
+        assert_eq!(expected, config.is_license_allowed(license));
+    }
+}
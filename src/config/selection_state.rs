@@ -0,0 +1,102 @@
+use crate::env_var;
+use crate::github::repository::Repository;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Last asset pattern (untagged asset name) selected in interactive mode for each repository
+#[derive(Debug, Default, PartialEq)]
+pub struct SelectionState {
+    selections: BTreeMap<String, String>,
+}
+
+impl SelectionState {
+    /// A missing or invalid state file is treated as an empty state
+    pub fn load() -> Self {
+        state_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    fn parse(content: &str) -> Self {
+        Self {
+            selections: serde_json::from_str(content).unwrap_or_default(),
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = state_path().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Cannot find the state directory",
+            )
+        })?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&self.selections)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, content)
+    }
+
+    pub fn get(&self, repository: &Repository) -> Option<&str> {
+        self.selections.get(&key(repository)).map(String::as_str)
+    }
+
+    pub fn set(&mut self, repository: &Repository, pattern: String) {
+        self.selections.insert(key(repository), pattern);
+    }
+}
+
+fn key(repository: &Repository) -> String {
+    repository.to_string().to_lowercase()
+}
+
+fn state_path() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join("dra").join("selections.json"))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn state_dir() -> Option<PathBuf> {
+    env_var::string("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            env_var::string("HOME").map(|home| PathBuf::from(home).join(".local").join("state"))
+        })
+}
+
+#[cfg(target_os = "windows")]
+fn state_dir() -> Option<PathBuf> {
+    env_var::string("LOCALAPPDATA").map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_selection() {
+        let state =
+            SelectionState::parse(r#"{"devmatteini/dra-tests": "helloworld_{tag}.tar.gz"}"#);
+        let repository = Repository::try_parse("DevMatteini/dra-tests").unwrap();
+
+        assert_eq!(Some("helloworld_{tag}.tar.gz"), state.get(&repository));
+    }
+
+    #[test]
+    fn invalid_state() {
+        let state = SelectionState::parse("not json");
+
+        assert_eq!(SelectionState::default(), state);
+    }
+
+    #[test]
+    fn set_selection() {
+        let mut state = SelectionState::default();
+        let repository = Repository::try_parse("devmatteini/dra-tests").unwrap();
+
+        state.set(&repository, "helloworld_{tag}.zip".to_string());
+
+        assert_eq!(Some("helloworld_{tag}.zip"), state.get(&repository));
+    }
+}
@@ -0,0 +1,138 @@
+use crate::config::{ConfigError, find_by_repository};
+use crate::github::release::Tag;
+use crate::github::repository::Repository;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use wildmatch::WildMatch;
+
+/// Asset selection rules for a repository, used in automatic mode
+/// before the built-in operating system/architecture rules.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AssetWeights {
+    /// Asset name pattern with `{tag}`, `{version}`, `{os}` and `{arch}` placeholders
+    /// and `*`/`?` wildcards (e.g. `dra-{version}-{arch}-*-{os}-musl.tar.gz`)
+    pub pattern: Option<String>,
+    /// Score added to the assets whose name contains the key (case insensitive).
+    /// Negative values can be used to penalize assets
+    #[serde(default)]
+    pub scores: HashMap<String, i32>,
+}
+
+impl AssetWeights {
+    pub fn pattern_for(&self, tag: &Tag, os: &str, arch: &str) -> Option<WildMatch> {
+        self.pattern.as_ref().map(|pattern| {
+            let pattern = pattern
+                .replace("{tag}", &tag.0)
+                .replace("{version}", &tag.version())
+                .replace("{os}", os)
+                .replace("{arch}", arch);
+            WildMatch::new_case_insensitive(&pattern)
+        })
+    }
+
+    pub fn score(&self, asset_name: &str) -> i32 {
+        let asset_name = asset_name.to_lowercase();
+        self.scores
+            .iter()
+            .filter(|(key, _)| asset_name.contains(&key.to_lowercase()))
+            .map(|(_, score)| score)
+            .sum()
+    }
+}
+
+/// Repository patterns (e.g. `devmatteini/dra` or `devmatteini/*`) mapped to their asset weights
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct WeightFile(HashMap<String, AssetWeights>);
+
+impl WeightFile {
+    pub fn for_repository(&self, repository: &Repository) -> Option<&AssetWeights> {
+        find_by_repository(&self.0, repository)
+    }
+}
+
+pub struct WeightFileLoader;
+
+impl WeightFileLoader {
+    pub fn load(path: &Path) -> Result<WeightFile, ConfigError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| ConfigError::Read(path.to_path_buf(), e))?;
+        Self::parse(&content).map_err(|e| ConfigError::Parse(path.to_path_buf(), e))
+    }
+
+    fn parse(content: &str) -> Result<WeightFile, String> {
+        toml::from_str(content).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const WEIGHT_FILE: &str = r#"
+["devmatteini/dra"]
+pattern = "dra-{version}-{arch}-*-{os}-*"
+scores = { musl = 10, gnu = -5 }
+
+["devmatteini/*"]
+scores = { zip = 1 }
+"#;
+
+    #[test_case("devmatteini/dra", Some("dra-{version}-{arch}-*-{os}-*"); "exact match")]
+    #[test_case("DevMatteini/Dra", Some("dra-{version}-{arch}-*-{os}-*"); "case insensitive")]
+    #[test_case("devmatteini/dra-tests", None; "wildcard match")]
+    fn for_repository(repository: &str, expected_pattern: Option<&str>) {
+        let weight_file = WeightFileLoader::parse(WEIGHT_FILE).unwrap();
+        let repository = Repository::try_parse(repository).unwrap();
+
+        let result = weight_file.for_repository(&repository).unwrap();
+
+        assert_eq!(expected_pattern, result.pattern.as_deref());
+    }
+
+    #[test]
+    fn no_matching_repository() {
+        let weight_file = WeightFileLoader::parse(WEIGHT_FILE).unwrap();
+        let repository = Repository::try_parse("sharkdp/bat").unwrap();
+
+        assert!(weight_file.for_repository(&repository).is_none());
+    }
+
+    #[test]
+    fn unknown_field() {
+        let result = WeightFileLoader::parse("[\"devmatteini/dra\"]\npatern = \"dra-*\"");
+
+        assert!(result.is_err());
+    }
+
+    #[test_case("dra-0.1.0-x86_64-unknown-linux-musl.tar.gz", 10; "positive")]
+    #[test_case("dra-0.1.0-x86_64-unknown-linux-gnu.tar.gz", -5; "negative")]
+    #[test_case("dra-0.1.0-x86_64-unknown-linux-MUSL-gnu.tar.gz", 5; "sum")]
+    #[test_case("dra-0.1.0-x86_64-apple-darwin.tar.gz", 0; "no score")]
+    fn score(asset_name: &str, expected: i32) {
+        let weights = AssetWeights {
+            pattern: None,
+            scores: HashMap::from([("musl".to_string(), 10), ("gnu".to_string(), -5)]),
+        };
+
+        assert_eq!(expected, weights.score(asset_name));
+    }
+
+    #[test]
+    fn pattern_with_placeholders() {
+        let weights = AssetWeights {
+            pattern: Some("dra-{version}-{arch}-*-{os}-*".to_string()),
+            scores: HashMap::new(),
+        };
+
+        let pattern = weights
+            .pattern_for(&Tag("v0.1.0".to_string()), "linux", "x86_64")
+            .unwrap();
+
+        assert!(pattern.matches("dra-0.1.0-x86_64-unknown-linux-musl.tar.gz"));
+        assert!(!pattern.matches("dra-0.1.0-aarch64-unknown-linux-musl.tar.gz"));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::config::cache_dir;
+use crate::github::client::DownloadStream;
+use crate::github::metadata_cache::url_hash;
+use log::debug;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+/// Downloaded assets saved on disk by their url, so that they are available offline
+#[derive(Debug, Clone)]
+pub struct AssetCache {
+    dir: PathBuf,
+}
+
+impl AssetCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// `<cache dir>/dra/assets` (e.g. `~/.cache/dra/assets`)
+    pub fn from_environment() -> Option<Self> {
+        cache_dir().map(|dir| Self::new(dir.join("dra").join("assets")))
+    }
+
+    pub fn open(&self, url: &str) -> Option<DownloadStream> {
+        let file = File::open(self.path(url)).ok()?;
+        let content_length = file.metadata().ok().map(|x| x.len());
+        Some(DownloadStream {
+            reader: Box::new(file),
+            content_length,
+        })
+    }
+
+    /// The asset is saved while it's read, and it's cached only when it's read completely.
+    /// Failing to write the cache doesn't make the download fail
+    pub fn save_while_reading(&self, url: &str, stream: DownloadStream) -> DownloadStream {
+        let temporary = self.dir.join(format!(
+            "{}.{}.part",
+            url_hash(url),
+            uuid::Uuid::new_v4().simple()
+        ));
+        let file = std::fs::create_dir_all(&self.dir).and_then(|_| File::create(&temporary));
+        match file {
+            Ok(file) => DownloadStream {
+                reader: Box::new(CachingReader {
+                    inner: stream.reader,
+                    file: Some(file),
+                    temporary,
+                    path: self.path(url),
+                }),
+                content_length: stream.content_length,
+            },
+            Err(e) => {
+                debug!("Cannot cache the asset {}: {}", url, e);
+                stream
+            }
+        }
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        self.dir.join(url_hash(url))
+    }
+}
+
+struct CachingReader {
+    inner: Box<dyn Read + Send>,
+    /// `None` once the asset is cached, or when writing the cache failed
+    file: Option<File>,
+    temporary: PathBuf,
+    path: PathBuf,
+}
+
+impl CachingReader {
+    fn discard(&mut self) {
+        self.file = None;
+        std::fs::remove_file(&self.temporary).ok();
+    }
+}
+
+impl Read for CachingReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        let Some(file) = self.file.as_mut() else {
+            return Ok(read);
+        };
+        if read == 0 {
+            self.file = None;
+            if let Err(e) = std::fs::rename(&self.temporary, &self.path) {
+                debug!("Cannot cache the asset {}: {}", self.path.display(), e);
+                self.discard();
+            }
+        } else if let Err(e) = file.write_all(&buf[..read]) {
+            debug!("Cannot cache the asset {}: {}", self.path.display(), e);
+            self.discard();
+        }
+        Ok(read)
+    }
+}
+
+/// Partially read assets are not cached
+impl Drop for CachingReader {
+    fn drop(&mut self) {
+        if self.file.is_some() {
+            self.discard();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str =
+        "https://github.com/devmatteini/dra-tests/releases/download/v1.0.0/helloworld.tar.gz";
+
+    #[test]
+    fn asset_read_completely_is_cached() {
+        let cache = temporary_cache("asset_read_completely_is_cached");
+        let mut stream = cache.save_while_reading(URL, stream(b"content"));
+
+        std::io::copy(&mut stream.reader, &mut std::io::sink()).unwrap();
+        drop(stream);
+
+        let mut content = String::new();
+        cache
+            .open(URL)
+            .unwrap()
+            .reader
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!("content", content);
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn asset_read_partially_is_not_cached() {
+        let cache = temporary_cache("asset_read_partially_is_not_cached");
+        let mut stream = cache.save_while_reading(URL, stream(b"content"));
+
+        stream.reader.read_exact(&mut [0; 3]).unwrap();
+        drop(stream);
+
+        assert!(cache.open(URL).is_none());
+        assert_eq!(0, std::fs::read_dir(&cache.dir).unwrap().count());
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    fn stream(content: &'static [u8]) -> DownloadStream {
+        DownloadStream {
+            reader: Box::new(content),
+            content_length: Some(content.len() as u64),
+        }
+    }
+
+    fn temporary_cache(name: &str) -> AssetCache {
+        AssetCache::new(
+            std::env::temp_dir()
+                .join("dra-asset-cache-tests")
+                .join(name),
+        )
+    }
+}
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+// DOCS: https://developer.atlassian.com/cloud/bitbucket/rest/api-group-downloads/
+#[derive(Deserialize, Debug)]
+pub struct BitbucketDownloadsResponse {
+    pub values: Vec<BitbucketDownloadResponse>,
+    /// Url of the next page, missing in the last one
+    pub next: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BitbucketDownloadResponse {
+    pub name: String,
+    pub size: Option<u64>,
+    pub downloads: Option<u64>,
+    pub created_on: Option<String>,
+    pub links: BitbucketLinksResponse,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BitbucketLinksResponse {
+    /// Redirects to the file
+    #[serde(rename = "self")]
+    pub self_link: BitbucketLinkResponse,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BitbucketLinkResponse {
+    pub href: String,
+}
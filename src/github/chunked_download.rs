@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Size of each ranged request
+pub const CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+pub type FetchRange = dyn Fn(u64, u64) -> Result<Vec<u8>, String> + Send + Sync;
+
+/// Inclusive byte ranges (`start`, `end`) of `total` bytes split in chunks of `chunk_size`
+pub fn chunk_ranges(total: u64, chunk_size: u64) -> Vec<(u64, u64)> {
+    (0..total)
+        .step_by(chunk_size as usize)
+        .map(|start| (start, (start + chunk_size).min(total) - 1))
+        .collect()
+}
+
+/// Total size from a `Content-Range` header (e.g. `bytes 0-1023/4096`)
+pub fn parse_content_range(header: &str) -> Option<u64> {
+    header
+        .strip_prefix("bytes ")
+        .and_then(|x| x.rsplit_once('/'))
+        .and_then(|(_, total)| total.parse().ok())
+}
+
+/// Reads the chunks in order while they are fetched concurrently.
+///
+/// Workers fetch at most `window` chunks ahead of the reader, so memory usage is bounded.
+pub struct ChunkedReader {
+    shared: Arc<Shared>,
+    total_chunks: usize,
+    current: Cursor<Vec<u8>>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+struct State {
+    next_to_fetch: usize,
+    next_to_read: usize,
+    fetched: BTreeMap<usize, Result<Vec<u8>, String>>,
+    cancelled: bool,
+}
+
+impl ChunkedReader {
+    /// `first_chunk` is the content of the first range, already fetched
+    pub fn start(
+        ranges: Vec<(u64, u64)>,
+        first_chunk: Vec<u8>,
+        workers: usize,
+        fetch: Arc<FetchRange>,
+    ) -> Self {
+        let total_chunks = ranges.len();
+        let window = workers * 2;
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                next_to_fetch: 1,
+                next_to_read: 1,
+                fetched: BTreeMap::new(),
+                cancelled: false,
+            }),
+            changed: Condvar::new(),
+        });
+        let ranges = Arc::new(ranges);
+        for _ in 0..workers.min(total_chunks.saturating_sub(1)) {
+            let shared = shared.clone();
+            let ranges = ranges.clone();
+            let fetch = fetch.clone();
+            std::thread::spawn(move || worker(&shared, &ranges, window, fetch.as_ref()));
+        }
+        Self {
+            shared,
+            total_chunks,
+            current: Cursor::new(first_chunk),
+        }
+    }
+
+    fn next_chunk(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+        let mut state = self.shared.state.lock().unwrap();
+        if state.next_to_read >= self.total_chunks {
+            return Ok(None);
+        }
+        loop {
+            let index = state.next_to_read;
+            if let Some(chunk) = state.fetched.remove(&index) {
+                state.next_to_read += 1;
+                self.shared.changed.notify_all();
+                return chunk.map(Some).map_err(std::io::Error::other);
+            }
+            state = self.shared.changed.wait(state).unwrap();
+        }
+    }
+}
+
+fn worker(shared: &Shared, ranges: &[(u64, u64)], window: usize, fetch: &FetchRange) {
+    loop {
+        let index = {
+            let mut state = shared.state.lock().unwrap();
+            while !state.cancelled
+                && state.next_to_fetch < ranges.len()
+                && state.next_to_fetch >= state.next_to_read + window
+            {
+                state = shared.changed.wait(state).unwrap();
+            }
+            if state.cancelled || state.next_to_fetch >= ranges.len() {
+                return;
+            }
+            state.next_to_fetch += 1;
+            state.next_to_fetch - 1
+        };
+        let (start, end) = ranges[index];
+        let mut pending = PendingChunk {
+            shared,
+            index,
+            chunk: None,
+        };
+        pending.chunk = Some(fetch(start, end));
+    }
+}
+
+/// Hands the chunk over to the reader when dropped, even when the worker panics while fetching
+/// it, so that the reader fails instead of waiting forever
+struct PendingChunk<'a> {
+    shared: &'a Shared,
+    index: usize,
+    chunk: Option<Result<Vec<u8>, String>>,
+}
+
+impl Drop for PendingChunk<'_> {
+    fn drop(&mut self) {
+        let chunk = self
+            .chunk
+            .take()
+            .unwrap_or_else(|| Err(format!("Download of chunk {} stopped", self.index)));
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.fetched.insert(self.index, chunk);
+            self.shared.changed.notify_all();
+        }
+    }
+}
+
+impl Read for ChunkedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let bytes = self.current.read(buf)?;
+            if bytes > 0 || buf.is_empty() {
+                return Ok(bytes);
+            }
+            match self.next_chunk()? {
+                Some(chunk) => self.current = Cursor::new(chunk),
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+impl Drop for ChunkedReader {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.cancelled = true;
+            self.shared.changed.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(10, 4, vec![(0, 3), (4, 7), (8, 9)]; "last chunk is smaller")]
+    #[test_case(8, 4, vec![(0, 3), (4, 7)]; "exact chunks")]
+    #[test_case(3, 4, vec![(0, 2)]; "single chunk")]
+    fn ranges(total: u64, chunk_size: u64, expected: Vec<(u64, u64)>) {
+        assert_eq!(expected, chunk_ranges(total, chunk_size));
+    }
+
+    #[test_case("bytes 0-1023/4096", Some(4096); "known size")]
+    #[test_case("bytes 0-1023/*", None; "unknown size")]
+    #[test_case("invalid", None; "invalid")]
+    fn content_range(header: &str, expected: Option<u64>) {
+        assert_eq!(expected, parse_content_range(header));
+    }
+
+    #[test]
+    fn chunks_are_read_in_order() {
+        let content: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let ranges = chunk_ranges(content.len() as u64, 64);
+        let source = content.clone();
+        let fetch = Arc::new(move |start: u64, end: u64| {
+            // Later chunks are faster, so they are fetched out of order
+            std::thread::sleep(std::time::Duration::from_micros(1000 - start));
+            Ok(source[start as usize..=end as usize].to_vec())
+        });
+
+        let mut reader = ChunkedReader::start(ranges, content[..64].to_vec(), 4, fetch);
+        let mut result = vec![];
+        reader.read_to_end(&mut result).unwrap();
+
+        assert_eq!(content, result);
+    }
+
+    #[test]
+    fn chunk_error() {
+        let ranges = chunk_ranges(256, 64);
+        let fetch = Arc::new(|start: u64, _: u64| {
+            if start == 128 {
+                Err("connection reset".to_string())
+            } else {
+                Ok(vec![0; 64])
+            }
+        });
+
+        let mut reader = ChunkedReader::start(ranges, vec![0; 64], 2, fetch);
+        let result = reader.read_to_end(&mut vec![]);
+
+        assert_eq!("connection reset", result.unwrap_err().to_string());
+    }
+
+    #[test]
+    fn worker_panic() {
+        let ranges = chunk_ranges(256, 64);
+        let fetch = Arc::new(|start: u64, _: u64| {
+            if start == 128 {
+                panic!("worker panic");
+            }
+            Ok(vec![0; 64])
+        });
+
+        let mut reader = ChunkedReader::start(ranges, vec![0; 64], 2, fetch);
+        let result = reader.read_to_end(&mut vec![]);
+
+        assert_eq!(
+            "Download of chunk 2 stopped",
+            result.unwrap_err().to_string()
+        );
+    }
+}
@@ -1,56 +1,264 @@
 use crate::env_var;
+use crate::github::asset_cache::AssetCache;
+use crate::github::bitbucket_response::BitbucketDownloadsResponse;
+use crate::github::chunked_download::{
+    CHUNK_SIZE, ChunkedReader, chunk_ranges, parse_content_range,
+};
 use crate::github::constants::{
-    DRA_DISABLE_GITHUB_AUTHENTICATION, DRA_GITHUB_TOKEN, GH_TOKEN, GITHUB_TOKEN,
+    BITBUCKET_TOKEN, DRA_BITBUCKET_TOKEN, DRA_DISABLE_GITHUB_AUTHENTICATION, DRA_GITEA_TOKEN,
+    DRA_GITHUB_API, DRA_GITHUB_TOKEN, DRA_GITLAB_TOKEN, GH_ENTERPRISE_TOKEN, GH_TOKEN, GITEA_TOKEN,
+    GITHUB_ENTERPRISE_TOKEN, GITHUB_TOKEN, GITLAB_TOKEN,
 };
+use crate::github::credential_command::{CredentialCommand, CredentialError};
+use crate::github::device_flow::{AccessTokenResponse, DeviceCodeResponse};
 use crate::github::error::GithubError;
-use crate::github::release::{Asset, Release, Tag};
-use crate::github::release_response::ReleaseResponse;
-use crate::github::repository::Repository;
+use crate::github::gh_config;
+use crate::github::gitlab_response::GitlabReleaseResponse;
+use crate::github::graphql::{self, GraphqlRelease, GraphqlRequest, GraphqlResponse, ReleaseQuery};
+use crate::github::license;
+use crate::github::license_response::RepositoryLicenseResponse;
+use crate::github::metadata_cache::{CacheEntry, MetadataCache};
+use crate::github::package_response::{LayerResponse, ManifestResponse, RegistryTokenResponse};
+use crate::github::release::{Asset, Release, ReleaseFilter, Tag, is_api_asset_url};
+use crate::github::release_response::{AssetResponse, ReleaseResponse};
+use crate::github::repository::{Forge, Repository};
+use crate::github::retry::RetryPolicy;
+use crate::github::token_store::{self, TokenStorage};
+use base64::Engine;
+use log::debug;
+use serde::de::DeserializeOwned;
 use std::io::Read;
 use std::process::Command;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
+const RELEASES_PER_PAGE: usize = 100;
+/// Releases embed only the first page of their assets
+const EMBEDDED_ASSETS_LIMIT: usize = 30;
+const ASSETS_PER_PAGE: usize = 100;
+/// Default maximum page size of Gitea instances
+const GITEA_RELEASES_PER_PAGE: usize = 50;
+const GITHUB_API_URL: &str = "https://api.github.com";
+/// Hosts of github.com releases: browser downloads, API and the storage they redirect to
+const GITHUB_HOSTS: [&str; 3] = [
+    "github.com",
+    "api.github.com",
+    "objects.githubusercontent.com",
+];
+const GITHUB_LOGIN_URL: &str = "https://github.com/login";
+const BITBUCKET_API_URL: &str = "https://api.bitbucket.org/2.0";
+/// Maximum page size of Bitbucket API
+const BITBUCKET_DOWNLOADS_PER_PAGE: usize = 100;
+/// Metadata requests (e.g. release information) must complete within this time, unless a timeout is configured
+const DEFAULT_METADATA_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
 pub struct GithubClient {
     pub token: Option<String>,
+    gzip_encoding: bool,
+    retry: RetryPolicy,
+    timeout: Option<Duration>,
+    /// Without a proxy, the one of `ALL_PROXY`, `HTTPS_PROXY` or `HTTP_PROXY` is used (if any)
+    proxy: Option<ureq::Proxy>,
+    tls: Option<ureq::tls::TlsConfig>,
+    api_url: String,
+    forge: Forge,
+    cache: Option<MetadataCache>,
+    asset_cache: Option<AssetCache>,
+    /// Release information and assets are read only from the caches
+    offline: bool,
+    /// Shared by the clones of the client, so that connections are kept alive and reused across
+    /// the release requests and the asset downloads. It's built on first use, and built again
+    /// when the connection options (timeout, proxy or TLS) change
+    agent: Arc<OnceLock<ureq::Agent>>,
+}
+
+pub struct DownloadStream {
+    pub reader: Box<dyn Read + Send>,
+    pub content_length: Option<u64>,
 }
 
 impl GithubClient {
     pub fn new(token: Option<String>) -> Self {
-        Self { token }
+        Self {
+            token,
+            gzip_encoding: false,
+            retry: RetryPolicy::default(),
+            timeout: None,
+            proxy: None,
+            tls: None,
+            api_url: GITHUB_API_URL.to_string(),
+            forge: Forge::GitHub,
+            cache: None,
+            asset_cache: None,
+            offline: false,
+            agent: Arc::default(),
+        }
+    }
+
+    /// Use the output of an external credential helper (e.g. `pass show github-token`) as token
+    pub fn from_credential_command(cmd: &str) -> Result<Self, CredentialError> {
+        debug!("Using GitHub token from command: {}", cmd);
+        CredentialCommand::run(cmd).map(|token| Self::new(Some(token)))
+    }
+
+    /// Request gzip compressed responses for metadata requests (e.g. release information).
+    /// Asset downloads are never affected, since they are usually already compressed.
+    pub fn with_gzip_encoding(mut self, enabled: bool) -> Self {
+        self.gzip_encoding = enabled;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Maximum time to connect and to receive the response of each request.
+    /// Metadata requests use it as timeout of the whole request.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self.agent = Arc::default();
+        self
+    }
+
+    pub fn with_proxy(mut self, proxy: Option<ureq::Proxy>) -> Self {
+        self.proxy = proxy;
+        self.agent = Arc::default();
+        self
+    }
+
+    pub fn with_tls_config(mut self, tls: Option<ureq::tls::TlsConfig>) -> Self {
+        self.tls = tls;
+        self.agent = Arc::default();
+        self
+    }
+
+    /// Base URL of the REST API (e.g. `https://github.example.com/api/v3` for GitHub Enterprise Server)
+    pub fn with_api_url(mut self, api_url: String) -> Self {
+        self.api_url = api_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Releases of GitLab projects are fetched from the GitLab API (see `with_api_url`)
+    pub fn with_forge(mut self, forge: Forge) -> Self {
+        self.forge = forge;
+        self
+    }
+
+    /// Release information responses are cached with their ETag (see `MetadataCache`)
+    pub fn with_metadata_cache(mut self, cache: Option<MetadataCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Cached assets are not downloaded again, while the downloaded ones are saved to the cache
+    pub fn with_asset_cache(mut self, cache: Option<AssetCache>) -> Self {
+        self.asset_cache = cache;
+        self
+    }
+
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Same client, without sending the GitHub token (e.g. to download from a mirror)
+    pub fn without_token(&self) -> Self {
+        Self {
+            token: None,
+            ..self.clone()
+        }
+    }
+
+    /// GitLab tokens are read from `DRA_GITLAB_TOKEN` or `GITLAB_TOKEN`,
+    /// Gitea tokens from `DRA_GITEA_TOKEN` or `GITEA_TOKEN` and
+    /// Bitbucket access tokens from `DRA_BITBUCKET_TOKEN` or `BITBUCKET_TOKEN`.
+    /// GitHub tokens are scoped to their host (see `github_token_for`), `api_url` is `--github-api-url`
+    pub fn from_environment_for(repository: &Repository, api_url: Option<&str>) -> Self {
+        let forge = repository.forge();
+        let (dra_token, token) = match forge {
+            Forge::GitHub if repository.host.is_none() => return Self::from_environment(),
+            _ if is_authentication_disabled() => return Self::new(None),
+            Forge::GitHub => {
+                let is_explicit_api_url =
+                    api_url.is_some() || env_var::string(DRA_GITHUB_API).is_some();
+                let token = github_token_for(
+                    repository.host.as_deref(),
+                    is_explicit_api_url,
+                    || token_from_environment().map(|x| x.value),
+                    enterprise_token,
+                );
+                debug!("GitHub Enterprise Server token found: {}", token.is_some());
+                return Self::new(token);
+            }
+            Forge::GitLab => (DRA_GITLAB_TOKEN, GITLAB_TOKEN),
+            Forge::Gitea => (DRA_GITEA_TOKEN, GITEA_TOKEN),
+            Forge::Bitbucket => (DRA_BITBUCKET_TOKEN, BITBUCKET_TOKEN),
+        };
+        let token = env_var::string(dra_token).or_else(|| env_var::string(token));
+        debug!("{:?} token found: {}", forge, token.is_some());
+        Self::new(token)
     }
 
     pub fn from_environment() -> Self {
-        let is_auth_disabled = env_var::boolean(DRA_DISABLE_GITHUB_AUTHENTICATION);
-        if is_auth_disabled {
+        if is_authentication_disabled() {
+            debug!("GitHub authentication is disabled");
             return Self::new(None);
         }
 
-        let token = env_var::string(DRA_GITHUB_TOKEN)
-            .or_else(|| env_var::string(GITHUB_TOKEN))
-            .or_else(|| env_var::string(GH_TOKEN))
-            .or_else(github_cli_token);
-
-        Self::new(token)
+        match token_from_environment() {
+            Some(token) => {
+                debug!("Using GitHub token from {}", token.source);
+                Self::new(Some(token.value))
+            }
+            None => {
+                debug!("No GitHub token found, requests are unauthenticated");
+                Self::new(None)
+            }
+        }
     }
 
     fn get(
         &self,
         url: &str,
-        timeout: Option<Duration>,
+        kind: RequestKind,
     ) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
-        let agent: ureq::Agent = ureq::Agent::config_builder()
-            .timeout_global(timeout)
-            .build()
-            .into();
+        debug!("GET {}", url);
+        let request = self.configure(self.agent().get(url), kind);
 
-        self.token
-            .as_ref()
-            .map(|x| {
-                agent
-                    .get(url)
-                    .header("Authorization", &format!("token {}", x))
-            })
-            .unwrap_or_else(|| agent.get(url))
+        match self.authorization(url) {
+            Some((name, value)) => request.header(name, &value),
+            None => request,
+        }
+    }
+
+    /// Whether the GitHub token can be sent to `url`: the github.com hosts, or the host of the
+    /// GitHub Enterprise Server API
+    pub fn is_github_url(&self, url: &str) -> bool {
+        if self.api_url != GITHUB_API_URL {
+            return same_origin(url, &self.api_url);
+        }
+        url::Url::parse(url).is_ok_and(|x| {
+            x.scheme() == "https"
+                && x.host_str()
+                    .is_some_and(|host| GITHUB_HOSTS.contains(&host))
+        })
+    }
+
+    /// Header with the token (if any) of the requests to `url`
+    fn authorization(&self, url: &str) -> Option<(&'static str, String)> {
+        match (self.token.as_ref(), self.forge) {
+            (None, _) => None,
+            // Direct asset URLs (`--asset-url`) can point anywhere
+            (Some(_), Forge::GitHub) if !self.is_github_url(url) => None,
+            (Some(x), Forge::GitHub) => Some(("Authorization", format!("token {}", x))),
+            // Release links can point anywhere, the token is sent only to the API host
+            (Some(_), _) if !same_origin(url, &self.api_url) => None,
+            (Some(x), Forge::GitLab) => Some(("PRIVATE-TOKEN", x.clone())),
+            (Some(x), Forge::Gitea) => Some(("Authorization", format!("token {}", x))),
+            (Some(x), Forge::Bitbucket) => Some(("Authorization", format!("Bearer {}", x))),
+        }
     }
 
     // DOCS:
@@ -61,35 +269,913 @@ impl GithubClient {
         repository: &Repository,
         tag: Option<&Tag>,
     ) -> Result<Release, GithubError> {
-        let url = get_release_url(repository, tag);
-        let response = self
-            .get(&url, Some(Duration::from_secs(5)))
+        match self.forge {
+            Forge::GitLab => return self.gitlab_get_release(repository, tag),
+            Forge::Bitbucket => return self.bitbucket_release(repository, tag),
+            _ => {}
+        }
+        let url = get_release_url(&self.repository_url(repository), tag);
+        let release = self.get_json::<ReleaseResponse>(&url)?;
+        self.with_all_assets(repository, release)
+            .map(to_release(repository))
+    }
+
+    /// All the releases of the repository accepted by the filter, sorted by creation date (newest first)
+    // DOCS: https://docs.github.com/en/rest/releases/releases#list-releases
+    pub fn list_releases(
+        &self,
+        repository: &Repository,
+        filter: ReleaseFilter,
+    ) -> Result<Vec<Release>, GithubError> {
+        match self.forge {
+            Forge::GitLab => return self.gitlab_list_releases(repository, filter, None),
+            Forge::Bitbucket => return self.bitbucket_release(repository, None).map(|x| vec![x]),
+            _ => {}
+        }
+        let mut releases = vec![];
+        for page in 1.. {
+            let (url, per_page) = self.list_releases_url(repository, page);
+            let response = self.get_json::<Vec<ReleaseResponse>>(&url)?;
+            let is_last_page = response.len() < per_page;
+            debug!("Found {} releases in page {}", response.len(), page);
+            releases.extend(
+                response
+                    .into_iter()
+                    .filter(|x| is_accepted(filter, x))
+                    .map(to_release(repository)),
+            );
+            if is_last_page {
+                break;
+            }
+        }
+        Ok(releases)
+    }
+
+    /// Newest release accepted by the filter, since `/releases/latest` ignores pre-releases and drafts.
+    /// Draft releases are listed only with a token that has push access to the repository
+    // DOCS: https://docs.github.com/en/rest/releases/releases#list-releases
+    pub fn get_newest_release(
+        &self,
+        repository: &Repository,
+        filter: ReleaseFilter,
+    ) -> Result<Release, GithubError> {
+        match self.forge {
+            Forge::GitLab => {
+                return self
+                    .gitlab_list_releases(repository, filter, Some(1))?
+                    .into_iter()
+                    .next()
+                    .ok_or(GithubError::RepositoryOrReleaseNotFound);
+            }
+            Forge::Bitbucket => return self.bitbucket_release(repository, None),
+            _ => {}
+        }
+        let (url, _) = self.list_releases_url(repository, 1);
+        let release = self
+            .get_json::<Vec<ReleaseResponse>>(&url)?
+            .into_iter()
+            .find(|x| is_accepted(filter, x))
+            .ok_or(GithubError::RepositoryOrReleaseNotFound)?;
+        self.with_all_assets(repository, release)
+            .map(to_release(repository))
+    }
+
+    /// Releases from `list_releases` may miss some assets (see `with_all_assets`),
+    /// so the release is fetched again when it has many assets.
+    /// Draft releases can't be fetched by tag, so they are returned as they are
+    pub fn complete_release(
+        &self,
+        repository: &Repository,
+        release: Release,
+    ) -> Result<Release, GithubError> {
+        let assets = release
+            .assets
+            .iter()
+            .filter(|x| !x.is_source_code())
+            .count();
+        if self.forge != Forge::GitHub || assets < EMBEDDED_ASSETS_LIMIT {
+            return Ok(release);
+        }
+        match self.get_release(repository, Some(&release.tag)) {
+            Ok(complete) => Ok(complete),
+            Err(GithubError::RepositoryOrReleaseNotFound) => Ok(release),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Draft releases can't be fetched by tag, so they are searched in the releases list
+    pub fn find_release_by_tag(
+        &self,
+        repository: &Repository,
+        tag: &Tag,
+    ) -> Result<Release, GithubError> {
+        let filter = ReleaseFilter {
+            pre_release: true,
+            draft: true,
+        };
+        self.list_releases(repository, filter)?
+            .into_iter()
+            .find(|x| x.tag.0 == tag.0)
+            .ok_or(GithubError::RepositoryOrReleaseNotFound)
+    }
+
+    /// The assets embedded in the release are replaced with all the pages of its assets,
+    /// when they may be incomplete
+    // DOCS: https://docs.github.com/en/rest/releases/assets#list-release-assets
+    fn with_all_assets(
+        &self,
+        repository: &Repository,
+        mut release: ReleaseResponse,
+    ) -> Result<ReleaseResponse, GithubError> {
+        let id = release
+            .id
+            .filter(|_| self.forge == Forge::GitHub)
+            .filter(|_| release.assets.len() >= EMBEDDED_ASSETS_LIMIT);
+        let Some(id) = id else {
+            return Ok(release);
+        };
+        let mut assets = vec![];
+        for page in 1.. {
+            let url = format!(
+                "{repository}/releases/{id}/assets?per_page={per_page}&page={page}",
+                repository = self.repository_url(repository),
+                per_page = ASSETS_PER_PAGE,
+            );
+            let response = self.get_json::<Vec<AssetResponse>>(&url)?;
+            let is_last_page = response.len() < ASSETS_PER_PAGE;
+            assets.extend(response);
+            if is_last_page {
+                break;
+            }
+        }
+        debug!(
+            "Found {} assets in release {}",
+            assets.len(),
+            release.tag_name
+        );
+        release.assets = assets;
+        Ok(release)
+    }
+
+    // DOCS: https://docs.github.com/en/rest/releases/assets#get-a-release-asset
+    pub fn get_asset(&self, repository: &Repository, id: u64) -> Result<Asset, GithubError> {
+        if self.forge != Forge::GitHub {
+            return Err(GithubError::Unsupported(format!(
+                "Downloading an asset by id is not supported on {:?}",
+                self.forge
+            )));
+        }
+        let url = format!(
+            "{repository}/releases/assets/{id}",
+            repository = self.repository_url(repository),
+        );
+        self.get_json::<AssetResponse>(&url).map(Asset::from)
+    }
+
+    /// SPDX identifier of the repository license, if GitHub was able to detect it
+    // DOCS: https://docs.github.com/en/rest/licenses/licenses#get-the-license-for-a-repository
+    pub fn get_license(&self, repository: &Repository) -> Result<Option<String>, GithubError> {
+        if self.forge != Forge::GitHub {
+            return Ok(None);
+        }
+        let url = format!("{}/license", self.repository_url(repository));
+        match self.get_json::<RepositoryLicenseResponse>(&url) {
+            Ok(response) => Ok(license::from_spdx_id(response.license.spdx_id)),
+            Err(GithubError::RepositoryOrReleaseNotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // DOCS:
+    // - https://docs.gitlab.com/api/releases/#get-the-latest-release
+    // - https://docs.gitlab.com/api/releases/#get-a-release-by-a-tag-name
+    fn gitlab_get_release(
+        &self,
+        repository: &Repository,
+        tag: Option<&Tag>,
+    ) -> Result<Release, GithubError> {
+        let release = tag
+            .map(|x| urlencoding::encode(&x.0).into_owned())
+            .unwrap_or_else(|| String::from("permalink/latest"));
+        let url = format!(
+            "{}/releases/{}",
+            self.gitlab_project_url(repository),
+            release
+        );
+        self.get_json::<GitlabReleaseResponse>(&url)
+            .map(|x| Release::from_gitlab_response(x, repository))
+    }
+
+    /// Upcoming releases (with a future release date) are considered pre-releases.
+    /// GitLab doesn't have draft releases.
+    // DOCS: https://docs.gitlab.com/api/releases/#list-releases
+    fn gitlab_list_releases(
+        &self,
+        repository: &Repository,
+        filter: ReleaseFilter,
+        max_pages: Option<usize>,
+    ) -> Result<Vec<Release>, GithubError> {
+        let mut releases = vec![];
+        for page in 1..=max_pages.unwrap_or(usize::MAX) {
+            let url = format!(
+                "{project}/releases?per_page={per_page}&page={page}",
+                project = self.gitlab_project_url(repository),
+                per_page = RELEASES_PER_PAGE,
+            );
+            let response = self.get_json::<Vec<GitlabReleaseResponse>>(&url)?;
+            let is_last_page = response.len() < RELEASES_PER_PAGE;
+            debug!("Found {} releases in page {}", response.len(), page);
+            releases.extend(
+                response
+                    .into_iter()
+                    .filter(|x| filter.pre_release || !x.upcoming_release)
+                    .map(|x| Release::from_gitlab_response(x, repository)),
+            );
+            if is_last_page {
+                break;
+            }
+        }
+        Ok(releases)
+    }
+
+    fn gitlab_project_url(&self, repository: &Repository) -> String {
+        let project = format!("{}/{}", repository.owner, repository.repo);
+        format!(
+            "{api}/projects/{project}",
+            api = self.api_url,
+            project = urlencoding::encode(&project),
+        )
+    }
+
+    /// Bitbucket has no releases, they are made of the repository downloads (see `Release::from_bitbucket_downloads`)
+    // DOCS: https://developer.atlassian.com/cloud/bitbucket/rest/api-group-downloads/#api-repositories-workspace-repo-slug-downloads-get
+    fn bitbucket_release(
+        &self,
+        repository: &Repository,
+        tag: Option<&Tag>,
+    ) -> Result<Release, GithubError> {
+        let mut downloads = vec![];
+        let mut next = Some(format!(
+            "{api}/repositories/{workspace}/{repo}/downloads?pagelen={per_page}",
+            api = self.api_url,
+            workspace = &repository.owner,
+            repo = &repository.repo,
+            per_page = BITBUCKET_DOWNLOADS_PER_PAGE,
+        ));
+        while let Some(url) = next {
+            let response = self.get_json::<BitbucketDownloadsResponse>(&url)?;
+            debug!("Found {} downloads", response.values.len());
+            downloads.extend(response.values);
+            next = response.next;
+        }
+        let release = Release::from_bitbucket_downloads(downloads, tag);
+        if release.assets.is_empty() {
+            return Err(GithubError::RepositoryOrReleaseNotFound);
+        }
+        Ok(release)
+    }
+
+    // DOCS: https://docs.github.com/en/apps/oauth-apps/building-oauth-apps/authorizing-oauth-apps#step-1-app-requests-the-device-and-user-verification-codes-from-github
+    pub fn request_device_code(
+        &self,
+        client_id: &str,
+        scope: &str,
+    ) -> Result<DeviceCodeResponse, GithubError> {
+        let url = format!("{}/device/code", GITHUB_LOGIN_URL);
+        self.post_form(&url, &[("client_id", client_id), ("scope", scope)])
+    }
+
+    // DOCS: https://docs.github.com/en/apps/oauth-apps/building-oauth-apps/authorizing-oauth-apps#step-3-app-polls-github-to-check-if-the-user-authorized-the-device
+    pub fn request_device_access_token(
+        &self,
+        client_id: &str,
+        device_code: &str,
+    ) -> Result<AccessTokenResponse, GithubError> {
+        let url = format!("{}/oauth/access_token", GITHUB_LOGIN_URL);
+        self.post_form(
+            &url,
+            &[
+                ("client_id", client_id),
+                ("device_code", device_code),
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ],
+        )
+    }
+
+    /// Form requests are never authenticated
+    fn post_form<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        form: &[(&str, &str)],
+    ) -> Result<T, GithubError> {
+        debug!("POST {}", url);
+        self.retry.run(|| {
+            self.configure(self.agent().post(url), RequestKind::Metadata)
+                .header("Accept", "application/json")
+                .send_form(form.iter().copied())
+                .map_err(GithubError::from)?
+                .body_mut()
+                .read_json::<T>()
+                .map_err(GithubError::from)
+        })
+    }
+
+    /// Releases of many repositories with a single request. The GraphQL API requires a token.
+    /// Releases with more assets than a GraphQL page are fetched again with the REST API
+    // DOCS: https://docs.github.com/en/graphql/reference/objects#repository
+    pub fn graphql_releases(
+        &self,
+        queries: &[ReleaseQuery],
+    ) -> Result<Vec<GraphqlRelease>, GithubError> {
+        if self.forge != Forge::GitHub {
+            return Err(GithubError::Unsupported(
+                "GraphQL API is available only on GitHub".to_string(),
+            ));
+        }
+        let Some(token) = self.token.as_ref() else {
+            return Err(GithubError::Unsupported(
+                "GraphQL API requires a GitHub token".to_string(),
+            ));
+        };
+        let url = graphql::graphql_url(&self.api_url);
+        let request = GraphqlRequest {
+            query: graphql::releases_query(queries),
+        };
+        let response = self.retry.run(|| {
+            debug!("POST {}", url);
+            let response = self
+                .configure(self.agent().post(&url), RequestKind::Metadata)
+                .header("Authorization", &format!("bearer {}", token))
+                .config()
+                .http_status_as_error(false)
+                .build()
+                .send_json(&request)
+                .map_err(GithubError::from)?;
+            let status = response.status().as_u16();
+            if status >= 400 {
+                return Err(GithubError::from_status(status, response.headers()));
+            }
+            response
+                .into_body()
+                .read_json::<GraphqlResponse>()
+                .map_err(GithubError::from)
+        })?;
+        for error in &response.errors {
+            debug!("GraphQL error: {}", error.message);
+        }
+        let releases = graphql::releases_from(response, queries, |x| self.repository_url(x));
+        releases
+            .into_iter()
+            .zip(queries)
+            .map(|(result, query)| match result.release {
+                Some(release) if result.has_more_assets => {
+                    debug!(
+                        "Release {} has more assets than a GraphQL page",
+                        release.tag.0
+                    );
+                    Ok(GraphqlRelease {
+                        release: Some(self.get_release(&query.repository, Some(&release.tag))?),
+                        ..result
+                    })
+                }
+                _ => Ok(result),
+            })
+            .collect()
+    }
+
+    /// Url of a page of releases and the page size.
+    /// Gitea releases API is the same as GitHub, except for the page size parameter.
+    // DOCS: https://docs.gitea.com/api/1.22/#tag/repository/operation/repoListReleases
+    fn list_releases_url(&self, repository: &Repository, page: usize) -> (String, usize) {
+        let (parameter, per_page) = match self.forge {
+            Forge::Gitea => ("limit", GITEA_RELEASES_PER_PAGE),
+            _ => ("per_page", RELEASES_PER_PAGE),
+        };
+        let url = format!(
+            "{repository}/releases?{parameter}={per_page}&page={page}",
+            repository = self.repository_url(repository),
+        );
+        (url, per_page)
+    }
+
+    fn repository_url(&self, repository: &Repository) -> String {
+        format!(
+            "{api}/repos/{owner}/{repo}",
+            api = self.api_url,
+            owner = &repository.owner,
+            repo = &repository.repo,
+        )
+    }
+
+    fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, GithubError> {
+        self.retry.run(|| self.try_get_json(url))
+    }
+
+    fn try_get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, GithubError> {
+        let cached = self.cache.as_ref().and_then(|x| x.get(url));
+        if self.offline {
+            debug!("Using cached response of {} (offline)", url);
+            return match cached {
+                Some(entry) => parse_json(&entry.body),
+                None => Err(GithubError::NotCached(format!(
+                    "Release information ({})",
+                    url
+                ))),
+            };
+        }
+        let request = self.get(url, RequestKind::Metadata);
+        let request = if self.gzip_encoding {
+            request.header("Accept-Encoding", "gzip")
+        } else {
+            request
+        };
+        let request = match cached.as_ref() {
+            Some(entry) => request.header("If-None-Match", &entry.etag),
+            None => request,
+        };
+        let response = request
+            .config()
+            .http_status_as_error(false)
+            .build()
             .call()
             .map_err(GithubError::from)?;
-        let (_, mut body) = response.into_parts();
-        deserialize(&mut body).map(to_release(repository))
+        let status = response.status().as_u16();
+        if let (304, Some(entry)) = (status, cached) {
+            debug!("Using cached response of {}", url);
+            return parse_json(&entry.body);
+        }
+        if status >= 400 {
+            return Err(GithubError::from_status(status, response.headers()));
+        }
+        let (head, mut body) = response.into_parts();
+
+        let is_gzip = head
+            .headers
+            .get("Content-Encoding")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.eq_ignore_ascii_case("gzip"));
+        let etag = head.headers.get("ETag").and_then(|v| v.to_str().ok());
+        if let (Some(cache), Some(etag)) = (self.cache.as_ref(), etag) {
+            let content = if is_gzip {
+                let mut content = String::new();
+                flate2::read::GzDecoder::new(body.into_reader())
+                    .read_to_string(&mut content)
+                    .map_err(|e| GithubError::from(ureq::Error::Io(e)))?;
+                content
+            } else {
+                body.read_to_string().map_err(GithubError::from)?
+            };
+            let result = parse_json(&content);
+            if result.is_ok() {
+                cache.put(&CacheEntry {
+                    url: url.to_string(),
+                    etag: etag.to_string(),
+                    body: content,
+                });
+            }
+            return result;
+        }
+        if is_gzip {
+            let decoder = flate2::read::GzDecoder::new(body.into_reader());
+            serde_json::from_reader(decoder)
+                .map_err(|e| GithubError::JsonDeserialization(e.to_string()))
+        } else {
+            body.read_json::<T>().map_err(GithubError::from)
+        }
+    }
+
+    pub fn download_asset_stream(&self, asset: &Asset) -> Result<DownloadStream, GithubError> {
+        self.cached_download(asset, || self.fetch_asset_stream(asset))
+    }
+
+    /// Download the asset with concurrent ranged requests of `CHUNK_SIZE` bytes.
+    /// Small assets, or servers that don't support ranged requests, use a single request.
+    pub fn download_asset_chunked(
+        &self,
+        asset: &Asset,
+        connections: usize,
+    ) -> Result<DownloadStream, GithubError> {
+        self.cached_download(asset, || self.fetch_asset_chunked(asset, connections))
+    }
+
+    fn cached_download<F>(&self, asset: &Asset, fetch: F) -> Result<DownloadStream, GithubError>
+    where
+        F: FnOnce() -> Result<DownloadStream, GithubError>,
+    {
+        if let Some(stream) = self
+            .asset_cache
+            .as_ref()
+            .and_then(|x| x.open(&asset.download_url))
+        {
+            debug!("Using cached asset {}", asset.name);
+            return Ok(stream);
+        }
+        if self.offline {
+            return Err(GithubError::NotCached(format!("Asset {}", asset.name)));
+        }
+        let stream = fetch()?;
+        Ok(match self.asset_cache.as_ref() {
+            Some(cache) => cache.save_while_reading(&asset.download_url, stream),
+            None => stream,
+        })
     }
 
     // DOCS: https://docs.github.com/en/rest/releases/assets#get-a-release-asset
-    pub fn download_asset_stream(
+    fn fetch_asset_stream(&self, asset: &Asset) -> Result<DownloadStream, GithubError> {
+        let url = self.asset_url(asset);
+        let accept = asset_accept(url);
+        debug!("Downloading asset {} (Accept: {})", asset.name, accept);
+        let response = self.retry.run(|| {
+            self.get(url, RequestKind::Download)
+                .header("Accept", accept)
+                .call()
+                .map_err(GithubError::from)
+        })?;
+        let (head, body) = response.into_parts();
+        Ok(DownloadStream {
+            reader: Box::new(body.into_reader()),
+            content_length: content_length(&head.headers),
+        })
+    }
+
+    fn fetch_asset_chunked(
         &self,
         asset: &Asset,
-    ) -> Result<(impl Read + Send, Option<u64>), GithubError> {
+        connections: usize,
+    ) -> Result<DownloadStream, GithubError> {
+        if asset.size.is_some_and(|x| x <= CHUNK_SIZE) {
+            return self.fetch_asset_stream(asset);
+        }
+        let url = self.asset_url(asset);
+        let accept = asset_accept(url);
+        debug!(
+            "Downloading asset {} with {} connections",
+            asset.name, connections
+        );
+        let (head, body) = self
+            .retry
+            .run(|| self.get_range(url, accept, 0, CHUNK_SIZE - 1))?
+            .into_parts();
+        let total = (head.status == 206)
+            .then(|| head.headers.get("Content-Range"))
+            .flatten()
+            .and_then(|x| x.to_str().ok())
+            .and_then(parse_content_range);
+        let Some(total) = total else {
+            debug!("Ranged requests are not supported, downloading the whole asset");
+            return Ok(DownloadStream {
+                reader: Box::new(body.into_reader()),
+                content_length: content_length(&head.headers),
+            });
+        };
+
+        let mut first_chunk = vec![];
+        body.into_reader()
+            .read_to_end(&mut first_chunk)
+            .map_err(|e| GithubError::from(ureq::Error::Io(e)))?;
+        let client = self.clone();
+        let url = url.to_string();
+        let fetch = Arc::new(move |start, end| client.fetch_range(&url, accept, start, end));
+        Ok(DownloadStream {
+            reader: Box::new(ChunkedReader::start(
+                chunk_ranges(total, CHUNK_SIZE),
+                first_chunk,
+                connections,
+                fetch,
+            )),
+            content_length: Some(total),
+        })
+    }
+
+    /// Private repository assets may not be downloadable from the browser url with a token,
+    /// so authenticated GitHub downloads use the API url of the asset (when known)
+    fn asset_url<'a>(&self, asset: &'a Asset) -> &'a str {
+        match (self.token.as_ref(), self.forge, asset.api_url.as_deref()) {
+            (Some(_), Forge::GitHub, Some(api_url)) => api_url,
+            _ => &asset.download_url,
+        }
+    }
+
+    /// Not retried, callers retry the whole range fetch
+    fn get_range(
+        &self,
+        url: &str,
+        accept: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<ureq::http::Response<ureq::Body>, GithubError> {
+        self.get(url, RequestKind::Download)
+            .header("Accept", accept)
+            .header("Range", &format!("bytes={}-{}", start, end))
+            .call()
+            .map_err(GithubError::from)
+    }
+
+    fn fetch_range(
+        &self,
+        url: &str,
+        accept: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<u8>, String> {
+        self.retry
+            .run(|| self.try_fetch_range(url, accept, start, end))
+            .map_err(|e| e.to_string())
+    }
+
+    fn try_fetch_range(
+        &self,
+        url: &str,
+        accept: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<u8>, GithubError> {
+        let io_error =
+            |kind, message| GithubError::from(ureq::Error::Io(std::io::Error::new(kind, message)));
+        let response = self.get_range(url, accept, start, end)?;
+        if response.status() != 206 {
+            return Err(io_error(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Unexpected status {} for range {}-{}",
+                    response.status(),
+                    start,
+                    end
+                ),
+            ));
+        }
+        let mut chunk = vec![];
+        response
+            .into_body()
+            .into_reader()
+            .read_to_end(&mut chunk)
+            .map_err(|e| GithubError::from(ureq::Error::Io(e)))?;
+        if chunk.len() as u64 != end - start + 1 {
+            return Err(io_error(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "Incomplete range {}-{}, received {} bytes",
+                    start,
+                    end,
+                    chunk.len()
+                ),
+            ));
+        }
+        Ok(chunk)
+    }
+
+    // Artifacts pushed to GitHub Container Registry (e.g. with `oras push`) are stored as
+    // image layers, where the file name is saved in the layer title annotation.
+    // DOCS: https://github.com/opencontainers/distribution-spec/blob/main/spec.md#pulling-manifests
+    pub fn download_ghcr_artifact(
+        &self,
+        repository: &Repository,
+        tag: &Tag,
+        artifact_name: &str,
+    ) -> Result<DownloadStream, GithubError> {
+        if self.offline {
+            return Err(GithubError::NotCached(format!(
+                "Package artifact {}",
+                artifact_name
+            )));
+        }
+        let package = ghcr_package_name(repository);
+        let registry_token = self.ghcr_token(&package)?;
+        let layer = self.ghcr_find_layer(&package, tag, artifact_name, &registry_token)?;
+
+        let url = format!("{}/v2/{}/blobs/{}", GHCR, package, layer.digest);
+        debug!("GET {}", url);
+        let response = self.retry.run(|| {
+            self.configure(self.agent().get(&url), RequestKind::Download)
+                .header("Authorization", &format!("Bearer {}", registry_token))
+                .call()
+                .map_err(GithubError::from)
+        })?;
+        let (_, body) = response.into_parts();
+        Ok(DownloadStream {
+            reader: Box::new(body.into_reader()),
+            content_length: Some(layer.size),
+        })
+    }
+
+    fn ghcr_token(&self, package: &str) -> Result<String, GithubError> {
+        let url = format!(
+            "{}/token?service=ghcr.io&scope=repository:{}:pull",
+            GHCR, package
+        );
+        debug!("GET {}", url);
+        let request = self.configure(self.agent().get(&url), RequestKind::Metadata);
+        let request = match self.token.as_ref() {
+            Some(token) => {
+                let credentials =
+                    base64::engine::general_purpose::STANDARD.encode(format!("dra:{}", token));
+                request.header("Authorization", &format!("Basic {}", credentials))
+            }
+            None => request,
+        };
+        let response = request.call().map_err(GithubError::from)?;
+        let (_, mut body) = response.into_parts();
+        body.read_json::<RegistryTokenResponse>()
+            .map(|x| x.token)
+            .map_err(GithubError::from)
+    }
+
+    fn ghcr_find_layer(
+        &self,
+        package: &str,
+        tag: &Tag,
+        artifact_name: &str,
+        registry_token: &str,
+    ) -> Result<LayerResponse, GithubError> {
+        let url = format!("{}/v2/{}/manifests/{}", GHCR, package, tag.0);
+        debug!("GET {}", url);
         let response = self
-            .get(&asset.download_url, None)
-            .header("Accept", "application/vnd.github.raw")
+            .configure(self.agent().get(&url), RequestKind::Metadata)
+            .header("Authorization", &format!("Bearer {}", registry_token))
+            .header("Accept", OCI_MANIFEST_MEDIA_TYPE)
             .call()
             .map_err(GithubError::from)?;
-        let (head, body) = response.into_parts();
-        let content_length = head
-            .headers
-            .get("Content-Length")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse::<u64>().ok());
-        Ok((body.into_reader(), content_length))
+        let (_, mut body) = response.into_parts();
+        let manifest = body
+            .read_json::<ManifestResponse>()
+            .map_err(GithubError::from)?;
+
+        manifest
+            .layers
+            .into_iter()
+            .find(|x| x.title() == Some(artifact_name))
+            .ok_or_else(|| GithubError::PackageArtifactNotFound(artifact_name.to_string()))
+    }
+
+    /// Cloning the agent shares its connection pool
+    fn agent(&self) -> ureq::Agent {
+        self.agent.get_or_init(|| self.build_agent()).clone()
+    }
+
+    fn configure<B>(
+        &self,
+        request: ureq::RequestBuilder<B>,
+        kind: RequestKind,
+    ) -> ureq::RequestBuilder<B> {
+        match kind {
+            RequestKind::Metadata => request
+                .config()
+                .timeout_global(Some(self.timeout.unwrap_or(DEFAULT_METADATA_TIMEOUT)))
+                .build(),
+            RequestKind::Download => request,
+        }
+    }
+
+    fn build_agent(&self) -> ureq::Agent {
+        debug!("Creating HTTP agent");
+        let config = ureq::Agent::config_builder()
+            .timeout_connect(self.timeout)
+            .timeout_recv_response(self.timeout);
+        let config = match self.proxy.as_ref() {
+            Some(proxy) => config.proxy(Some(proxy.clone())),
+            None => config,
+        };
+        let config = match self.tls.as_ref() {
+            Some(tls) => config.tls_config(tls.clone()),
+            None => config,
+        };
+        config.build().into()
     }
 }
 
+/// Downloads can take as long as needed once the response is received,
+/// metadata requests must complete within the timeout
+#[derive(Clone, Copy)]
+enum RequestKind {
+    Metadata,
+    Download,
+}
+
+const GHCR: &str = "https://ghcr.io";
+const OCI_MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+
+/// API asset urls return the asset metadata unless the binary content is requested
+fn asset_accept(url: &str) -> &'static str {
+    if is_api_asset_url(url) {
+        "application/octet-stream"
+    } else {
+        "application/vnd.github.raw"
+    }
+}
+
+fn content_length(headers: &ureq::http::HeaderMap) -> Option<u64> {
+    headers
+        .get("Content-Length")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+}
+
+/// GHCR package names are always lowercase
+fn ghcr_package_name(repository: &Repository) -> String {
+    format!("{}/{}", repository.owner, repository.repo).to_lowercase()
+}
+
+/// `api_url` (e.g. `--github-api-url`) takes precedence over `DRA_GITHUB_API`
+/// and the GitHub Enterprise Server host of the repository
+pub fn resolve_api_url(api_url: Option<&str>, repository: &Repository) -> String {
+    let from_env = env_var::string(DRA_GITHUB_API);
+    api_url_from(api_url.or(from_env.as_deref()), repository)
+}
+
+fn api_url_from(api_url: Option<&str>, repository: &Repository) -> String {
+    match (api_url, repository.host.as_ref(), repository.forge()) {
+        (Some(api_url), _, _) => api_url.to_string(),
+        (None, Some(host), Forge::GitHub) => format!("https://{}/api/v3", host),
+        (None, Some(host), Forge::GitLab) => format!("https://{}/api/v4", host),
+        (None, Some(host), Forge::Gitea) => format!("https://{}/api/v1", host),
+        (None, Some(_), Forge::Bitbucket) => BITBUCKET_API_URL.to_string(),
+        (None, None, _) => GITHUB_API_URL.to_string(),
+    }
+}
+
+fn same_origin(url: &str, other: &str) -> bool {
+    match (url::Url::parse(url), url::Url::parse(other)) {
+        (Ok(url), Ok(other)) => url.origin() == other.origin(),
+        _ => false,
+    }
+}
+
+pub struct EnvironmentToken {
+    pub source: &'static str,
+    pub value: String,
+}
+
+pub fn is_authentication_disabled() -> bool {
+    env_var::boolean(DRA_DISABLE_GITHUB_AUTHENTICATION)
+}
+
+pub fn token_from_environment() -> Option<EnvironmentToken> {
+    let from_env_var = |name: &'static str| {
+        env_var::string(name).map(|value| EnvironmentToken {
+            source: name,
+            value,
+        })
+    };
+
+    from_env_var(DRA_GITHUB_TOKEN)
+        .or_else(|| from_env_var(GITHUB_TOKEN))
+        .or_else(|| from_env_var(GH_TOKEN))
+        .or_else(|| {
+            token_store::load().map(|token| EnvironmentToken {
+                source: match token.storage {
+                    TokenStorage::Keyring => "dra auth login (system keyring)",
+                    TokenStorage::File(_) => "dra auth login (token file)",
+                },
+                value: token.value,
+            })
+        })
+        .or_else(|| {
+            github_cli_token().map(|value| EnvironmentToken {
+                source: "gh auth token",
+                value,
+            })
+        })
+        .or_else(|| {
+            gh_config::token().map(|value| EnvironmentToken {
+                source: "gh hosts.yml",
+                value,
+            })
+        })
+}
+
+/// The github.com token is never sent to another host: a GitHub Enterprise Server uses only a token
+/// scoped to it, unless its API URL is explicit (`--github-api-url` or `DRA_GITHUB_API`).
+/// Hosts that are not a known forge are GitHub Enterprise Servers, so they could be any server.
+fn github_token_for<G, E>(
+    host: Option<&str>,
+    is_explicit_api_url: bool,
+    github_token: G,
+    enterprise_token: E,
+) -> Option<String>
+where
+    G: FnOnce() -> Option<String>,
+    E: FnOnce(&str) -> Option<String>,
+{
+    match host {
+        None => github_token(),
+        Some(host) => enterprise_token(host).or_else(|| {
+            if is_explicit_api_url {
+                github_token()
+            } else {
+                None
+            }
+        }),
+    }
+}
+
+/// Same variables of GitHub cli, then the token of the host in its `hosts.yml`
+// DOCS: https://cli.github.com/manual/gh_help_environment
+fn enterprise_token(host: &str) -> Option<String> {
+    env_var::string(GH_ENTERPRISE_TOKEN)
+        .or_else(|| env_var::string(GITHUB_ENTERPRISE_TOKEN))
+        .or_else(|| gh_config::token_for(host))
+}
+
 fn github_cli_token() -> Option<String> {
     Command::new("gh")
         .args(["auth", "token"])
@@ -105,23 +1191,175 @@ fn github_cli_token() -> Option<String> {
         .map(|x| x.trim().to_string())
 }
 
-fn get_release_url(repository: &Repository, tag: Option<&Tag>) -> String {
+fn get_release_url(repository_url: &str, tag: Option<&Tag>) -> String {
     format!(
-        "https://api.github.com/repos/{owner}/{repo}/releases/{release}",
-        owner = &repository.owner,
-        repo = &repository.repo,
+        "{repository_url}/releases/{release}",
         release = tag
             .map(|t| format!("tags/{}", t.0))
             .unwrap_or_else(|| String::from("latest"))
     )
 }
 
-fn deserialize(response: &mut ureq::Body) -> Result<ReleaseResponse, GithubError> {
-    response
-        .read_json::<ReleaseResponse>()
-        .map_err(GithubError::from)
+fn parse_json<T: DeserializeOwned>(content: &str) -> Result<T, GithubError> {
+    serde_json::from_str(content).map_err(|e| GithubError::JsonDeserialization(e.to_string()))
+}
+
+fn is_accepted(filter: ReleaseFilter, release: &ReleaseResponse) -> bool {
+    (filter.draft || !release.draft) && (filter.pre_release || !release.prerelease)
 }
 
 fn to_release(repository: &Repository) -> impl Fn(ReleaseResponse) -> Release + '_ {
     |response| Release::from_response(response, repository)
 }
+
+#[cfg(test)]
+mod api_url_from {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Some("https://ghe.corp/api/v3"), "devmatteini/dra", "https://ghe.corp/api/v3"; "explicit")]
+    #[test_case(Some("https://ghe.corp/api/v3"), "github.example.com/devmatteini/dra", "https://ghe.corp/api/v3"; "explicit over host")]
+    #[test_case(None, "github.example.com/devmatteini/dra", "https://github.example.com/api/v3"; "repository host")]
+    #[test_case(None, "devmatteini/dra", "https://api.github.com"; "github")]
+    #[test_case(None, "gitlab.com/group/project", "https://gitlab.com/api/v4"; "gitlab")]
+    #[test_case(None, "codeberg.org/owner/repo", "https://codeberg.org/api/v1"; "gitea")]
+    #[test_case(None, "bitbucket.org/team/repo", "https://api.bitbucket.org/2.0"; "bitbucket")]
+    fn resolved(api_url: Option<&str>, repository: &str, expected: &str) {
+        let repository = Repository::try_parse(repository).unwrap();
+
+        assert_eq!(expected, api_url_from(api_url, &repository));
+    }
+}
+
+#[cfg(test)]
+mod github_token_for {
+    use super::*;
+    use test_case::test_case;
+
+    const GITHUB: &str = "gho_github";
+    const ENTERPRISE: &str = "gho_enterprise";
+
+    #[test_case(None, false, None, Some(GITHUB); "github.com")]
+    #[test_case(Some("evil.example"), false, None, None; "unknown host")]
+    #[test_case(Some("github.example.com"), false, Some(ENTERPRISE), Some(ENTERPRISE); "enterprise token")]
+    #[test_case(Some("github.example.com"), true, None, Some(GITHUB); "explicit api url")]
+    #[test_case(Some("github.example.com"), true, Some(ENTERPRISE), Some(ENTERPRISE); "enterprise token with explicit api url")]
+    fn resolved(
+        host: Option<&str>,
+        is_explicit_api_url: bool,
+        enterprise_token: Option<&str>,
+        expected: Option<&str>,
+    ) {
+        let result = github_token_for(
+            host,
+            is_explicit_api_url,
+            || Some(GITHUB.to_string()),
+            |_| enterprise_token.map(String::from),
+        );
+
+        assert_eq!(expected.map(String::from), result);
+    }
+
+    #[test]
+    fn no_authorization_for_unknown_host() {
+        let repository = Repository::try_parse("evil.example/owner/repo").unwrap();
+        let token = github_token_for(
+            repository.host.as_deref(),
+            false,
+            || Some(GITHUB.to_string()),
+            |_| None,
+        );
+        let client = GithubClient::new(token)
+            .with_forge(repository.forge())
+            .with_api_url(api_url_from(None, &repository));
+
+        let result = client.authorization("https://evil.example/api/v3/repos/owner/repo");
+
+        assert_eq!(None, result);
+    }
+}
+
+#[cfg(test)]
+mod authorization {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("https://github.com/owner/repo/releases/download/v1/tool.tar.gz"; "browser download")]
+    #[test_case("https://api.github.com/repos/owner/repo/releases/assets/1"; "api")]
+    #[test_case("https://objects.githubusercontent.com/github-production-release-asset/1"; "storage")]
+    fn github_host(url: &str) {
+        let client = GithubClient::new(Some("ghp_token".into()));
+
+        let result = client.authorization(url);
+
+        assert_eq!(
+            Some(("Authorization", "token ghp_token".to_string())),
+            result
+        );
+    }
+
+    #[test_case("https://attacker.example/tool.tar.gz"; "other host")]
+    #[test_case("https://github.com.attacker.example/tool.tar.gz"; "github prefix")]
+    #[test_case("http://github.com/owner/repo/releases/download/v1/tool.tar.gz"; "plain http")]
+    fn foreign_host(url: &str) {
+        let client = GithubClient::new(Some("ghp_token".into()));
+
+        let result = client.authorization(url);
+
+        assert_eq!(None, result);
+    }
+
+    #[test]
+    fn github_enterprise_server_host() {
+        let client = GithubClient::new(Some("ghe_token".into()))
+            .with_api_url("https://github.example.com/api/v3".into());
+
+        assert!(
+            client
+                .authorization(
+                    "https://github.example.com/owner/repo/releases/download/v1/tool.tar.gz"
+                )
+                .is_some()
+        );
+        assert_eq!(None, client.authorization("https://github.com/owner/repo"));
+    }
+}
+
+#[cfg(test)]
+mod same_origin {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("https://gitlab.com/group/project/-/releases/v1.0/downloads/dra.tar.gz", true; "same host")]
+    #[test_case("https://example.com/dra.tar.gz", false; "other host")]
+    #[test_case("http://gitlab.com/dra.tar.gz", false; "other scheme")]
+    fn gitlab_api(url: &str, expected: bool) {
+        assert_eq!(expected, same_origin(url, "https://gitlab.com/api/v4"));
+    }
+}
+
+#[cfg(test)]
+mod agent {
+    use super::*;
+
+    #[test]
+    fn clones_share_the_agent() {
+        let client = GithubClient::new(None);
+        client.agent();
+
+        let clone = client.without_token();
+
+        assert!(Arc::ptr_eq(&client.agent, &clone.agent));
+        assert!(clone.agent.get().is_some());
+    }
+
+    #[test]
+    fn connection_options_build_a_new_agent() {
+        let client = GithubClient::new(None);
+        client.agent();
+
+        let client = client.with_timeout(Some(Duration::from_secs(1)));
+
+        assert!(client.agent.get().is_none());
+    }
+}
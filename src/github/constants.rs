@@ -1,4 +1,13 @@
 pub const DRA_GITHUB_TOKEN: &str = "DRA_GITHUB_TOKEN";
 pub const GITHUB_TOKEN: &str = "GITHUB_TOKEN";
 pub const GH_TOKEN: &str = "GH_TOKEN";
+pub const GH_ENTERPRISE_TOKEN: &str = "GH_ENTERPRISE_TOKEN";
+pub const GITHUB_ENTERPRISE_TOKEN: &str = "GITHUB_ENTERPRISE_TOKEN";
+pub const DRA_GITHUB_API: &str = "DRA_GITHUB_API";
+pub const DRA_GITLAB_TOKEN: &str = "DRA_GITLAB_TOKEN";
+pub const GITLAB_TOKEN: &str = "GITLAB_TOKEN";
+pub const DRA_GITEA_TOKEN: &str = "DRA_GITEA_TOKEN";
+pub const GITEA_TOKEN: &str = "GITEA_TOKEN";
+pub const DRA_BITBUCKET_TOKEN: &str = "DRA_BITBUCKET_TOKEN";
+pub const BITBUCKET_TOKEN: &str = "BITBUCKET_TOKEN";
 pub const DRA_DISABLE_GITHUB_AUTHENTICATION: &str = "DRA_DISABLE_GITHUB_AUTHENTICATION";
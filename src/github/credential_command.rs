@@ -0,0 +1,107 @@
+use std::fmt::Formatter;
+use std::process::Command;
+
+/// Run an external credential helper and use its standard output as the GitHub token
+pub struct CredentialCommand;
+
+#[derive(Debug, PartialEq)]
+pub enum CredentialError {
+    Execution(String),
+    Failed(String),
+    EmptyOutput,
+    InvalidOutput,
+}
+
+impl CredentialCommand {
+    pub fn run(cmd: &str) -> Result<String, CredentialError> {
+        let output = shell(cmd)
+            .output()
+            .map_err(|e| CredentialError::Execution(e.to_string()))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(CredentialError::Failed(format!(
+                "status: {}{}",
+                output
+                    .status
+                    .code()
+                    .map(|x| x.to_string())
+                    .unwrap_or_else(|| "NA".into()),
+                if stderr.is_empty() {
+                    stderr
+                } else {
+                    format!(", {}", stderr)
+                }
+            )));
+        }
+
+        let token = String::from_utf8(output.stdout)
+            .map_err(|_| CredentialError::InvalidOutput)?
+            .trim()
+            .to_string();
+        if token.is_empty() {
+            return Err(CredentialError::EmptyOutput);
+        }
+        Ok(token)
+    }
+}
+
+#[cfg(target_family = "unix")]
+fn shell(cmd: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(cmd);
+    command
+}
+
+#[cfg(target_os = "windows")]
+fn shell(cmd: &str) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(cmd);
+    command
+}
+
+impl std::fmt::Display for CredentialError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CredentialError::Execution(e) => {
+                write!(f, "Error executing GitHub token command: {}", e)
+            }
+            CredentialError::Failed(e) => write!(f, "GitHub token command failed ({})", e),
+            CredentialError::EmptyOutput => f.write_str("GitHub token command returned no token"),
+            CredentialError::InvalidOutput => {
+                f.write_str("GitHub token command returned an invalid UTF-8 output")
+            }
+        }
+    }
+}
+
+#[cfg(all(test, target_family = "unix"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_from_stdout() {
+        let result = CredentialCommand::run("echo '  ghp_secret  '");
+
+        assert_eq!(Ok("ghp_secret".to_string()), result);
+    }
+
+    #[test]
+    fn command_failed() {
+        let result = CredentialCommand::run("echo 'no credentials' >&2; exit 3");
+
+        assert_eq!(
+            Err(CredentialError::Failed(
+                "status: 3, no credentials".to_string()
+            )),
+            result
+        );
+    }
+
+    #[test]
+    fn empty_output() {
+        let result = CredentialCommand::run("true");
+
+        assert_eq!(Err(CredentialError::EmptyOutput), result);
+    }
+}
@@ -0,0 +1,92 @@
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Seconds added to the polling interval when GitHub responds with `slow_down`
+const SLOW_DOWN_INCREMENT: u64 = 5;
+
+// DOCS: https://docs.github.com/en/apps/oauth-apps/building-oauth-apps/authorizing-oauth-apps#device-flow
+#[derive(Deserialize, Debug)]
+pub struct DeviceCodeResponse {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    /// Seconds before the device code expires
+    pub expires_in: u64,
+    /// Minimum seconds between access token requests
+    pub interval: u64,
+}
+
+/// GitHub responds with `200 OK` while the authorization is pending, with the reason in `error`
+#[derive(Deserialize, Debug)]
+pub struct AccessTokenResponse {
+    pub access_token: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+    /// New polling interval, sent with `slow_down`
+    pub interval: Option<u64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeviceFlowStatus {
+    Authorized(String),
+    Pending,
+    /// Poll less often, using the new interval
+    SlowDown(Duration),
+    Expired,
+    Denied,
+    Failed(String),
+}
+
+impl AccessTokenResponse {
+    pub fn status(self, interval: Duration) -> DeviceFlowStatus {
+        if let Some(token) = self.access_token {
+            return DeviceFlowStatus::Authorized(token);
+        }
+        match self.error.as_deref() {
+            Some("authorization_pending") => DeviceFlowStatus::Pending,
+            Some("slow_down") => DeviceFlowStatus::SlowDown(
+                self.interval
+                    .map(Duration::from_secs)
+                    .unwrap_or(interval + Duration::from_secs(SLOW_DOWN_INCREMENT)),
+            ),
+            Some("expired_token") => DeviceFlowStatus::Expired,
+            Some("access_denied") => DeviceFlowStatus::Denied,
+            error => DeviceFlowStatus::Failed(
+                self.error_description
+                    .or(error.map(String::from))
+                    .unwrap_or_else(|| "missing access token".to_string()),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod status {
+    use super::*;
+    use test_case::test_case;
+
+    const INTERVAL: Duration = Duration::from_secs(5);
+
+    #[test_case(Some("gho_123"), None, None, DeviceFlowStatus::Authorized("gho_123".into()); "authorized")]
+    #[test_case(None, Some("authorization_pending"), None, DeviceFlowStatus::Pending; "pending")]
+    #[test_case(None, Some("slow_down"), Some(15), DeviceFlowStatus::SlowDown(Duration::from_secs(15)); "slow down")]
+    #[test_case(None, Some("slow_down"), None, DeviceFlowStatus::SlowDown(Duration::from_secs(10)); "slow down without interval")]
+    #[test_case(None, Some("expired_token"), None, DeviceFlowStatus::Expired; "expired")]
+    #[test_case(None, Some("access_denied"), None, DeviceFlowStatus::Denied; "denied")]
+    #[test_case(None, Some("incorrect_client_credentials"), None, DeviceFlowStatus::Failed("incorrect_client_credentials".into()); "failed")]
+    fn from_response(
+        access_token: Option<&str>,
+        error: Option<&str>,
+        interval: Option<u64>,
+        expected: DeviceFlowStatus,
+    ) {
+        let response = AccessTokenResponse {
+            access_token: access_token.map(String::from),
+            error: error.map(String::from),
+            error_description: None,
+            interval,
+        };
+
+        assert_eq!(expected, response.status(INTERVAL));
+    }
+}
@@ -1,25 +1,97 @@
 use crate::github::constants::{DRA_GITHUB_TOKEN, GH_TOKEN, GITHUB_TOKEN};
 use std::fmt::Formatter;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Debug)]
 pub enum GithubError {
     Http(Box<ureq::Error>),
     JsonDeserialization(String),
     RepositoryOrReleaseNotFound,
-    RateLimitExceeded,
+    /// With the time the rate limit resets, when the response includes it
+    RateLimitExceeded(Option<SystemTime>),
     Unauthorized,
+    PackageArtifactNotFound(String),
+    /// Operations not supported by the forge of the repository
+    Unsupported(String),
+    /// Release information or asset missing from the cache, in offline mode
+    NotCached(String),
 }
 
 impl GithubError {
     pub fn from(error: ureq::Error) -> Self {
         match error {
             ureq::Error::StatusCode(401) => Self::Unauthorized,
-            ureq::Error::StatusCode(403) => Self::RateLimitExceeded,
+            ureq::Error::StatusCode(403) => Self::RateLimitExceeded(None),
             ureq::Error::StatusCode(404) => Self::RepositoryOrReleaseNotFound,
             ureq::Error::Json(error) => Self::JsonDeserialization(error.to_string()),
             other => Self::Http(Box::new(other)),
         }
     }
+
+    /// Error of a response whose status is not turned into an error by ureq, so that the
+    /// rate limit headers are available (`403` or `429` responses)
+    pub fn from_status(status: u16, headers: &ureq::http::HeaderMap) -> Self {
+        match status {
+            403 | 429 => Self::RateLimitExceeded(rate_limit_reset(headers, SystemTime::now())),
+            status => Self::from(ureq::Error::StatusCode(status)),
+        }
+    }
+
+    /// Server errors, timeouts and dropped connections, that may succeed if the request is retried
+    pub fn is_transient(&self) -> bool {
+        match self {
+            GithubError::Http(error) => match error.as_ref() {
+                ureq::Error::StatusCode(status) => (500..=599).contains(status),
+                ureq::Error::Timeout(_) | ureq::Error::ConnectionFailed => true,
+                ureq::Error::Io(error) => is_transient_io_error(error.kind()),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// `Retry-After` is sent for secondary rate limits, while `X-RateLimit-Reset` (seconds since epoch)
+/// for primary rate limits, when `X-RateLimit-Remaining` is 0
+// DOCS: https://docs.github.com/en/rest/using-the-rest-api/best-practices-for-using-the-rest-api#handle-rate-limit-errors-appropriately
+fn rate_limit_reset(headers: &ureq::http::HeaderMap, now: SystemTime) -> Option<SystemTime> {
+    let header = |name: &str| headers.get(name).and_then(|x| x.to_str().ok());
+    let seconds = |x: &str| x.trim().parse::<u64>().ok().map(Duration::from_secs);
+
+    if let Some(retry_after) = header("retry-after").and_then(seconds) {
+        return Some(now + retry_after);
+    }
+    match header("x-ratelimit-remaining") {
+        Some(remaining) if remaining.trim() == "0" => header("x-ratelimit-reset")
+            .and_then(seconds)
+            .map(|x| UNIX_EPOCH + x),
+        _ => None,
+    }
+}
+
+fn is_transient_io_error(kind: std::io::ErrorKind) -> bool {
+    use std::io::ErrorKind;
+    matches!(
+        kind,
+        ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::BrokenPipe
+            | ErrorKind::TimedOut
+            | ErrorKind::UnexpectedEof
+    )
+}
+
+/// UTC time of the reset and the time left (e.g. `2024-05-31T10:00:00Z (in 12m 30s)`)
+fn format_reset(reset: SystemTime) -> String {
+    let left = reset
+        .duration_since(SystemTime::now())
+        .map(|x| Duration::from_secs(x.as_secs()))
+        .unwrap_or_default();
+    format!(
+        "{} (in {})",
+        humantime::format_rfc3339_seconds(reset),
+        humantime::format_duration(left)
+    )
 }
 
 fn authentication_tokens() -> String {
@@ -36,17 +108,30 @@ impl std::fmt::Display for GithubError {
             GithubError::RepositoryOrReleaseNotFound => {
                 f.write_str("Repository or release not found")
             }
-            GithubError::RateLimitExceeded => {
+            GithubError::RateLimitExceeded(reset) => {
+                let reset = reset
+                    .map(|x| format!(" It resets at {}.", format_reset(x)))
+                    .unwrap_or_default();
                 let message = format!(
-                    "GitHub API rate limit exceeded.
-Export one of {} environment variable to avoid this error.
+                    "GitHub API rate limit exceeded.{}
+Export one of {} environment variable to avoid this error, or use --wait-for-rate-limit.
 Otherwise, login with GitHub cli 'gh auth login' to use the authentication token automatically.
 
 More information can be found at https://github.com/devmatteini/dra#usage",
+                    reset,
                     authentication_tokens()
                 );
                 f.write_str(&message)
             }
+            GithubError::Unsupported(message) => f.write_str(message),
+            GithubError::NotCached(what) => write!(
+                f,
+                "{} not found in the cache. Run the command without --offline to cache it",
+                what
+            ),
+            GithubError::PackageArtifactNotFound(name) => {
+                f.write_str(&format!("Artifact {} not found in package", name))
+            }
             GithubError::Unauthorized => {
                 let message = format!(
                     "Invalid GitHub credentials.
@@ -59,3 +144,29 @@ Otherwise, login with GitHub cli 'gh auth login' to use the authentication token
         }
     }
 }
+
+#[cfg(test)]
+mod rate_limit_reset {
+    use super::*;
+    use test_case::test_case;
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test_case(&[("retry-after", "60")], Some(NOW + 60); "retry after")]
+    #[test_case(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", "1700000600")], Some(1_700_000_600); "primary rate limit")]
+    #[test_case(&[("x-ratelimit-remaining", "10"), ("x-ratelimit-reset", "1700000600")], None; "not exhausted")]
+    #[test_case(&[], None; "no headers")]
+    fn reset(headers: &[(&'static str, &str)], expected: Option<u64>) {
+        let mut map = ureq::http::HeaderMap::new();
+        for (name, value) in headers {
+            map.insert(*name, value.parse().unwrap());
+        }
+
+        let result = rate_limit_reset(&map, UNIX_EPOCH + Duration::from_secs(NOW));
+
+        assert_eq!(
+            expected.map(|x| UNIX_EPOCH + Duration::from_secs(x)),
+            result
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use crate::env_var;
+use std::path::PathBuf;
+
+const GITHUB_HOST: &str = "github.com";
+
+/// Token stored by GitHub cli in its `hosts.yml` file, used when `gh` isn't installed (or fails).
+/// GitHub cli stores tokens in plain text only when the system keyring isn't available
+pub fn token() -> Option<String> {
+    token_for(GITHUB_HOST)
+}
+
+/// Token of a GitHub Enterprise Server host (e.g. `github.example.com`)
+pub fn token_for(host: &str) -> Option<String> {
+    let path = config_dir()?.join("hosts.yml");
+    let content = std::fs::read_to_string(path).ok()?;
+    parse_hosts_file(&content, host)
+}
+
+/// Same lookup as GitHub cli: `GH_CONFIG_DIR`, then `XDG_CONFIG_HOME/gh` and `~/.config/gh`
+// DOCS: https://cli.github.com/manual/gh_help_environment
+#[cfg(not(target_os = "windows"))]
+fn config_dir() -> Option<PathBuf> {
+    env_var::string("GH_CONFIG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| env_var::string("XDG_CONFIG_HOME").map(|x| PathBuf::from(x).join("gh")))
+        .or_else(|| env_var::string("HOME").map(|x| PathBuf::from(x).join(".config").join("gh")))
+}
+
+#[cfg(target_os = "windows")]
+fn config_dir() -> Option<PathBuf> {
+    env_var::string("GH_CONFIG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| env_var::string("XDG_CONFIG_HOME").map(|x| PathBuf::from(x).join("gh")))
+        .or_else(|| env_var::string("APPDATA").map(|x| PathBuf::from(x).join("GitHub CLI")))
+}
+
+/// Only the `oauth_token` of the active account is considered (the direct child of the host),
+/// since the ones of the other accounts are nested in `users`.
+/// Supports only the subset of YAML written by GitHub cli.
+fn parse_hosts_file(content: &str, host: &str) -> Option<String> {
+    let mut in_host = false;
+    let mut child_indent = None;
+    for line in content.lines().filter(|x| !x.trim().is_empty()) {
+        let indent = line.len() - line.trim_start().len();
+        if indent == 0 {
+            in_host = line.trim_end().strip_suffix(':') == Some(host);
+            child_indent = None;
+            continue;
+        }
+        if !in_host || *child_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+        if let Some(token) = line.trim().strip_prefix("oauth_token:") {
+            let token = token.trim().trim_matches(['"', '\'']);
+            if !token.is_empty() {
+                return Some(token.to_string());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod parse_hosts_file {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("github.com:\n    oauth_token: gho_123\n    user: dra\n"; "single account")]
+    #[test_case("github.com:\n    user: dra\n    oauth_token: \"gho_123\"\n"; "quoted")]
+    #[test_case("github.com:\n    users:\n        other:\n            oauth_token: gho_456\n        dra:\n            oauth_token: gho_123\n    git_protocol: https\n    oauth_token: gho_123\n    user: dra\n"; "multiple accounts")]
+    #[test_case("ghe.example.com:\n    oauth_token: gho_789\ngithub.com:\n    oauth_token: gho_123\n"; "multiple hosts")]
+    fn found(content: &str) {
+        let result = parse_hosts_file(content, GITHUB_HOST);
+
+        assert_eq!(Some("gho_123".to_string()), result);
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case("github.com:\n    user: dra\n    git_protocol: https\n"; "token in keyring")]
+    #[test_case("ghe.example.com:\n    oauth_token: gho_789\n"; "other host")]
+    #[test_case("github.com:\n    users:\n        dra:\n            oauth_token: gho_456\n"; "inactive account")]
+    fn not_found(content: &str) {
+        let result = parse_hosts_file(content, GITHUB_HOST);
+
+        assert_eq!(None, result);
+    }
+}
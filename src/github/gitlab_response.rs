@@ -0,0 +1,36 @@
+use serde::Deserialize;
+
+// DOCS: https://docs.gitlab.com/api/releases/
+#[derive(Deserialize, Debug)]
+pub struct GitlabReleaseResponse {
+    pub tag_name: String,
+    pub description: Option<String>,
+    pub released_at: Option<String>,
+    /// Releases with a future release date
+    #[serde(default)]
+    pub upcoming_release: bool,
+    pub assets: GitlabAssetsResponse,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct GitlabAssetsResponse {
+    /// Source code archives generated by GitLab
+    pub sources: Vec<GitlabSourceResponse>,
+    /// Release links, including the files of generic packages
+    pub links: Vec<GitlabLinkResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitlabSourceResponse {
+    pub format: String,
+    pub url: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitlabLinkResponse {
+    pub name: String,
+    pub url: String,
+    /// Permanent url of the link, that redirects to `url`
+    pub direct_asset_url: Option<String>,
+}
@@ -0,0 +1,295 @@
+use crate::github::release::{Release, Tag};
+use crate::github::release_response::{AssetResponse, ReleaseResponse};
+use crate::github::repository::Repository;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Release of a repository to fetch with the GraphQL API, the latest one without a tag
+#[derive(Debug, Clone)]
+pub struct ReleaseQuery {
+    pub repository: Repository,
+    pub tag: Option<Tag>,
+}
+
+/// Result of a `ReleaseQuery`
+#[derive(Debug)]
+pub struct GraphqlRelease {
+    /// `None` when the repository or the release doesn't exist
+    pub release: Option<Release>,
+    /// Tag of the latest release of the repository, even when another release is queried
+    pub latest_tag: Option<Tag>,
+    /// The release has more assets than the response, which has only the first 100
+    pub has_more_assets: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GraphqlRequest {
+    pub query: String,
+}
+
+// DOCS: https://docs.github.com/en/graphql/guides/forming-calls-with-graphql
+#[derive(Deserialize, Debug)]
+pub struct GraphqlResponse {
+    /// Repositories by alias (`r0`, `r1`, ...), `null` when they don't exist
+    pub data: Option<HashMap<String, Option<RepositoryNode>>>,
+    #[serde(default)]
+    pub errors: Vec<GraphqlError>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GraphqlError {
+    pub message: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RepositoryNode {
+    pub release: Option<ReleaseNode>,
+    pub latest: Option<LatestReleaseNode>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LatestReleaseNode {
+    #[serde(rename = "tagName")]
+    pub tag_name: String,
+}
+
+// DOCS: https://docs.github.com/en/graphql/reference/objects#release
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseNode {
+    pub tag_name: String,
+    pub published_at: Option<String>,
+    pub description: Option<String>,
+    pub is_prerelease: bool,
+    pub is_draft: bool,
+    pub release_assets: ReleaseAssetsNode,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseAssetsNode {
+    pub total_count: usize,
+    pub nodes: Vec<ReleaseAssetNode>,
+}
+
+// DOCS: https://docs.github.com/en/graphql/reference/objects#releaseasset
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseAssetNode {
+    pub name: String,
+    pub content_type: Option<String>,
+    pub size: Option<u64>,
+    pub download_count: Option<u64>,
+    pub download_url: String,
+    pub updated_at: Option<String>,
+}
+
+const RELEASE_FIELDS: &str = "fragment ReleaseFields on Release { tagName publishedAt description \
+isPrerelease isDraft releaseAssets(first: 100) { totalCount nodes { name contentType size \
+downloadCount downloadUrl updatedAt } } }";
+
+/// A single query with one aliased repository (`r0`, `r1`, ...) for each release query
+pub fn releases_query(queries: &[ReleaseQuery]) -> String {
+    let repositories = queries
+        .iter()
+        .enumerate()
+        .map(|(index, query)| {
+            let release = match query.tag.as_ref() {
+                Some(tag) => format!("release(tagName: {})", string_literal(&tag.0)),
+                None => "latestRelease".to_string(),
+            };
+            format!(
+                "{alias}: repository(owner: {owner}, name: {name}) {{ release: {release} {{ ...ReleaseFields }} latest: latestRelease {{ tagName }} }}",
+                alias = alias(index),
+                owner = string_literal(&query.repository.owner),
+                name = string_literal(&query.repository.repo),
+                release = release,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("query {{ {} }} {}", repositories, RELEASE_FIELDS)
+}
+
+/// Results in the same order of the queries
+pub fn releases_from(
+    response: GraphqlResponse,
+    queries: &[ReleaseQuery],
+    repository_url: impl Fn(&Repository) -> String,
+) -> Vec<GraphqlRelease> {
+    let mut data = response.data.unwrap_or_default();
+    queries
+        .iter()
+        .enumerate()
+        .map(|(index, query)| {
+            let node = data.remove(&alias(index)).flatten();
+            let latest_tag = node
+                .as_ref()
+                .and_then(|x| x.latest.as_ref())
+                .map(|x| Tag(x.tag_name.clone()));
+            let release = node.and_then(|x| x.release);
+            let has_more_assets = release
+                .as_ref()
+                .is_some_and(|x| x.release_assets.total_count > x.release_assets.nodes.len());
+            GraphqlRelease {
+                release: release.map(|x| {
+                    let url = repository_url(&query.repository);
+                    Release::from_response(x.into_response(&url), &query.repository)
+                }),
+                latest_tag,
+                has_more_assets,
+            }
+        })
+        .collect()
+}
+
+impl ReleaseNode {
+    /// Same shape of the REST API response, whose source code archive urls are
+    /// `{repository url}/tarball/{tag}` and `{repository url}/zipball/{tag}`
+    fn into_response(self, repository_url: &str) -> ReleaseResponse {
+        ReleaseResponse {
+            id: None,
+            tarball_url: Some(format!("{}/tarball/{}", repository_url, self.tag_name)),
+            zipball_url: Some(format!("{}/zipball/{}", repository_url, self.tag_name)),
+            tag_name: self.tag_name,
+            published_at: self.published_at,
+            body: self.description,
+            prerelease: self.is_prerelease,
+            draft: self.is_draft,
+            assets: self
+                .release_assets
+                .nodes
+                .into_iter()
+                .map(|x| AssetResponse {
+                    name: x.name,
+                    browser_download_url: x.download_url,
+                    url: None,
+                    content_type: x.content_type,
+                    size: x.size,
+                    download_count: x.download_count,
+                    updated_at: x.updated_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// GraphQL endpoint of the REST API base url (`/api/v3` on GitHub Enterprise Server)
+pub fn graphql_url(api_url: &str) -> String {
+    match api_url.strip_suffix("/api/v3") {
+        Some(host) => format!("{}/api/graphql", host),
+        None => format!("{}/graphql", api_url),
+    }
+}
+
+fn alias(index: usize) -> String {
+    format!("r{}", index)
+}
+
+/// JSON strings are valid GraphQL strings
+fn string_literal(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn query_of_many_repositories() {
+        let queries = vec![
+            query("devmatteini/dra", None),
+            query("devmatteini/dra-tests", Some("0.1.5")),
+        ];
+
+        let result = releases_query(&queries);
+
+        assert!(result.contains(
+            r#"r0: repository(owner: "devmatteini", name: "dra") { release: latestRelease { ...ReleaseFields }"#
+        ));
+        assert!(result.contains(
+            r#"r1: repository(owner: "devmatteini", name: "dra-tests") { release: release(tagName: "0.1.5") { ...ReleaseFields }"#
+        ));
+        assert!(result.ends_with(RELEASE_FIELDS));
+    }
+
+    #[test]
+    fn releases_in_query_order() {
+        let queries = vec![
+            query("devmatteini/dra-tests", Some("0.1.5")),
+            query("devmatteini/missing", None),
+        ];
+        let response: GraphqlResponse = serde_json::from_str(
+            r#"{
+  "data": {
+    "r0": {
+      "release": {
+        "tagName": "0.1.5",
+        "publishedAt": "2022-06-06T10:00:00Z",
+        "description": "Release notes",
+        "isPrerelease": false,
+        "isDraft": false,
+        "releaseAssets": {
+          "totalCount": 101,
+          "nodes": [
+            {
+              "name": "helloworld.tar.gz",
+              "contentType": "application/gzip",
+              "size": 1024,
+              "downloadCount": 42,
+              "downloadUrl": "https://github.com/devmatteini/dra-tests/releases/download/0.1.5/helloworld.tar.gz",
+              "updatedAt": "2022-06-06T10:00:00Z"
+            }
+          ]
+        }
+      },
+      "latest": { "tagName": "0.2.0" }
+    },
+    "r1": null
+  },
+  "errors": [{ "message": "Could not resolve to a Repository with the name 'devmatteini/missing'." }]
+}"#,
+        )
+        .unwrap();
+
+        let result = releases_from(response, &queries, |x| {
+            format!("https://api.github.com/repos/{}", x)
+        });
+
+        let release = result[0].release.as_ref().unwrap();
+        assert_eq!("0.1.5", release.tag.0);
+        assert_eq!(
+            vec![
+                "helloworld.tar.gz",
+                "dra-tests-0.1.5-source-code.tar.gz",
+                "dra-tests-0.1.5-source-code.zip"
+            ],
+            release
+                .assets
+                .iter()
+                .map(|x| x.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some("0.2.0"),
+            result[0].latest_tag.as_ref().map(|x| x.0.as_str())
+        );
+        assert!(result[0].has_more_assets);
+        assert!(result[1].release.is_none());
+        assert!(result[1].latest_tag.is_none());
+    }
+
+    #[test_case("https://api.github.com", "https://api.github.com/graphql"; "github")]
+    #[test_case("https://ghe.corp/api/v3", "https://ghe.corp/api/graphql"; "enterprise server")]
+    fn graphql_endpoint(api_url: &str, expected: &str) {
+        assert_eq!(expected, graphql_url(api_url));
+    }
+
+    fn query(repository: &str, tag: Option<&str>) -> ReleaseQuery {
+        ReleaseQuery {
+            repository: Repository::try_parse(repository).unwrap(),
+            tag: tag.map(|x| Tag(x.to_string())),
+        }
+    }
+}
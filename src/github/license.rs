@@ -0,0 +1,73 @@
+/// GitHub uses this value when the license can't be identified
+const NO_ASSERTION: &str = "NOASSERTION";
+
+pub fn is_license_file(name: &str) -> bool {
+    let name = name.to_uppercase();
+    name.starts_with("LICENSE") || name.starts_with("LICENCE") || name.starts_with("COPYING")
+}
+
+pub fn from_spdx_id(spdx_id: Option<String>) -> Option<String> {
+    spdx_id.filter(|x| !x.is_empty() && x != NO_ASSERTION)
+}
+
+/// Best-effort detection of the SPDX identifier from the content of a license file.
+/// Only the most common permissive licenses are recognized.
+pub fn detect_license(text: &str) -> Option<String> {
+    let spdx_identifier = text.lines().find_map(|line| {
+        line.split_once("SPDX-License-Identifier:")
+            .map(|(_, id)| id.trim().to_string())
+    });
+    if spdx_identifier.is_some() {
+        return spdx_identifier;
+    }
+
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let license = if normalized.contains("Apache License") && normalized.contains("Version 2.0") {
+        "Apache-2.0"
+    } else if normalized.contains("Permission is hereby granted, free of charge") {
+        "MIT"
+    } else if normalized.contains("Redistribution and use in source and binary forms") {
+        if normalized.contains("Neither the name") {
+            "BSD-3-Clause"
+        } else {
+            "BSD-2-Clause"
+        }
+    } else if normalized
+        .contains("Permission to use, copy, modify, and/or distribute this software")
+    {
+        "ISC"
+    } else {
+        return None;
+    };
+    Some(license.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("MIT License\n\nPermission is hereby granted, free of charge, to any person", "MIT"; "mit")]
+    #[test_case("                                 Apache License\n                           Version 2.0, January 2004", "Apache-2.0"; "apache")]
+    #[test_case("Redistribution and use in source and binary forms, with or without\nmodification", "BSD-2-Clause"; "bsd 2 clause")]
+    #[test_case("Redistribution and use in source and binary forms, with or without\nmodification\n3. Neither the name of the copyright holder", "BSD-3-Clause"; "bsd 3 clause")]
+    #[test_case("// SPDX-License-Identifier: MPL-2.0", "MPL-2.0"; "spdx identifier")]
+    fn detected(text: &str, expected: &str) {
+        assert_eq!(Some(expected.to_string()), detect_license(text));
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(None, detect_license("All rights reserved"));
+    }
+
+    #[test_case(Some("MIT"), Some("MIT"); "spdx id")]
+    #[test_case(Some("NOASSERTION"), None; "no assertion")]
+    #[test_case(None, None; "missing")]
+    fn spdx_id(spdx_id: Option<&str>, expected: Option<&str>) {
+        assert_eq!(
+            expected.map(String::from),
+            from_spdx_id(spdx_id.map(String::from))
+        );
+    }
+}
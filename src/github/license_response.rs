@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct RepositoryLicenseResponse {
+    pub license: LicenseResponse,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LicenseResponse {
+    pub spdx_id: Option<String>,
+}
@@ -0,0 +1,101 @@
+use crate::config::cache_dir;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+/// API responses saved on disk with their ETag, so that the next requests of the same url are
+/// conditional (`If-None-Match`) and an unchanged response (`304 Not Modified`) is read from disk
+#[derive(Debug, Clone)]
+pub struct MetadataCache {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CacheEntry {
+    pub url: String,
+    pub etag: String,
+    /// JSON body of the response
+    pub body: String,
+}
+
+impl MetadataCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// `<cache dir>/dra/metadata` (e.g. `~/.cache/dra/metadata`)
+    pub fn from_environment() -> Option<Self> {
+        cache_dir().map(|dir| Self::new(dir.join("dra").join("metadata")))
+    }
+
+    /// A missing or invalid entry is treated as not cached
+    pub fn get(&self, url: &str) -> Option<CacheEntry> {
+        let content = std::fs::read_to_string(self.entry_path(url)).ok()?;
+        serde_json::from_str::<CacheEntry>(&content)
+            .ok()
+            .filter(|x| x.url == url)
+    }
+
+    /// Failing to write the cache doesn't make the request fail
+    pub fn put(&self, entry: &CacheEntry) {
+        let result = std::fs::create_dir_all(&self.dir).and_then(|_| {
+            let content = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+            std::fs::write(self.entry_path(&entry.url), content)
+        });
+        if let Err(e) = result {
+            debug!("Cannot cache the response of {}: {}", entry.url, e);
+        }
+    }
+
+    /// Entries are named after the hash of the url, which includes the repository and the tag
+    fn entry_path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", url_hash(url)))
+    }
+}
+
+/// SHA-256 of the url, used as file name of the cache entries
+pub fn url_hash(url: &str) -> String {
+    Sha256::digest(url.as_bytes())
+        .iter()
+        .map(|x| format!("{:02x}", x))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://api.github.com/repos/devmatteini/dra-tests/releases/latest";
+
+    #[test]
+    fn cached_response() {
+        let cache = temporary_cache("cached_response");
+        let entry = CacheEntry {
+            url: URL.to_string(),
+            etag: "W/\"abc\"".to_string(),
+            body: r#"{"tag_name": "v1.0.0"}"#.to_string(),
+        };
+
+        cache.put(&entry);
+
+        assert_eq!(Some(entry), cache.get(URL));
+        assert_eq!(None, cache.get(&format!("{}?page=2", URL)));
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn missing_cache_directory() {
+        let cache = temporary_cache("missing_cache_directory");
+
+        assert_eq!(None, cache.get(URL));
+    }
+
+    fn temporary_cache(name: &str) -> MetadataCache {
+        MetadataCache::new(
+            std::env::temp_dir()
+                .join("dra-metadata-cache-tests")
+                .join(name),
+        )
+    }
+}
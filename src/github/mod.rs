@@ -1,7 +1,23 @@
+pub mod asset_cache;
+mod bitbucket_response;
+mod chunked_download;
 pub mod client;
 mod constants;
+pub mod credential_command;
+pub mod device_flow;
 pub mod error;
+mod gh_config;
+mod gitlab_response;
+pub mod graphql;
+pub mod license;
+mod license_response;
+pub mod metadata_cache;
+mod package_response;
+pub mod proxy;
 pub mod release;
 mod release_response;
 pub mod repository;
+pub mod retry;
 pub mod tagged_asset;
+pub mod tls;
+pub mod token_store;
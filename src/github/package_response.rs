@@ -0,0 +1,29 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug)]
+pub struct RegistryTokenResponse {
+    pub token: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ManifestResponse {
+    pub layers: Vec<LayerResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LayerResponse {
+    pub digest: String,
+    pub size: u64,
+    #[serde(default)]
+    pub annotations: HashMap<String, String>,
+}
+
+// https://github.com/opencontainers/image-spec/blob/main/annotations.md#pre-defined-annotation-keys
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+impl LayerResponse {
+    pub fn title(&self) -> Option<&str> {
+        self.annotations.get(TITLE_ANNOTATION).map(String::as_str)
+    }
+}
@@ -1,26 +1,76 @@
+use crate::github::bitbucket_response::BitbucketDownloadResponse;
+use crate::github::gitlab_response::{GitlabLinkResponse, GitlabReleaseResponse};
 use crate::github::release_response::{AssetResponse, ReleaseResponse};
 use crate::github::repository::Repository;
+use std::time::SystemTime;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Tag(pub String);
 
 impl Tag {
     pub fn version(&self) -> String {
         self.0.replace('v', "")
     }
+
+    /// Semantic version of the tag, ignoring the `v` prefix (e.g. `v1.2.3`)
+    pub fn semver(&self) -> Option<semver::Version> {
+        let version = self.0.strip_prefix('v').unwrap_or(&self.0);
+        semver::Version::parse(version).ok()
+    }
+
+    /// Wildcard pattern (e.g. `v1.2.*` or `2024.05.*`) to match tags of non-semver schemes
+    pub fn wildcard(&self) -> Option<wildmatch::WildMatch> {
+        let is_wildcard = self.0.contains(['*', '?']) && self.version_req().is_none();
+        is_wildcard.then(|| wildmatch::WildMatch::new(&self.0))
+    }
+
+    /// Semantic version range (e.g. `^1.4` or `>=2,<3`). Plain versions like `1.4.0` are
+    /// exact tags, not ranges
+    pub fn version_req(&self) -> Option<semver::VersionReq> {
+        let is_range = self.0.starts_with(['^', '~', '<', '>', '=', '*']) || self.0.contains(',');
+        if is_range {
+            semver::VersionReq::parse(&self.0).ok()
+        } else {
+            None
+        }
+    }
+}
+
+/// Kinds of releases considered besides the published stable ones
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReleaseFilter {
+    pub pre_release: bool,
+    pub draft: bool,
 }
 
 #[derive(Debug)]
 pub struct Release {
     pub tag: Tag,
     pub assets: Vec<Asset>,
+    pub published_at: Option<SystemTime>,
+    /// Release notes (markdown)
+    pub body: Option<String>,
+    pub pre_release: bool,
 }
 
-#[derive(Debug)]
+/// Tag of the release made of all the Bitbucket downloads
+pub const BITBUCKET_LATEST_TAG: &str = "latest";
+
+pub const SOURCE_CODE_TAR_GZ: &str = "Source code (tar.gz)";
+pub const SOURCE_CODE_ZIP: &str = "Source code (zip)";
+
+#[derive(Debug, Clone)]
 pub struct Asset {
     pub name: String,
     pub display_name: Option<String>,
     pub download_url: String,
+    /// API url of GitHub release assets (`.../releases/assets/{id}`), used instead of the
+    /// download url when authenticated, since private repository assets may require it
+    pub api_url: Option<String>,
+    pub content_type: Option<String>,
+    pub size: Option<u64>,
+    pub download_count: Option<u64>,
+    pub updated_at: Option<SystemTime>,
 }
 
 impl From<AssetResponse> for Asset {
@@ -28,7 +78,14 @@ impl From<AssetResponse> for Asset {
         Self {
             name: asset.name,
             download_url: asset.browser_download_url,
+            api_url: asset.url,
             display_name: None,
+            content_type: asset.content_type,
+            size: asset.size,
+            download_count: asset.download_count,
+            updated_at: asset
+                .updated_at
+                .and_then(|x| humantime::parse_rfc3339(&x).ok()),
         }
     }
 }
@@ -36,41 +93,181 @@ impl From<AssetResponse> for Asset {
 impl Release {
     pub fn from_response(release: ReleaseResponse, repository: &Repository) -> Self {
         let tag = Tag(release.tag_name);
+        let published_at = release
+            .published_at
+            .and_then(|x| humantime::parse_rfc3339(&x).ok());
 
         let source_code_base = source_code(repository, &tag);
-        let tarball = tarball_asset(release.tarball_url, &source_code_base);
-        let zipball = zipball_asset(release.zipball_url, &source_code_base);
+        let tarball = release
+            .tarball_url
+            .map(|url| tarball_asset(url, &source_code_base));
+        let zipball = release
+            .zipball_url
+            .map(|url| zipball_asset(url, &source_code_base));
+
+        let is_draft = release.draft;
+        let assets = release
+            .assets
+            .into_iter()
+            .map(|x| {
+                if is_draft {
+                    Asset::from_draft(x)
+                } else {
+                    Asset::from(x)
+                }
+            })
+            .chain(tarball)
+            .chain(zipball)
+            .collect();
+
+        Self {
+            tag,
+            assets,
+            published_at,
+            body: release.body,
+            pre_release: release.prerelease,
+        }
+    }
+}
 
+impl Release {
+    /// Release links come first, followed by the source code archives
+    pub fn from_gitlab_response(release: GitlabReleaseResponse, repository: &Repository) -> Self {
+        let tag = Tag(release.tag_name);
+        let published_at = release
+            .released_at
+            .and_then(|x| humantime::parse_rfc3339_weak(&x).ok());
+
+        let source_code_base = source_code(repository, &tag);
+        let sources = release
+            .assets
+            .sources
+            .into_iter()
+            .filter_map(|x| match x.format.as_str() {
+                "tar.gz" => Some(tarball_asset(x.url, &source_code_base)),
+                "zip" => Some(zipball_asset(x.url, &source_code_base)),
+                _ => None,
+            });
         let assets = release
             .assets
+            .links
             .into_iter()
-            .map(Asset::from)
-            .chain([tarball, zipball])
+            .map(Asset::from_gitlab_link)
+            .chain(sources)
             .collect();
 
-        Self { tag, assets }
+        Self {
+            tag,
+            assets,
+            published_at,
+            body: release.description,
+            pre_release: release.upcoming_release,
+        }
+    }
+}
+
+impl Release {
+    /// Bitbucket downloads aren't grouped by release: the latest release has all the downloads,
+    /// while the release of a tag has only the downloads whose name contains its version
+    pub fn from_bitbucket_downloads(
+        downloads: Vec<BitbucketDownloadResponse>,
+        tag: Option<&Tag>,
+    ) -> Self {
+        let assets = downloads
+            .into_iter()
+            .filter(|x| tag.is_none_or(|tag| x.name.contains(&tag.version())))
+            .map(Asset::from_bitbucket_download)
+            .collect::<Vec<_>>();
+        let published_at = assets.iter().filter_map(|x| x.updated_at).max();
+
+        Self {
+            tag: tag
+                .cloned()
+                .unwrap_or_else(|| Tag(BITBUCKET_LATEST_TAG.to_string())),
+            assets,
+            published_at,
+            body: None,
+            pre_release: false,
+        }
     }
 }
 
 impl Asset {
+    fn from_bitbucket_download(download: BitbucketDownloadResponse) -> Self {
+        Self {
+            name: download.name,
+            display_name: None,
+            download_url: download.links.self_link.href,
+            api_url: None,
+            content_type: None,
+            size: download.size,
+            download_count: download.downloads,
+            updated_at: download
+                .created_on
+                .and_then(|x| humantime::parse_rfc3339_weak(&x).ok()),
+        }
+    }
+
+    /// The asset name is the file name of the link url, since link names are often descriptions
+    fn from_gitlab_link(link: GitlabLinkResponse) -> Self {
+        let download_url = link.direct_asset_url.unwrap_or(link.url);
+        let file_name = url::Url::parse(&download_url)
+            .ok()
+            .and_then(|url| url.path_segments()?.next_back().map(String::from))
+            .and_then(|x| urlencoding::decode(&x).ok().map(|x| x.into_owned()))
+            .filter(|x| !x.is_empty());
+        let name = file_name.unwrap_or_else(|| link.name.clone());
+        Self {
+            display_name: (name != link.name).then_some(link.name),
+            name,
+            download_url,
+            api_url: None,
+            content_type: None,
+            size: None,
+            download_count: None,
+            updated_at: None,
+        }
+    }
+
+    /// Assets of draft releases can only be downloaded from the API url
+    fn from_draft(asset: AssetResponse) -> Self {
+        let api_url = asset.url.clone();
+        let asset = Self::from(asset);
+        Self {
+            download_url: api_url.unwrap_or(asset.download_url),
+            ..asset
+        }
+    }
+
     pub fn show_name(&self) -> &str {
         self.display_name.as_ref().unwrap_or(&self.name)
     }
 
-    pub fn is_same_name(&self, name: &str) -> bool {
-        self.display_name
-            .as_deref()
-            .filter(|&n| n == name)
-            .is_some()
-            || self.name == name
+    /// Source code archives generated by the forge, not uploaded to the release
+    pub fn is_source_code(&self) -> bool {
+        matches!(
+            self.display_name.as_deref(),
+            Some(SOURCE_CODE_TAR_GZ | SOURCE_CODE_ZIP)
+        )
     }
 }
 
+/// API urls are `https://api.github.com/repos/...`, or `https://{host}/api/v3/repos/...`
+/// on GitHub Enterprise Server
+pub fn is_api_asset_url(url: &str) -> bool {
+    url.contains("/repos/") && url.contains("/releases/assets/")
+}
+
 fn tarball_asset(url: String, base_name: &str) -> Asset {
     Asset {
         name: format!("{}.tar.gz", base_name),
         download_url: url,
-        display_name: Some("Source code (tar.gz)".to_string()),
+        api_url: None,
+        display_name: Some(SOURCE_CODE_TAR_GZ.to_string()),
+        content_type: Some("application/gzip".to_string()),
+        size: None,
+        download_count: None,
+        updated_at: None,
     }
 }
 
@@ -78,10 +275,234 @@ fn zipball_asset(url: String, base_name: &str) -> Asset {
     Asset {
         name: format!("{}.zip", base_name),
         download_url: url,
-        display_name: Some("Source code (zip)".to_string()),
+        api_url: None,
+        display_name: Some(SOURCE_CODE_ZIP.to_string()),
+        content_type: Some("application/zip".to_string()),
+        size: None,
+        download_count: None,
+        updated_at: None,
     }
 }
 
 fn source_code(repository: &Repository, tag: &Tag) -> String {
     format!("{}-{}-source-code", repository.repo, tag.version(),)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::bitbucket_response::BitbucketDownloadsResponse;
+    use test_case::test_case;
+
+    #[test_case("^1.4"; "caret")]
+    #[test_case(">=2, <3"; "comparators")]
+    #[test_case("~1.4.2"; "tilde")]
+    fn version_range(tag: &str) {
+        assert!(Tag(tag.into()).version_req().is_some());
+    }
+
+    #[test_case("1.4.0"; "plain version")]
+    #[test_case("v1.4.0"; "v tag")]
+    #[test_case("nightly"; "not a version")]
+    fn not_a_version_range(tag: &str) {
+        assert!(Tag(tag.into()).version_req().is_none());
+    }
+
+    #[test_case("v1.2.*", "v1.2.10", true; "version wildcard")]
+    #[test_case("2024.05.*", "2024.05.31", true; "date scheme")]
+    #[test_case("v1.2.*", "v1.3.0", false; "not matching")]
+    fn tag_wildcard(pattern: &str, tag: &str, expected: bool) {
+        let wildcard = Tag(pattern.into()).wildcard().unwrap();
+
+        assert_eq!(expected, wildcard.matches(tag));
+    }
+
+    #[test_case("v1.2.0"; "exact tag")]
+    #[test_case("*"; "version range")]
+    fn not_a_tag_wildcard(tag: &str) {
+        assert!(Tag(tag.into()).wildcard().is_none());
+    }
+
+    #[test]
+    fn draft_assets_use_api_url() {
+        let release = Release::from_response(release_response(true, None), &repository());
+
+        assert_eq!(
+            vec!["https://api.github.com/repos/devmatteini/dra-tests/releases/assets/1"],
+            release
+                .assets
+                .iter()
+                .map(|x| x.download_url.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert!(is_api_asset_url(&release.assets[0].download_url));
+    }
+
+    #[test]
+    fn published_assets_use_browser_url() {
+        let tarball = Some("https://api.github.com/repos/devmatteini/dra-tests/tarball/v1.0.0");
+        let release = Release::from_response(release_response(false, tarball), &repository());
+
+        assert_eq!(
+            vec![
+                "https://github.com/devmatteini/dra-tests/releases/download/v1.0.0/helloworld.tar.gz",
+                "https://api.github.com/repos/devmatteini/dra-tests/tarball/v1.0.0",
+            ],
+            release
+                .assets
+                .iter()
+                .map(|x| x.download_url.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert!(!is_api_asset_url(&release.assets[0].download_url));
+        assert_eq!(
+            Some("https://api.github.com/repos/devmatteini/dra-tests/releases/assets/1"),
+            release.assets[0].api_url.as_deref()
+        );
+    }
+
+    #[test]
+    fn asset_updated_at() {
+        let release = Release::from_response(release_response(false, None), &repository());
+
+        assert_eq!(
+            humantime::parse_rfc3339("2024-05-31T10:00:00Z").ok(),
+            release.assets[0].updated_at
+        );
+    }
+
+    #[test]
+    fn gitlab_release() {
+        let response: GitlabReleaseResponse = serde_json::from_str(
+            r#"{
+                "tag_name": "v1.0.0",
+                "description": "First release",
+                "released_at": "2024-05-31T10:00:00.000Z",
+                "assets": {
+                    "sources": [
+                        {"format": "zip", "url": "https://gitlab.com/group/dra-tests/-/archive/v1.0.0/dra-tests-v1.0.0.zip"},
+                        {"format": "tar.bz2", "url": "https://gitlab.com/group/dra-tests/-/archive/v1.0.0/dra-tests-v1.0.0.tar.bz2"}
+                    ],
+                    "links": [
+                        {
+                            "name": "Linux binary",
+                            "url": "https://gitlab.com/api/v4/projects/1/packages/generic/dra/1.0.0/helloworld.tar.gz",
+                            "direct_asset_url": "https://gitlab.com/group/dra-tests/-/releases/v1.0.0/downloads/helloworld.tar.gz"
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let release = Release::from_gitlab_response(response, &repository());
+
+        assert_eq!(
+            vec![
+                ("helloworld.tar.gz", "Linux binary"),
+                ("dra-tests-1.0.0-source-code.zip", SOURCE_CODE_ZIP),
+            ],
+            release
+                .assets
+                .iter()
+                .map(|x| (x.name.as_str(), x.show_name()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "https://gitlab.com/group/dra-tests/-/releases/v1.0.0/downloads/helloworld.tar.gz",
+            release.assets[0].download_url
+        );
+        assert_eq!(Some("First release".to_string()), release.body);
+        assert!(release.published_at.is_some());
+    }
+
+    #[test_case(None, vec!["dra-1.0.0-linux.tar.gz", "dra-0.9.0-linux.tar.gz"]; "latest")]
+    #[test_case(Some("v0.9.0"), vec!["dra-0.9.0-linux.tar.gz"]; "tag")]
+    fn bitbucket_downloads(tag: Option<&str>, expected: Vec<&str>) {
+        let response: BitbucketDownloadsResponse = serde_json::from_str(
+            r#"{
+                "values": [
+                    {
+                        "name": "dra-1.0.0-linux.tar.gz",
+                        "size": 1024,
+                        "downloads": 3,
+                        "created_on": "2024-05-31T10:00:00.123456+00:00",
+                        "links": {"self": {"href": "https://api.bitbucket.org/2.0/repositories/team/dra/downloads/dra-1.0.0-linux.tar.gz"}}
+                    },
+                    {
+                        "name": "dra-0.9.0-linux.tar.gz",
+                        "links": {"self": {"href": "https://api.bitbucket.org/2.0/repositories/team/dra/downloads/dra-0.9.0-linux.tar.gz"}}
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let tag = tag.map(|x| Tag(x.to_string()));
+
+        let release = Release::from_bitbucket_downloads(response.values, tag.as_ref());
+
+        assert_eq!(
+            expected,
+            release
+                .assets
+                .iter()
+                .map(|x| x.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            tag.map_or(BITBUCKET_LATEST_TAG.to_string(), |x| x.0),
+            release.tag.0
+        );
+    }
+
+    #[test]
+    fn bitbucket_download_created_on() {
+        let response: BitbucketDownloadsResponse = serde_json::from_str(
+            r#"{
+                "values": [
+                    {
+                        "name": "dra.tar.gz",
+                        "created_on": "2024-05-31T10:00:00.123456+00:00",
+                        "links": {"self": {"href": "https://api.bitbucket.org/2.0/repositories/team/dra/downloads/dra.tar.gz"}}
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let release = Release::from_bitbucket_downloads(response.values, None);
+
+        assert!(release.assets[0].updated_at.is_some());
+        assert_eq!(release.assets[0].updated_at, release.published_at);
+    }
+
+    fn release_response(draft: bool, tarball_url: Option<&str>) -> ReleaseResponse {
+        ReleaseResponse {
+            id: Some(1),
+            tag_name: "v1.0.0".into(),
+            tarball_url: tarball_url.map(String::from),
+            zipball_url: None,
+            published_at: None,
+            body: None,
+            prerelease: false,
+            draft,
+            assets: vec![AssetResponse {
+                name: "helloworld.tar.gz".into(),
+                browser_download_url:
+                    "https://github.com/devmatteini/dra-tests/releases/download/v1.0.0/helloworld.tar.gz"
+                        .into(),
+                url: Some(
+                    "https://api.github.com/repos/devmatteini/dra-tests/releases/assets/1".into(),
+                ),
+                content_type: None,
+                size: None,
+                download_count: None,
+                updated_at: Some("2024-05-31T10:00:00Z".into()),
+            }],
+        }
+    }
+
+    fn repository() -> Repository {
+        Repository::try_parse("devmatteini/dra-tests").unwrap()
+    }
+}
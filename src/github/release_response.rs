@@ -2,9 +2,17 @@ use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
 pub struct ReleaseResponse {
+    pub id: Option<u64>,
     pub tag_name: String,
-    pub tarball_url: String,
-    pub zipball_url: String,
+    /// Missing in draft releases whose tag doesn't exist yet
+    pub tarball_url: Option<String>,
+    pub zipball_url: Option<String>,
+    pub published_at: Option<String>,
+    pub body: Option<String>,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
     pub assets: Vec<AssetResponse>,
 }
 
@@ -12,4 +20,10 @@ pub struct ReleaseResponse {
 pub struct AssetResponse {
     pub name: String,
     pub browser_download_url: String,
+    /// API url of the asset, the only one that can download assets of draft releases
+    pub url: Option<String>,
+    pub content_type: Option<String>,
+    pub size: Option<u64>,
+    pub download_count: Option<u64>,
+    pub updated_at: Option<String>,
 }
@@ -3,23 +3,46 @@ use url::Url;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Repository {
+    /// GitLab namespaces can include subgroups (e.g. `group/subgroup`)
     pub owner: String,
     pub repo: String,
+    /// Host of a GitHub Enterprise Server or GitLab instance, `None` for github.com
+    pub host: Option<String>,
+    /// Tag of a release page URL (e.g. `https://github.com/{owner}/{repo}/releases/tag/v1.2.3`),
+    /// used when `--tag` is not set
+    pub tag: Option<String>,
 }
 
+/// Hosting service of the repository
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+    /// Gitea and its forks (e.g. Forgejo, used by Codeberg)
+    Gitea,
+    /// Only the downloads of bitbucket.org repositories are available
+    Bitbucket,
+}
+
+const GITHUB_HOST: &str = "github.com";
+
 impl Repository {
     pub fn try_parse(src: &str) -> Result<Repository, String> {
         if src.is_empty() {
             return Err("Invalid repository. Cannot be empty".to_string());
         }
 
-        if src.starts_with("http://github.com") || src.starts_with("https://github.com") {
+        if src.starts_with("http://") || src.starts_with("https://") || src.starts_with("ssh://") {
             Self::parse_url(src)
+        } else if let Some((host, path)) = scp_like(src) {
+            Self::parse(&format!("{}/{}", host, path))
         } else {
             Self::parse(src)
         }
     }
 
+    /// The host (if any) is the first part of the input when it looks like a domain
+    /// (e.g. `github.example.com/{owner}/{repo}`)
     fn parse(input: &str) -> Result<Repository, String> {
         if !input.contains('/') {
             return Err("Invalid repository. Use {owner}/{repo} format".to_string());
@@ -28,14 +51,12 @@ impl Repository {
             .split('/')
             .filter(|x| !x.is_empty())
             .collect::<Vec<&str>>();
-        if parts.len() < 2 {
-            return Err("Invalid repository. Missing owner or repo".to_string());
-        }
-
-        Ok(Repository {
-            owner: parts[0].to_string(),
-            repo: parts[1].to_string(),
-        })
+        let (host, parts) = match parts.as_slice() {
+            [host, rest @ ..] if rest.len() >= 2 && host.contains('.') => (Some(*host), rest),
+            parts => (None, parts),
+        };
+        Self::from_parts(host, parts)
+            .ok_or_else(|| "Invalid repository. Missing owner or repo".to_string())
     }
 
     fn parse_url(input: &str) -> Result<Repository, String> {
@@ -45,17 +66,73 @@ impl Repository {
             .split('/')
             .filter(|x| !x.is_empty())
             .collect::<Vec<&str>>();
-        if parts.len() < 2 {
-            return Err("Invalid repository URL. Missing owner or repo".to_string());
+        Self::from_parts(github_url.host_str(), &parts)
+            .ok_or_else(|| "Invalid repository URL. Missing owner or repo".to_string())
+    }
+
+    /// On GitHub, the repository is made of the first two parts of the path. On GitLab, the
+    /// project path ends where the page path starts (e.g. `group/subgroup/project/-/releases`)
+    fn from_parts(host: Option<&str>, parts: &[&str]) -> Option<Repository> {
+        let end = match host.map(forge_of) {
+            Some(Forge::GitLab) => parts.iter().position(|x| *x == "-").unwrap_or(parts.len()),
+            _ => parts.len().min(2),
+        };
+        let (parts, page) = parts.split_at(end);
+        match parts {
+            [owner @ .., repo] if !owner.is_empty() => Some(Repository {
+                owner: owner.join("/"),
+                repo: repo.strip_suffix(".git").unwrap_or(repo).to_string(),
+                host: enterprise_host(host),
+                tag: release_tag(page),
+            }),
+            _ => None,
         }
+    }
 
-        Ok(Repository {
-            owner: parts[0].to_string(),
-            repo: parts[1].to_string(),
-        })
+    pub fn forge(&self) -> Forge {
+        self.host.as_deref().map(forge_of).unwrap_or(Forge::GitHub)
     }
 }
 
+/// GitLab and Gitea instances are recognized by their host (e.g. `gitlab.example.com`,
+/// `codeberg.org` or `gitea.example.com`), while Bitbucket is only `bitbucket.org`
+fn forge_of(host: &str) -> Forge {
+    let host = host.to_lowercase();
+    let has_prefix = |prefixes: &[&str]| prefixes.iter().any(|x| host.starts_with(x));
+    if host == "gitlab.com" || has_prefix(&["gitlab."]) {
+        Forge::GitLab
+    } else if host == "codeberg.org" || has_prefix(&["gitea.", "forgejo.", "codeberg."]) {
+        Forge::Gitea
+    } else if host == "bitbucket.org" {
+        Forge::Bitbucket
+    } else {
+        Forge::GitHub
+    }
+}
+
+/// SCP-like syntax of git remotes (e.g. `git@github.com:{owner}/{repo}.git`)
+fn scp_like(input: &str) -> Option<(&str, &str)> {
+    let (user, rest) = input.split_once('@')?;
+    let (host, path) = rest.split_once(':')?;
+    let is_scp_like = !user.contains('/') && !host.contains('/') && !path.is_empty();
+    is_scp_like.then_some((host, path))
+}
+
+/// Tag of release pages (`releases/tag/{tag}`), asset downloads (`releases/download/{tag}/{asset}`)
+/// and GitLab release pages (`-/releases/{tag}`)
+fn release_tag(page: &[&str]) -> Option<String> {
+    let tag = match page {
+        ["releases", "tag" | "download", tag, ..] | ["-", "releases", tag, ..] => tag,
+        _ => return None,
+    };
+    urlencoding::decode(tag).ok().map(|x| x.into_owned())
+}
+
+fn enterprise_host(host: Option<&str>) -> Option<String> {
+    host.filter(|x| !x.eq_ignore_ascii_case(GITHUB_HOST))
+        .map(|x| x.to_lowercase())
+}
+
 impl std::fmt::Display for Repository {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}/{}", &self.owner, &self.repo)
@@ -65,6 +142,7 @@ impl std::fmt::Display for Repository {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_case::test_case;
 
     #[test]
     fn valid_repository() {
@@ -75,7 +153,9 @@ mod tests {
         assert_eq!(
             Ok(Repository {
                 owner: "foo".to_string(),
-                repo: "bar".to_string()
+                repo: "bar".to_string(),
+                host: None,
+                tag: None,
             }),
             result
         );
@@ -90,7 +170,9 @@ mod tests {
         assert_eq!(
             Ok(Repository {
                 owner: "foo".to_string(),
-                repo: "bar".to_string()
+                repo: "bar".to_string(),
+                host: None,
+                tag: None,
             }),
             result
         );
@@ -105,12 +187,102 @@ mod tests {
         assert_eq!(
             Ok(Repository {
                 owner: "foo".to_string(),
-                repo: "bar".to_string()
+                repo: "bar".to_string(),
+                host: None,
+                tag: None,
+            }),
+            result
+        );
+    }
+
+    #[test_case("https://github.example.com/foo/bar"; "url")]
+    #[test_case("github.example.com/foo/bar"; "host prefix")]
+    fn valid_enterprise_repository(input: &str) {
+        let result = Repository::try_parse(input);
+
+        assert_eq!(
+            Ok(Repository {
+                owner: "foo".to_string(),
+                repo: "bar".to_string(),
+                host: Some("github.example.com".to_string()),
+                tag: None,
             }),
             result
         );
     }
 
+    #[test_case("gitlab.com/group/project", "group"; "project")]
+    #[test_case("gitlab.com/group/subgroup/project", "group/subgroup"; "subgroup")]
+    #[test_case("https://gitlab.com/group/subgroup/project/-/releases", "group/subgroup"; "url")]
+    fn valid_gitlab_repository(input: &str, owner: &str) {
+        let result = Repository::try_parse(input).unwrap();
+
+        assert_eq!(owner, result.owner);
+        assert_eq!("project", result.repo);
+        assert_eq!(Forge::GitLab, result.forge());
+    }
+
+    #[test_case("devmatteini/dra", Forge::GitHub; "github")]
+    #[test_case("github.example.com/devmatteini/dra", Forge::GitHub; "github enterprise")]
+    #[test_case("gitlab.example.com/devmatteini/dra", Forge::GitLab; "self-managed gitlab")]
+    #[test_case("codeberg.org/devmatteini/dra", Forge::Gitea; "codeberg")]
+    #[test_case("https://gitea.example.com/devmatteini/dra/releases", Forge::Gitea; "self-hosted gitea")]
+    #[test_case("forgejo.example.com/devmatteini/dra", Forge::Gitea; "self-hosted forgejo")]
+    #[test_case("https://bitbucket.org/devmatteini/dra/downloads/", Forge::Bitbucket; "bitbucket")]
+    fn forge(input: &str, expected: Forge) {
+        let result = Repository::try_parse(input).unwrap();
+
+        assert_eq!(expected, result.forge());
+    }
+
+    #[test]
+    fn github_host_prefix() {
+        let result = Repository::try_parse("github.com/foo/bar");
+
+        assert_eq!(
+            Ok(Repository {
+                owner: "foo".to_string(),
+                repo: "bar".to_string(),
+                host: None,
+                tag: None,
+            }),
+            result
+        );
+    }
+
+    #[test_case("git@github.com:foo/bar.git", None; "scp-like")]
+    #[test_case("ssh://git@github.com/foo/bar.git", None; "ssh url")]
+    #[test_case("https://github.com/foo/bar.git", None; "clone url")]
+    #[test_case("git@github.example.com:foo/bar.git", Some("github.example.com"); "enterprise scp-like")]
+    fn valid_git_remote(input: &str, host: Option<&str>) {
+        let result = Repository::try_parse(input);
+
+        assert_eq!(
+            Ok(Repository {
+                owner: "foo".to_string(),
+                repo: "bar".to_string(),
+                host: host.map(String::from),
+                tag: None,
+            }),
+            result
+        );
+    }
+
+    #[test_case("https://github.com/foo/bar/releases/tag/v1.2.3", Some("v1.2.3"); "release page")]
+    #[test_case("https://github.com/foo/bar/releases/download/v1.2.3/bar.tar.gz", Some("v1.2.3"); "asset download")]
+    #[test_case("https://github.com/foo/bar/releases/tag/release%2F1.0", Some("release/1.0"); "encoded tag")]
+    #[test_case("https://gitlab.com/group/bar/-/releases/v1.2.3", Some("v1.2.3"); "gitlab release page")]
+    #[test_case("https://codeberg.org/foo/bar/releases/tag/v1.2.3", Some("v1.2.3"); "gitea release page")]
+    #[test_case("github.com/foo/bar/releases/tag/v1.2.3", Some("v1.2.3"); "host prefix")]
+    #[test_case("https://github.com/foo/bar/releases", None; "releases page")]
+    #[test_case("https://github.com/foo/bar/tree/releases/tag/v1", None; "other page")]
+    fn tag_from_url(input: &str, expected: Option<&str>) {
+        let result = Repository::try_parse(input).unwrap();
+
+        assert_eq!(expected.map(String::from), result.tag);
+        assert_eq!("bar", result.repo);
+    }
+
     #[test]
     fn missing_owner() {
         let input = "/bar";
@@ -160,11 +332,10 @@ mod tests {
     where
         F: FnOnce(&str),
     {
-        if actual.is_ok() {
-            panic!("actual is ok: {:#?}", actual.unwrap())
+        match actual {
+            Ok(repository) => panic!("actual is ok: {:#?}", repository),
+            Err(error) => assert(&error),
         }
-        let error = actual.err().unwrap();
-        assert(&error);
     }
 
     fn assert_contains(expected: &str, actual: &str) {
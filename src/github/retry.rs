@@ -0,0 +1,190 @@
+use crate::github::error::GithubError;
+use log::warn;
+use std::time::{Duration, SystemTime};
+
+pub const DEFAULT_RETRIES: u32 = 2;
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+/// Added to the rate limit reset time, to avoid retrying a moment too early
+const RATE_LIMIT_MARGIN: Duration = Duration::from_secs(1);
+/// A rate limit still exceeded after its reset (e.g. a secondary rate limit) is not waited forever
+const MAX_RATE_LIMIT_WAITS: u32 = 2;
+
+/// Retries transient failures (server errors, timeouts and dropped connections).
+/// The first retry waits `delay`, then the delay doubles at each retry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub delay: Duration,
+    /// Wait until the rate limit resets and retry, when the reset time is known
+    pub wait_for_rate_limit: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: DEFAULT_RETRIES,
+            delay: DEFAULT_RETRY_DELAY,
+            wait_for_rate_limit: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Time to wait before the `retry`-th retry (starting from 1)
+    pub fn delay_before(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.delay.saturating_mul(factor).min(MAX_RETRY_DELAY)
+    }
+
+    pub fn run<T, F>(&self, mut operation: F) -> Result<T, GithubError>
+    where
+        F: FnMut() -> Result<T, GithubError>,
+    {
+        let mut retry = 0;
+        let mut rate_limit_waits = 0;
+        loop {
+            match operation() {
+                Err(e) if e.is_transient() && retry < self.retries => {
+                    retry += 1;
+                    let delay = self.delay_before(retry);
+                    warn!(
+                        "{}, retrying in {} ({}/{})",
+                        e,
+                        humantime::format_duration(delay),
+                        retry,
+                        self.retries
+                    );
+                    std::thread::sleep(delay);
+                }
+                Err(GithubError::RateLimitExceeded(Some(reset)))
+                    if self.wait_for_rate_limit && rate_limit_waits < MAX_RATE_LIMIT_WAITS =>
+                {
+                    rate_limit_waits += 1;
+                    let wait = reset.duration_since(SystemTime::now()).unwrap_or_default()
+                        + RATE_LIMIT_MARGIN;
+                    warn!(
+                        "GitHub API rate limit exceeded, waiting {} until it resets",
+                        humantime::format_duration(Duration::from_secs(wait.as_secs()))
+                    );
+                    std::thread::sleep(wait);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use test_case::test_case;
+
+    #[test_case(1, Duration::from_secs(1); "first retry")]
+    #[test_case(2, Duration::from_secs(2); "second retry")]
+    #[test_case(4, Duration::from_secs(8); "fourth retry")]
+    #[test_case(20, MAX_RETRY_DELAY; "max delay")]
+    fn exponential_delay(retry: u32, expected: Duration) {
+        let policy = RetryPolicy {
+            retries: 3,
+            delay: Duration::from_secs(1),
+            wait_for_rate_limit: false,
+        };
+
+        assert_eq!(expected, policy.delay_before(retry));
+    }
+
+    #[test]
+    fn transient_error_is_retried() {
+        let attempts = Cell::new(0);
+
+        let result = no_delay(3).run(|| {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() < 3 {
+                Err(server_error())
+            } else {
+                Ok("done")
+            }
+        });
+
+        assert_eq!("done", result.unwrap());
+        assert_eq!(3, attempts.get());
+    }
+
+    #[test]
+    fn retries_exhausted() {
+        let attempts = Cell::new(0);
+
+        let result: Result<(), GithubError> = no_delay(2).run(|| {
+            attempts.set(attempts.get() + 1);
+            Err(server_error())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(3, attempts.get());
+    }
+
+    #[test]
+    fn permanent_error_is_not_retried() {
+        let attempts = Cell::new(0);
+
+        let result: Result<(), GithubError> = no_delay(3).run(|| {
+            attempts.set(attempts.get() + 1);
+            Err(GithubError::RepositoryOrReleaseNotFound)
+        });
+
+        assert!(result.is_err());
+        assert_eq!(1, attempts.get());
+    }
+
+    #[test_case(true, 2; "wait")]
+    #[test_case(false, 1; "no wait")]
+    fn rate_limit(wait_for_rate_limit: bool, expected_attempts: u32) {
+        let attempts = Cell::new(0);
+        let policy = RetryPolicy {
+            wait_for_rate_limit,
+            ..no_delay(0)
+        };
+
+        let _ = policy.run(|| {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() < 2 {
+                Err(GithubError::RateLimitExceeded(Some(SystemTime::now())))
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(expected_attempts, attempts.get());
+    }
+
+    #[test]
+    fn rate_limit_waits_exhausted() {
+        let attempts = Cell::new(0);
+        let policy = RetryPolicy {
+            wait_for_rate_limit: true,
+            ..no_delay(0)
+        };
+
+        let result: Result<(), GithubError> = policy.run(|| {
+            attempts.set(attempts.get() + 1);
+            Err(GithubError::RateLimitExceeded(Some(SystemTime::UNIX_EPOCH)))
+        });
+
+        assert!(matches!(result, Err(GithubError::RateLimitExceeded(_))));
+        assert_eq!(MAX_RATE_LIMIT_WAITS + 1, attempts.get());
+    }
+
+    fn no_delay(retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            retries,
+            delay: Duration::ZERO,
+            wait_for_rate_limit: false,
+        }
+    }
+
+    fn server_error() -> GithubError {
+        GithubError::from(ureq::Error::StatusCode(503))
+    }
+}
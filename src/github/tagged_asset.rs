@@ -4,9 +4,20 @@ pub struct TaggedAsset;
 
 impl TaggedAsset {
     const PLACEHOLDER: &'static str = "{tag}";
+    const VERSION_PLACEHOLDER: &'static str = "{version}";
+    const VERSION_UNDERSCORE_PLACEHOLDER: &'static str = "{version_underscore}";
 
+    /// `{tag}` and `{version}` are replaced by the version without the `v` prefix (e.g. `1.2.3`),
+    /// `{version_underscore}` by the version with underscores (e.g. `1_2_3`)
     pub fn tag(tag: &Tag, untagged: &str) -> String {
-        untagged.replace(Self::PLACEHOLDER, &tag.version())
+        let version = tag.version();
+        untagged
+            .replace(
+                Self::VERSION_UNDERSCORE_PLACEHOLDER,
+                &version.replace('.', "_"),
+            )
+            .replace(Self::VERSION_PLACEHOLDER, &version)
+            .replace(Self::PLACEHOLDER, &version)
     }
 
     pub fn untag(tag: &Tag, asset: &Asset) -> String {
@@ -44,6 +55,15 @@ mod tests {
         assert_eq!(expected.to_string(), result);
     }
 
+    #[test_case("v1.5.3", "file-{version}-linux.deb", "file-1.5.3-linux.deb"; "version")]
+    #[test_case("v1.5.3", "file-{version_underscore}-linux.deb", "file-1_5_3-linux.deb"; "version underscore")]
+    #[test_case("v1.5.3", "file-{tag}-{version_underscore}.deb", "file-1.5.3-1_5_3.deb"; "multiple placeholders")]
+    fn tag_version_placeholders(tag: &str, untagged: &str, expected: &str) {
+        let result = TaggedAsset::tag(&tag_for(tag), untagged);
+
+        assert_eq!(expected.to_string(), result);
+    }
+
     #[test]
     fn tag_no_tag_in_asset_name() {
         let result = TaggedAsset::tag(&tag_for("v1.5.3"), "file-linux.deb");
@@ -59,7 +79,12 @@ mod tests {
         Asset {
             name: name.to_string(),
             download_url: "ANY_DOWNLOAD_URL".to_string(),
+            api_url: None,
             display_name: None,
+            content_type: None,
+            size: None,
+            download_count: None,
+            updated_at: None,
         }
     }
 }
@@ -0,0 +1,61 @@
+use std::path::Path;
+use ureq::tls::{Certificate, PemItem, RootCerts, TlsConfig};
+
+/// `ca_bundle` replaces the built-in root certificates (e.g. with the CA of a TLS-intercepting proxy),
+/// while `insecure` disables the verification of the server certificates
+pub fn tls_config(ca_bundle: Option<&Path>, insecure: bool) -> Result<TlsConfig, String> {
+    let config = TlsConfig::builder().disable_verification(insecure);
+    let config = match ca_bundle {
+        Some(path) => {
+            let content = std::fs::read(path)
+                .map_err(|e| format!("Error reading CA bundle {}: {}", path.display(), e))?;
+            let certificates = parse_ca_bundle(&content)
+                .map_err(|e| format!("Invalid CA bundle {}: {}", path.display(), e))?;
+            config.root_certs(RootCerts::new_with_certs(&certificates))
+        }
+        None => config,
+    };
+    Ok(config.build())
+}
+
+fn parse_ca_bundle(content: &[u8]) -> Result<Vec<Certificate<'static>>, String> {
+    let mut certificates = vec![];
+    for item in ureq::tls::parse_pem(content) {
+        match item.map_err(|e| e.to_string())? {
+            PemItem::Certificate(certificate) => certificates.push(certificate),
+            _ => continue,
+        }
+    }
+    if certificates.is_empty() {
+        Err("no PEM certificate found".to_string())
+    } else {
+        Ok(certificates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+AAECAwQF
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn certificates_found() {
+        let bundle = format!("# Corporate CA\n{}{}", CERTIFICATE, CERTIFICATE);
+
+        let result = parse_ca_bundle(bundle.as_bytes()).unwrap();
+
+        assert_eq!(2, result.len());
+        assert_eq!(&[0, 1, 2, 3, 4, 5], result[0].der());
+    }
+
+    #[test]
+    fn no_certificate() {
+        let result = parse_ca_bundle(b"not a certificate");
+
+        assert_eq!(Some("no PEM certificate found".to_string()), result.err());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::config::config_dir;
+use log::{debug, warn};
+use std::path::PathBuf;
+
+const KEYRING_SERVICE: &str = "dra";
+const KEYRING_USER: &str = "github.com";
+
+/// Where the token saved by `dra auth login` is stored
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenStorage {
+    /// Secret Service on Linux, Keychain on macOS and Credential Manager on Windows
+    Keyring,
+    /// Fallback when the system keyring isn't available
+    File(PathBuf),
+}
+
+impl std::fmt::Display for TokenStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenStorage::Keyring => f.write_str("system keyring"),
+            TokenStorage::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+pub struct StoredToken {
+    pub value: String,
+    pub storage: TokenStorage,
+}
+
+/// Token saved by `dra auth login`, used when no token environment variable is set.
+/// The system keyring takes precedence over the token file
+pub fn load() -> Option<StoredToken> {
+    load_from_keyring()
+        .map(|value| StoredToken {
+            value,
+            storage: TokenStorage::Keyring,
+        })
+        .or_else(|| {
+            let path = token_path()?;
+            let content = std::fs::read_to_string(&path).ok()?;
+            let token = content.trim();
+            (!token.is_empty()).then(|| StoredToken {
+                value: token.to_string(),
+                storage: TokenStorage::File(path),
+            })
+        })
+}
+
+/// The token is saved in the system keyring, unless `insecure_storage` is set or the keyring
+/// isn't available (e.g. no Secret Service on a headless Linux). In that case, it's saved in a
+/// file readable only by the current user
+pub fn save(token: &str, insecure_storage: bool) -> Result<TokenStorage, String> {
+    if !insecure_storage {
+        match keyring_entry().and_then(|x| x.set_password(token)) {
+            Ok(_) => {
+                // An old token file would be ignored anyway, but it's better not to leave it around
+                delete_file().ok();
+                return Ok(TokenStorage::Keyring);
+            }
+            Err(e) => warn!(
+                "Cannot save the token in the system keyring ({}), saving it in a file instead",
+                e
+            ),
+        }
+    }
+    save_to_file(token)
+        .map(TokenStorage::File)
+        .map_err(|e| format!("Error saving the token: {}", e))
+}
+
+/// Storages the token was removed from
+pub fn delete() -> Result<Vec<TokenStorage>, String> {
+    let mut deleted = vec![];
+    match keyring_entry().and_then(|x| x.delete_credential()) {
+        Ok(_) => deleted.push(TokenStorage::Keyring),
+        Err(keyring::Error::NoEntry) => {}
+        Err(e) => debug!("Cannot delete the token from the system keyring: {}", e),
+    }
+    if let Some(path) = delete_file().map_err(|e| format!("Error deleting the token: {}", e))? {
+        deleted.push(TokenStorage::File(path));
+    }
+    Ok(deleted)
+}
+
+fn keyring_entry() -> keyring::Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+}
+
+fn load_from_keyring() -> Option<String> {
+    match keyring_entry().and_then(|x| x.get_password()) {
+        Ok(token) => Some(token),
+        Err(keyring::Error::NoEntry) => None,
+        Err(e) => {
+            debug!("Cannot read the token from the system keyring: {}", e);
+            None
+        }
+    }
+}
+
+fn token_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("dra").join("token"))
+}
+
+fn save_to_file(token: &str) -> std::io::Result<PathBuf> {
+    let path = token_path().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Cannot find the config directory",
+        )
+    })?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    write_private(&path, token)?;
+    Ok(path)
+}
+
+/// Path of the deleted file, if it existed
+fn delete_file() -> std::io::Result<Option<PathBuf>> {
+    let Some(path) = token_path() else {
+        return Ok(None);
+    };
+    match std::fs::remove_file(&path) {
+        Ok(_) => Ok(Some(path)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn write_private(path: &std::path::Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // The mode is applied only when the file is created
+    file.set_permissions(PermissionsExt::from_mode(0o600))?;
+    file.write_all(content.as_bytes())
+}
+
+/// Files in the user profile are already private to the user
+#[cfg(target_os = "windows")]
+fn write_private(path: &std::path::Path, content: &str) -> std::io::Result<()> {
+    std::fs::write(path, content)
+}
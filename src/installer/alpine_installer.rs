@@ -0,0 +1,30 @@
+use std::process::Command;
+
+use crate::installer::command::exec_command;
+use crate::installer::destination::Destination;
+use crate::installer::executable::Executable;
+use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
+use crate::installer::result::{InstallOutput, InstallerResult};
+
+const APK: &str = "apk";
+
+pub struct AlpineInstaller;
+
+impl AlpineInstaller {
+    pub fn run(
+        file_info: SupportedFileInfo,
+        _destination: Destination,
+        _executables: Vec<Executable>,
+        options: &InstallOptions,
+    ) -> InstallerResult {
+        let mut command = Command::new(APK);
+        command.arg("add");
+        // Packages published as release assets are rarely signed with a key trusted by apk
+        if options.allow_untrusted_packages {
+            command.arg("--allow-untrusted");
+        }
+        exec_command(APK, command.arg(file_info.path))
+            .map(|_| InstallOutput::new(format!("Alpine package '{}' installed", file_info.name)))
+    }
+}
@@ -0,0 +1,32 @@
+use crate::installer::desktop_entry::install_desktop_entry;
+use crate::installer::destination::Destination;
+use crate::installer::executable::Executable;
+use crate::installer::executable_file_installer::ExecutableFileInstaller;
+use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
+use crate::installer::result::{InstallOutput, InstallerResult};
+
+pub struct AppImageInstaller;
+
+impl AppImageInstaller {
+    pub fn run(
+        file_info: SupportedFileInfo,
+        destination: Destination,
+        executables: Vec<Executable>,
+        options: &InstallOptions,
+    ) -> InstallerResult {
+        let output = ExecutableFileInstaller::run(file_info, destination, executables, options)?;
+        if !options.desktop_entry {
+            return Ok(output);
+        }
+
+        let mut messages = vec![output.to_string()];
+        for appimage in output.executables() {
+            messages.push(install_desktop_entry(appimage)?.to_string());
+        }
+        Ok(InstallOutput::with_executables(
+            messages.join("\n"),
+            output.executables().to_vec(),
+        ))
+    }
+}
@@ -0,0 +1,30 @@
+use std::process::Command;
+
+use crate::installer::command::exec_command;
+use crate::installer::destination::Destination;
+use crate::installer::executable::Executable;
+use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
+use crate::installer::result::{InstallOutput, InstallerResult};
+
+const PACMAN: &str = "pacman";
+
+pub struct ArchInstaller;
+
+impl ArchInstaller {
+    pub fn run(
+        file_info: SupportedFileInfo,
+        _destination: Destination,
+        _executables: Vec<Executable>,
+        _options: &InstallOptions,
+    ) -> InstallerResult {
+        exec_command(
+            PACMAN,
+            Command::new(PACMAN)
+                .arg("--upgrade")
+                .arg("--noconfirm")
+                .arg(file_info.path),
+        )
+        .map(|_| InstallOutput::new(format!("Arch package '{}' installed", file_info.name)))
+    }
+}
@@ -4,6 +4,8 @@ use crate::installer::error::{
 };
 use crate::installer::executable::Executable;
 use crate::installer::file::SupportedFileInfo;
+use crate::installer::man_page::install_man_pages;
+use crate::installer::options::InstallOptions;
 use crate::installer::result::{InstallOutput, InstallerResult};
 use itertools::{Either, Itertools};
 use std::ffi::OsString;
@@ -20,6 +22,7 @@ impl ArchiveInstaller {
         file_info: SupportedFileInfo,
         destination: Destination,
         executables_to_install: Vec<Executable>,
+        options: &InstallOptions,
     ) -> InstallerResult
     where
         F: FnOnce(&Path, &Path) -> Result<(), InstallError>,
@@ -28,6 +31,7 @@ impl ArchiveInstaller {
         extract_files(&file_info.path, &temp_dir)?;
 
         let all_executables = Self::all_executables_from(&temp_dir)?;
+        let executables_to_install = Self::expand_all(executables_to_install, &all_executables);
         let (successes, failures) = Self::try_install_executables(
             &temp_dir,
             &destination,
@@ -35,16 +39,32 @@ impl ArchiveInstaller {
             all_executables,
         );
 
+        let messages: Vec<_> = successes
+            .iter()
+            .map(|destination_path| {
+                format!(
+                    "Extracted archive executable to '{}'",
+                    destination_path.display()
+                )
+            })
+            .collect();
+
         if !failures.is_empty() {
             return Err(InstallError::Archive(ArchiveInstallerError {
-                successes,
+                successes: messages,
                 failures,
             }));
         }
 
+        let mut message = messages.join("\n").to_string();
+        if options.install_man_pages {
+            let man_pages = install_man_pages(&temp_dir)?;
+            message = format!("{}\n{}", message, man_pages);
+        }
+
         Self::cleanup(&temp_dir)?;
 
-        Ok(InstallOutput::new(successes.join("\n").to_string()))
+        Ok(InstallOutput::with_executables(message, successes))
     }
 
     fn create_temp_dir() -> Result<PathBuf, InstallError> {
@@ -70,12 +90,24 @@ impl ArchiveInstaller {
         Ok(executables)
     }
 
+    /// `Executable::All` is replaced by every executable of the archive, once per name
+    fn expand_all(executables: Vec<Executable>, all: &[ExecutableFile]) -> Vec<Executable> {
+        if !executables.iter().any(|x| matches!(x, Executable::All)) {
+            return executables;
+        }
+        all.iter()
+            .map(|x| x.name.to_string_lossy().to_string())
+            .unique()
+            .map(Executable::Selected)
+            .collect()
+    }
+
     fn try_install_executables(
         temp_dir: &Path,
         destination: &Destination,
         executables_to_install: Vec<Executable>,
         all_executables: Vec<ExecutableFile>,
-    ) -> (Vec<String>, Vec<ArchiveError>) {
+    ) -> (Vec<PathBuf>, Vec<ArchiveError>) {
         executables_to_install
             .into_iter()
             .map(|executable| {
@@ -84,12 +116,6 @@ impl ArchiveInstaller {
                         Self::copy_executable_to_destination(executable, destination)
                     })
                     .map_err(|error| ArchiveError(executable.name(), error))
-                    .map(|destination_path| {
-                        format!(
-                            "Extracted archive executable to '{}'",
-                            destination_path.display()
-                        )
-                    })
             })
             .partition_map(|result| match result {
                 Ok(x) => Either::Left(x),
@@ -105,6 +131,7 @@ impl ArchiveInstaller {
         match executable {
             Executable::Automatic(name) => Self::discover_executable(executables, name, directory),
             Executable::Selected(name) => Self::find_selected_executable(executables, name),
+            Executable::All => Err(ArchiveErrorType::ExecutableNotFound),
         }
     }
 
@@ -217,6 +244,7 @@ mod tests {
     use crate::installer::destination::Destination;
     use crate::installer::error::{ArchiveError, ArchiveErrorType, ArchiveInstallerError};
     use crate::installer::executable::Executable;
+    use crate::installer::options::InstallOptions;
     use crate::installer::result::InstallerResult;
     use crate::installer::{
         error::InstallError,
@@ -240,6 +268,7 @@ mod tests {
             any_file_info(),
             destination,
             vec![executable],
+            &InstallOptions::default(),
         );
 
         assert_ok(result);
@@ -262,6 +291,7 @@ mod tests {
             any_file_info(),
             destination,
             vec![executable],
+            &InstallOptions::default(),
         );
 
         assert_ok(result);
@@ -283,6 +313,7 @@ mod tests {
             any_file_info(),
             destination,
             vec![executable],
+            &InstallOptions::default(),
         );
 
         assert_no_executable(result);
@@ -306,6 +337,7 @@ mod tests {
             any_file_info(),
             destination,
             vec![executable],
+            &InstallOptions::default(),
         );
 
         assert_too_many_candidates(vec!["some-random-script", "mytool", "install.sh"], result)
@@ -336,6 +368,7 @@ mod tests {
                 Executable::Selected(mytool2.clone()),
                 Executable::Selected(mytool3.clone()),
             ],
+            &InstallOptions::default(),
         );
 
         assert_ok(result);
@@ -368,6 +401,7 @@ mod tests {
                 Executable::Selected(mytool2.clone()),
                 Executable::Selected(mytool3.clone()),
             ],
+            &InstallOptions::default(),
         );
 
         let error = assert_archive_error(result);
@@ -408,6 +442,7 @@ mod tests {
                 Executable::Selected(mytool3.clone()),
                 Executable::Selected(mytool4.clone()),
             ],
+            &InstallOptions::default(),
         );
 
         let error = assert_archive_error(result);
@@ -437,12 +472,37 @@ mod tests {
             any_file_info(),
             destination,
             vec![any_automatic_executable_name()],
+            &InstallOptions::default(),
         );
 
         assert_ok(result);
         assert_file_exists(executable_path(&destination_dir, "my-executable"))
     }
 
+    #[test]
+    fn all_executables() {
+        let destination_dir = temp_dir("all_executables");
+        let destination = Destination::Directory(destination_dir.clone());
+
+        let result = ArchiveInstaller::run(
+            |_, temp_dir| {
+                let nested_dir = create_dir(temp_dir, "nested");
+                create_file(temp_dir, "README.md");
+                create_executable_file(temp_dir, "mytool");
+                create_executable_file(&nested_dir, "mytool2");
+                Ok(())
+            },
+            any_file_info(),
+            destination,
+            vec![Executable::All],
+            &InstallOptions::default(),
+        );
+
+        assert_ok(result);
+        assert_file_exists(executable_path(&destination_dir, "mytool"));
+        assert_file_exists(executable_path(&destination_dir, "mytool2"));
+    }
+
     fn any_automatic_executable_name() -> Executable {
         Executable::Automatic(executable_name("ANY_EXECUTABLE_NAME"))
     }
@@ -1,7 +1,11 @@
 use crate::installer::error::{InstallError, InstallErrorMapErr};
+use log::debug;
+use std::ffi::OsStr;
+use std::path::Path;
 use std::process::{Command, Output};
 
 pub fn exec_command(name: &str, command: &mut Command) -> Result<(), InstallError> {
+    debug!("Executing {:?}", command);
     command
         .output()
         .map_fatal_err(format!("An error occurred executing '{}'", name))
@@ -23,3 +27,62 @@ fn handle_command_output(name: &str, output: Output) -> Result<(), InstallError>
         )))
     }
 }
+
+/// First of the commands (in order of preference) found in `$PATH`
+pub fn find_command<'a>(names: &[&'a str]) -> Option<&'a str> {
+    let path = std::env::var_os("PATH")?;
+    find_command_in(names, &path)
+}
+
+fn find_command_in<'a>(names: &[&'a str], path: &OsStr) -> Option<&'a str> {
+    let directories = std::env::split_paths(path).collect::<Vec<_>>();
+    names.iter().copied().find(|name| {
+        directories
+            .iter()
+            .any(|directory| is_command(directory, name))
+    })
+}
+
+fn is_command(directory: &Path, name: &str) -> bool {
+    let path = directory.join(name);
+    path.is_file() || (cfg!(windows) && path.with_extension("exe").is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn first_found_in_order_of_preference() {
+        let first = temp_dir("first");
+        let second = temp_dir("second");
+        create_file(&first, "7za");
+        create_file(&second, "7zz");
+        let path = std::env::join_paths([first, second]).unwrap();
+
+        let result = find_command_in(&["7z", "7zz", "7za"], &path);
+
+        assert_eq!(Some("7zz"), result);
+    }
+
+    #[test]
+    fn not_found() {
+        let path = std::env::join_paths([temp_dir("empty")]).unwrap();
+
+        let result = find_command_in(&["7z", "7zz", "7za"], &path);
+
+        assert_eq!(None, result);
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("dra-command-tests").join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn create_file(directory: &Path, name: &str) {
+        std::fs::write(directory.join(name), "").unwrap();
+    }
+}
@@ -6,6 +6,7 @@ use crate::installer::destination::Destination;
 use crate::installer::error::InstallErrorMapErr;
 use crate::installer::executable::{Executable, set_executable_permissions};
 use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
 use crate::installer::result::{InstallOutput, InstallerResult};
 
 pub struct CompressedFileInstaller;
@@ -15,9 +16,10 @@ impl CompressedFileInstaller {
         file_info: SupportedFileInfo,
         destination: Destination,
         _executables: Vec<Executable>,
+        _options: &InstallOptions,
     ) -> InstallerResult {
         Self::decompress_and_move(
-            |file| Box::new(flate2::read::GzDecoder::new(file)),
+            |file| Ok(Box::new(flate2::read::GzDecoder::new(file))),
             file_info,
             destination,
         )
@@ -27,9 +29,10 @@ impl CompressedFileInstaller {
         file_info: SupportedFileInfo,
         destination: Destination,
         _executables: Vec<Executable>,
+        _options: &InstallOptions,
     ) -> InstallerResult {
         Self::decompress_and_move(
-            |file| Box::new(xz2::read::XzDecoder::new(file)),
+            |file| Ok(Box::new(xz2::read::XzDecoder::new(file))),
             file_info,
             destination,
         )
@@ -39,9 +42,23 @@ impl CompressedFileInstaller {
         file_info: SupportedFileInfo,
         destination: Destination,
         _executables: Vec<Executable>,
+        _options: &InstallOptions,
     ) -> InstallerResult {
         Self::decompress_and_move(
-            |file| Box::new(bzip2::read::BzDecoder::new(file)),
+            |file| Ok(Box::new(bzip2::read::BzDecoder::new(file))),
+            file_info,
+            destination,
+        )
+    }
+
+    pub fn zst(
+        file_info: SupportedFileInfo,
+        destination: Destination,
+        _executables: Vec<Executable>,
+        _options: &InstallOptions,
+    ) -> InstallerResult {
+        Self::decompress_and_move(
+            |file| Ok(Box::new(zstd::stream::read::Decoder::new(file)?)),
             file_info,
             destination,
         )
@@ -53,12 +70,13 @@ impl CompressedFileInstaller {
         destination: Destination,
     ) -> InstallerResult
     where
-        D: FnOnce(File) -> Box<dyn Read>,
+        D: FnOnce(File) -> std::io::Result<Box<dyn Read>>,
     {
         let compressed_file = File::open(&file_info.path)
             .map_fatal_err(format!("Error opening {}", file_info.path.display()))?;
 
-        let mut stream = decode(compressed_file);
+        let mut stream = decode(compressed_file)
+            .map_fatal_err(format!("Error reading {}", file_info.path.display()))?;
 
         let executable_path = match destination {
             Destination::Directory(dir) => dir.join(executable_name(&file_info)),
@@ -72,10 +90,13 @@ impl CompressedFileInstaller {
 
         set_executable_permissions(&executable_path)?;
 
-        Ok(InstallOutput::new(format!(
-            "Extracted compressed executable to '{}'",
-            executable_path.display()
-        )))
+        Ok(InstallOutput::with_executables(
+            format!(
+                "Extracted compressed executable to '{}'",
+                executable_path.display()
+            ),
+            vec![executable_path],
+        ))
     }
 }
 
@@ -1,9 +1,15 @@
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use std::path::Path;
 use std::process::Command;
 
-use crate::installer::command::exec_command;
+use crate::installer::archive_installer::ArchiveInstaller;
+use crate::installer::command::{exec_command, find_command};
 use crate::installer::destination::Destination;
+use crate::installer::error::{InstallError, InstallErrorMapErr};
 use crate::installer::executable::Executable;
 use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
 use crate::installer::result::{InstallOutput, InstallerResult};
 
 const DPKG: &str = "dpkg";
@@ -11,15 +17,180 @@ const DPKG: &str = "dpkg";
 pub struct DebianInstaller;
 
 impl DebianInstaller {
+    /// Without `dpkg` (or with `--extract-deb`) the executables are extracted from the package,
+    /// without installing it
     pub fn run(
         file_info: SupportedFileInfo,
-        _destination: Destination,
-        _executables: Vec<Executable>,
+        destination: Destination,
+        executables: Vec<Executable>,
+        options: &InstallOptions,
     ) -> InstallerResult {
+        if options.extract_deb || find_command(&[DPKG]).is_none() {
+            return ArchiveInstaller::run(
+                Self::extract_executables,
+                file_info,
+                destination,
+                executables,
+                options,
+            );
+        }
+
         exec_command(
             DPKG,
             Command::new(DPKG).arg("--install").arg(file_info.path),
         )
         .map(|_| InstallOutput::new(format!("Debian package '{}' installed", file_info.name)))
     }
+
+    /// The files of the package are in the `data.tar.*` member of the `ar` archive, and the
+    /// executables are the ones in a `bin` or `sbin` directory (e.g. `./usr/bin/tool`)
+    fn extract_executables(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
+        let mut package = File::open(source)
+            .map_fatal_err(format!("Error opening Debian package {}", source.display()))?;
+        let (name, size) = find_data_member(&mut package)
+            .map_err(|x| InstallError::Fatal(format!("Error reading Debian package: {}", x)))?;
+
+        let data = package.take(size);
+        let stream: Box<dyn Read> = match name.strip_prefix("data.tar") {
+            Some(".gz") => Box::new(flate2::read::GzDecoder::new(data)),
+            Some(".xz") => Box::new(xz2::read::XzDecoder::new(data)),
+            Some(".bz2") => Box::new(bzip2::read::BzDecoder::new(data)),
+            Some(".zst") => Box::new(
+                zstd::stream::read::Decoder::new(data)
+                    .map_fatal_err(format!("Error reading {} of Debian package", name))?,
+            ),
+            Some("") => Box::new(data),
+            _ => {
+                return Err(InstallError::Fatal(format!(
+                    "Unsupported compression of {} in Debian package",
+                    name
+                )));
+            }
+        };
+
+        let mut archive = tar::Archive::new(stream);
+        let entries = archive
+            .entries()
+            .map_fatal_err("Error reading Debian package data".into())?;
+        for entry in entries {
+            let mut entry = entry.map_fatal_err("Error reading Debian package data".into())?;
+            let path = entry
+                .path()
+                .map_fatal_err("Error reading Debian package data".into())?
+                .into_owned();
+            let is_bin = path
+                .parent()
+                .and_then(|x| x.file_name())
+                .is_some_and(|x| x == "bin" || x == "sbin");
+            if !is_bin || !entry.header().entry_type().is_file() {
+                continue;
+            }
+            if let Some(name) = path.file_name() {
+                entry
+                    .unpack(temp_dir.join(name))
+                    .map_fatal_err(format!("Error extracting {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+const AR_MAGIC: &[u8; 8] = b"!<arch>\n";
+const AR_HEADER_SIZE: usize = 60;
+
+/// Move the reader to the content of the `data.tar.*` member of the `ar` archive, returning its
+/// name and size
+// https://manpages.debian.org/bookworm/dpkg-dev/deb.5.en.html
+fn find_data_member<R: Read>(reader: &mut R) -> std::io::Result<(String, u64)> {
+    let invalid = |message: &str| std::io::Error::new(ErrorKind::InvalidData, message);
+
+    let mut magic = [0u8; AR_MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if &magic != AR_MAGIC {
+        return Err(invalid("not an ar archive"));
+    }
+
+    loop {
+        let mut header = [0u8; AR_HEADER_SIZE];
+        match reader.read_exact(&mut header) {
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                return Err(invalid("data archive not found"));
+            }
+            result => result?,
+        }
+        let name = String::from_utf8_lossy(&header[0..16])
+            .trim_end()
+            .trim_end_matches('/')
+            .to_string();
+        let size = String::from_utf8_lossy(&header[48..58])
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| invalid("invalid member size"))?;
+
+        if name.starts_with("data.tar") {
+            return Ok((name, size));
+        }
+        // Members are aligned to an even offset
+        std::io::copy(
+            &mut reader.by_ref().take(size + size % 2),
+            &mut std::io::sink(),
+        )?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_member_found() {
+        let package = ar_archive(&[
+            ("debian-binary", b"2.0\n"),
+            ("control.tar.xz", b"odd"),
+            ("data.tar.zst", b"data"),
+        ]);
+        let mut reader = package.as_slice();
+
+        let result = find_data_member(&mut reader).unwrap();
+
+        assert_eq!(("data.tar.zst".to_string(), 4), result);
+        assert_eq!(b"data", reader);
+    }
+
+    #[test]
+    fn data_member_not_found() {
+        let package = ar_archive(&[("debian-binary", b"2.0\n")]);
+
+        let result = find_data_member(&mut package.as_slice());
+
+        assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+    }
+
+    #[test]
+    fn not_an_ar_archive() {
+        let result = find_data_member(&mut b"PK\x03\x04 not a deb".as_slice());
+
+        assert_eq!(ErrorKind::InvalidData, result.unwrap_err().kind());
+    }
+
+    fn ar_archive(members: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut archive = AR_MAGIC.to_vec();
+        for (name, content) in members {
+            let header = format!(
+                "{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+                format!("{}/", name),
+                0,
+                0,
+                0,
+                100644,
+                content.len()
+            );
+            archive.extend_from_slice(header.as_bytes());
+            archive.extend_from_slice(content);
+            if content.len() % 2 == 1 {
+                archive.push(b'\n');
+            }
+        }
+        archive
+    }
 }
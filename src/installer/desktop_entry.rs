@@ -0,0 +1,129 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use walkdir::WalkDir;
+
+use crate::env_var;
+use crate::installer::command::exec_command;
+use crate::installer::error::{InstallError, InstallErrorMapErr};
+use crate::installer::result::{InstallOutput, InstallerResult};
+
+/// Extract the desktop entry of an installed AppImage and add it to the applications of the user
+/// (`$XDG_DATA_HOME/applications`, default value is `~/.local/share/applications`), so that it
+/// can be launched from the desktop environment
+pub fn install_desktop_entry(appimage: &Path) -> InstallerResult {
+    let appimage = std::fs::canonicalize(appimage)
+        .map_fatal_err(format!("Error reading {}", appimage.display()))?;
+    let temp_dir =
+        crate::temp_file::make_temp_dir().map_fatal_err("Error creating temp dir".into())?;
+
+    let result = extract_desktop_entry(&appimage, &temp_dir)
+        .and_then(|entry| entry.map(|x| copy_desktop_entry(&x, &appimage)).transpose());
+    let _ = std::fs::remove_dir_all(&temp_dir);
+
+    let message = match result? {
+        Some(path) => format!("Installed desktop entry to '{}'", path.display()),
+        None => "No desktop entry found".to_string(),
+    };
+    Ok(InstallOutput::new(message))
+}
+
+/// AppImages extract their own content to `squashfs-root` in the current directory
+fn extract_desktop_entry(
+    appimage: &Path,
+    directory: &Path,
+) -> Result<Option<PathBuf>, InstallError> {
+    let name = appimage.display().to_string();
+    exec_command(
+        &name,
+        Command::new(appimage)
+            .arg("--appimage-extract")
+            .arg("*.desktop")
+            .current_dir(directory),
+    )?;
+
+    Ok(WalkDir::new(directory.join("squashfs-root"))
+        .max_depth(1)
+        .into_iter()
+        .filter_map(|x| x.ok())
+        .find(|x| x.file_type().is_file() && x.path().extension().is_some_and(|x| x == "desktop"))
+        .map(|x| x.into_path()))
+}
+
+fn copy_desktop_entry(entry: &Path, appimage: &Path) -> Result<PathBuf, InstallError> {
+    let content = std::fs::read_to_string(entry)
+        .map_fatal_err(format!("Error reading {}", entry.display()))?;
+
+    let applications = applications_directory().ok_or_else(|| {
+        InstallError::Fatal("Cannot find the applications directory, HOME is not set".into())
+    })?;
+    std::fs::create_dir_all(&applications)
+        .map_fatal_err(format!("Error creating {}", applications.display()))?;
+
+    let file_name = appimage.file_stem().unwrap_or(appimage.as_os_str());
+    let destination = applications.join(file_name).with_extension("desktop");
+    std::fs::write(&destination, with_executable(&content, appimage))
+        .map_fatal_err(format!("Error writing {}", destination.display()))?;
+    Ok(destination)
+}
+
+/// The program of `Exec` and `TryExec` keys is the AppImage, keeping the arguments (e.g. `%U`)
+fn with_executable(content: &str, appimage: &Path) -> String {
+    let appimage = appimage.display().to_string();
+    content
+        .lines()
+        .map(|line| match line.split_once('=') {
+            Some(("Exec", command)) => {
+                let arguments = command.split_once(' ').map(|(_, x)| x);
+                match arguments {
+                    Some(arguments) => format!("Exec={} {}", quoted(&appimage), arguments),
+                    None => format!("Exec={}", quoted(&appimage)),
+                }
+            }
+            Some(("TryExec", _)) => format!("TryExec={}", appimage),
+            _ => line.to_string(),
+        })
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+// https://specifications.freedesktop.org/desktop-entry-spec/latest/exec-variables.html
+fn quoted(path: &str) -> String {
+    if path.contains(' ') {
+        format!("\"{}\"", path)
+    } else {
+        path.to_string()
+    }
+}
+
+fn applications_directory() -> Option<PathBuf> {
+    env_var::string("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            env_var::string("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+        })
+        .map(|x| x.join("applications"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("Exec=AppRun %U", "Exec=/opt/apps/tool.AppImage %U"; "with arguments")]
+    #[test_case("Exec=tool", "Exec=/opt/apps/tool.AppImage"; "without arguments")]
+    #[test_case("TryExec=tool", "TryExec=/opt/apps/tool.AppImage"; "try exec")]
+    #[test_case("Name=Tool", "Name=Tool"; "other keys")]
+    fn executable_of_desktop_entry(line: &str, expected: &str) {
+        let result = with_executable(line, Path::new("/opt/apps/tool.AppImage"));
+
+        assert_eq!(format!("{}\n", expected), result);
+    }
+
+    #[test]
+    fn executable_path_with_spaces() {
+        let result = with_executable("Exec=AppRun %F", Path::new("/opt/my apps/tool.AppImage"));
+
+        assert_eq!("Exec=\"/opt/my apps/tool.AppImage\" %F\n", result);
+    }
+}
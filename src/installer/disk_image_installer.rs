@@ -0,0 +1,149 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::installer::archive_installer::ArchiveInstaller;
+use crate::installer::command::exec_command;
+use crate::installer::destination::Destination;
+use crate::installer::error::{InstallError, InstallErrorMapErr};
+use crate::installer::executable::Executable;
+use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
+use crate::installer::quarantine::remove_quarantine;
+use crate::installer::result::{InstallOutput, InstallerResult};
+
+const HDIUTIL: &str = "hdiutil";
+const DITTO: &str = "ditto";
+
+pub struct DiskImageInstaller;
+
+impl DiskImageInstaller {
+    /// Applications (`.app` bundles) are copied as they are, otherwise the executables of the
+    /// disk image are installed like the ones of an archive
+    pub fn run(
+        file_info: SupportedFileInfo,
+        destination: Destination,
+        executables: Vec<Executable>,
+        options: &InstallOptions,
+    ) -> InstallerResult {
+        if !cfg!(target_os = "macos") {
+            return Err(InstallError::NotSupported(format!(
+                "{} is a disk image, which can only be installed on macOS",
+                file_info.name
+            )));
+        }
+
+        let mountpoint =
+            crate::temp_file::make_temp_dir().map_fatal_err("Error creating temp dir".into())?;
+        Self::attach(&file_info.path, &mountpoint)?;
+
+        let result = match find_app_bundle(&mountpoint) {
+            Some(app) => Self::copy_app_bundle(&app, destination, options),
+            None => ArchiveInstaller::run(
+                |_, temp_dir| copy(&mountpoint, temp_dir),
+                file_info,
+                destination,
+                executables,
+                options,
+            ),
+        };
+
+        let detached = Self::detach(&mountpoint);
+        let _ = std::fs::remove_dir(&mountpoint);
+        let output = result?;
+        detached.map(|_| output)
+    }
+
+    fn attach(source: &Path, mountpoint: &Path) -> Result<(), InstallError> {
+        exec_command(
+            HDIUTIL,
+            Command::new(HDIUTIL)
+                .arg("attach")
+                .arg("-nobrowse")
+                .arg("-readonly")
+                .arg("-noautoopen")
+                .arg("-mountpoint")
+                .arg(mountpoint)
+                .arg(source),
+        )
+    }
+
+    fn detach(mountpoint: &Path) -> Result<(), InstallError> {
+        exec_command(
+            HDIUTIL,
+            Command::new(HDIUTIL)
+                .arg("detach")
+                .arg("-quiet")
+                .arg(mountpoint),
+        )
+    }
+
+    fn copy_app_bundle(
+        app: &Path,
+        destination: Destination,
+        options: &InstallOptions,
+    ) -> InstallerResult {
+        let app_path = match destination {
+            Destination::Directory(dir) => dir.join(app.file_name().unwrap_or(app.as_os_str())),
+            Destination::File(file) => file,
+        };
+        copy(app, &app_path)?;
+        if options.remove_quarantine {
+            remove_quarantine(&app_path);
+        }
+        Ok(InstallOutput::new(format!(
+            "Copied application to '{}'",
+            app_path.display()
+        )))
+    }
+}
+
+/// `ditto` preserves the permissions, symlinks and extended attributes of application bundles
+fn copy(source: &Path, destination: &Path) -> Result<(), InstallError> {
+    exec_command(DITTO, Command::new(DITTO).arg(source).arg(destination))
+}
+
+/// Disk images usually have the application and a symlink to `/Applications` at the top level
+fn find_app_bundle(directory: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(directory)
+        .ok()?
+        .filter_map(|x| x.ok())
+        .map(|x| x.path())
+        .find(|x| {
+            let is_app = x.extension().is_some_and(|x| x == "app");
+            let is_directory = x.symlink_metadata().is_ok_and(|x| x.is_dir());
+            is_app && is_directory
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_bundle_found() {
+        let dir = temp_dir("app_bundle_found");
+        std::fs::create_dir_all(dir.join("Tool.app").join("Contents")).unwrap();
+        std::fs::write(dir.join("README.txt"), "").unwrap();
+
+        let result = find_app_bundle(&dir);
+
+        assert_eq!(Some(dir.join("Tool.app")), result);
+    }
+
+    #[test]
+    fn app_bundle_not_found() {
+        let dir = temp_dir("app_bundle_not_found");
+        std::fs::write(dir.join("tool"), "").unwrap();
+
+        let result = find_app_bundle(&dir);
+
+        assert_eq!(None, result);
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("dra-disk-image-tests").join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+}
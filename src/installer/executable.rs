@@ -5,6 +5,8 @@ use std::path::Path;
 pub enum Executable {
     Automatic(String),
     Selected(String),
+    /// Every executable of an archive
+    All,
 }
 
 impl Executable {
@@ -12,6 +14,7 @@ impl Executable {
         match self {
             Executable::Automatic(name) => name.clone(),
             Executable::Selected(name) => name.clone(),
+            Executable::All => "all executables".to_string(),
         }
     }
 }
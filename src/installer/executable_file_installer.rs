@@ -2,6 +2,7 @@ use crate::installer::destination::Destination;
 use crate::installer::error::InstallErrorMapErr;
 use crate::installer::executable::{Executable, set_executable_permissions};
 use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
 use crate::installer::result::{InstallOutput, InstallerResult};
 
 pub struct ExecutableFileInstaller;
@@ -11,6 +12,7 @@ impl ExecutableFileInstaller {
         file_info: SupportedFileInfo,
         destination: Destination,
         _executables: Vec<Executable>,
+        _options: &InstallOptions,
     ) -> InstallerResult {
         let executable_path = match destination {
             Destination::Directory(dir) => dir.join(file_info.name),
@@ -25,9 +27,9 @@ impl ExecutableFileInstaller {
 
         set_executable_permissions(&executable_path)?;
 
-        Ok(InstallOutput::new(format!(
-            "Extracted executable to '{}'",
-            executable_path.display()
-        )))
+        Ok(InstallOutput::with_executables(
+            format!("Extracted executable to '{}'", executable_path.display()),
+            vec![executable_path],
+        ))
     }
 }
@@ -1,5 +1,5 @@
 use std::fmt::{Display, Formatter};
-use std::io::Read;
+use std::io::{BufRead, Read};
 use std::path::{Path, PathBuf};
 
 use crate::installer::error::InstallError;
@@ -9,17 +9,24 @@ pub enum Compression {
     Gz,
     Xz,
     Bz2,
+    Zst,
 }
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum FileType {
     Debian,
     Rpm,
+    AlpinePackage,
+    ArchPackage,
+    WindowsInstaller,
     TarArchive(Compression),
     ZipArchive,
     SevenZipArchive,
+    DiskImage,
     CompressedFile(Compression),
     ExecutableFile,
+    AppImage,
+    ShellScript,
 }
 
 #[derive(Debug)]
@@ -54,6 +61,17 @@ fn file_type_for(file: &FileInfo) -> Option<FileType> {
     if file_name.ends_with(".rpm") {
         return Some(FileType::Rpm);
     }
+    if file_name.ends_with(".msi") {
+        return Some(FileType::WindowsInstaller);
+    }
+    // Arch packages are tar archives too, installed by pacman instead of extracted
+    if file_name.ends_with(".pkg.tar.zst") || file_name.ends_with(".pkg.tar.xz") {
+        return Some(FileType::ArchPackage);
+    }
+    // Android packages have the same extension, but they are zip files
+    if file_name.ends_with(".apk") {
+        return is_gzip_file(&file.path).then_some(FileType::AlpinePackage);
+    }
     if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
         return Some(FileType::TarArchive(Compression::Gz));
     }
@@ -72,15 +90,29 @@ fn file_type_for(file: &FileInfo) -> Option<FileType> {
     if file_name.ends_with(".xz") || file_name.ends_with(".txz") {
         return Some(FileType::CompressedFile(Compression::Xz));
     }
+    if file_name.ends_with(".tar.zst") || file_name.ends_with(".tzst") {
+        return Some(FileType::TarArchive(Compression::Zst));
+    }
+    if file_name.ends_with(".zst") {
+        return Some(FileType::CompressedFile(Compression::Zst));
+    }
     if file_name.ends_with(".zip") {
         return Some(FileType::ZipArchive);
     }
     if file_name.ends_with(".7z") {
         return Some(FileType::SevenZipArchive);
     }
+    if file_name.ends_with(".dmg") {
+        return Some(FileType::DiskImage);
+    }
+    if (file_name.ends_with(".sh") || file_name.ends_with(".run")) && is_shell_script(&file.path) {
+        return Some(FileType::ShellScript);
+    }
+    if file_name.ends_with(".appimage") {
+        return Some(FileType::AppImage);
+    }
     if is_elf_file(&file.path)
         || Path::new(&file_name).extension().is_none()
-        || file_name.ends_with(".appimage")
         || file_name.ends_with(".exe")
     {
         return Some(FileType::ExecutableFile);
@@ -105,12 +137,60 @@ fn check_elf_file(path: &Path) -> std::io::Result<bool> {
     Ok(header == ELF_MAGIC_NUMBER)
 }
 
+fn is_gzip_file(path: &Path) -> bool {
+    check_gzip_file(path).unwrap_or(false)
+}
+
+// https://www.rfc-editor.org/rfc/rfc1952#page-6
+const GZIP_MAGIC_NUMBER: [u8; 2] = [0x1F, 0x8B];
+
+fn check_gzip_file(path: &Path) -> std::io::Result<bool> {
+    let mut file = std::fs::File::open(path)?;
+    let mut header = [0u8; 2];
+
+    file.read_exact(&mut header)?;
+
+    Ok(header == GZIP_MAGIC_NUMBER)
+}
+
+fn is_shell_script(path: &Path) -> bool {
+    check_shell_script(path).unwrap_or(false)
+}
+
+const SHELLS: [&str; 4] = ["sh", "bash", "zsh", "ksh"];
+
+fn check_shell_script(path: &Path) -> std::io::Result<bool> {
+    let file = std::fs::File::open(path)?;
+    let mut first_line = String::new();
+    std::io::BufReader::new(file).read_line(&mut first_line)?;
+
+    let is_shell = first_line
+        .strip_prefix("#!")
+        .and_then(shebang_interpreter)
+        .map(|interpreter| SHELLS.contains(&interpreter))
+        .unwrap_or(false);
+
+    Ok(is_shell)
+}
+
+/// Returns the interpreter name of a shebang line (e.g. `/bin/sh -e` or `/usr/bin/env bash`)
+fn shebang_interpreter(shebang: &str) -> Option<&str> {
+    let mut words = shebang.split_whitespace();
+    let program = words.next().map(|x| x.rsplit('/').next().unwrap_or(x))?;
+    if program == "env" {
+        words.next()
+    } else {
+        Some(program)
+    }
+}
+
 impl Display for Compression {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match *self {
             Compression::Gz => f.write_str("gz"),
             Compression::Xz => f.write_str("xz"),
             Compression::Bz2 => f.write_str("bz2"),
+            Compression::Zst => f.write_str("zst"),
         }
     }
 }
@@ -131,7 +211,8 @@ mod tests {
     use test_case::test_case;
 
     use super::{
-        Compression, ELF_MAGIC_NUMBER, FileInfo, FileType, SupportedFileInfo, validate_file,
+        Compression, ELF_MAGIC_NUMBER, FileInfo, FileType, GZIP_MAGIC_NUMBER, SupportedFileInfo,
+        shebang_interpreter, validate_file,
     };
     use crate::installer::error::InstallError;
 
@@ -145,12 +226,19 @@ mod tests {
     #[test_case("file.tar.xz", FileType::TarArchive(Compression::Xz))]
     #[test_case("file.txz", FileType::TarArchive(Compression::Xz))]
     #[test_case("file.xz", FileType::CompressedFile(Compression::Xz))]
+    #[test_case("file.tar.zst", FileType::TarArchive(Compression::Zst))]
+    #[test_case("file.tzst", FileType::TarArchive(Compression::Zst))]
+    #[test_case("file.zst", FileType::CompressedFile(Compression::Zst))]
     #[test_case("file.zip", FileType::ZipArchive)]
     #[test_case("file.exe", FileType::ExecutableFile)]
     #[test_case("file", FileType::ExecutableFile)]
-    #[test_case("file.AppImage", FileType::ExecutableFile)]
+    #[test_case("file.AppImage", FileType::AppImage)]
     #[test_case("file.7z", FileType::SevenZipArchive)]
+    #[test_case("file.dmg", FileType::DiskImage)]
     #[test_case("file.rpm", FileType::Rpm)]
+    #[test_case("file-1.0.0-1-x86_64.pkg.tar.zst", FileType::ArchPackage)]
+    #[test_case("file-x86_64.msi", FileType::WindowsInstaller)]
+    #[test_case("file-1.0.0-1-x86_64.pkg.tar.xz", FileType::ArchPackage)]
     fn supported_file(file_name: &str, expected_file_type: FileType) {
         let file_info = any_file_info(file_name);
         let result = validate_file(file_info);
@@ -167,7 +255,44 @@ mod tests {
         assert_ok_equal(FileType::ExecutableFile, result);
     }
 
+    #[test_case("install.sh", "#!/bin/sh\necho payload"; "sh")]
+    #[test_case("install.run", "#!/usr/bin/env bash\necho payload"; "env bash")]
+    fn supported_shell_script(file_name: &str, content: &str) {
+        let file_info = create_file(file_name, content.as_bytes());
+
+        let result = validate_file(file_info);
+
+        assert_ok_equal(FileType::ShellScript, result);
+    }
+
+    #[test]
+    fn supported_alpine_package() {
+        let file_info = create_file("tool-1.0.0-r0.apk", &GZIP_MAGIC_NUMBER);
+
+        let result = validate_file(file_info);
+
+        assert_ok_equal(FileType::AlpinePackage, result);
+    }
+
+    #[test]
+    fn android_package_not_supported() {
+        let file_info = create_file("app-release.apk", b"PK\x03\x04");
+
+        let result = validate_file(file_info);
+
+        assert_not_supported(result);
+    }
+
+    #[test_case("/bin/sh", Some("sh"))]
+    #[test_case("/bin/bash -e", Some("bash"))]
+    #[test_case("/usr/bin/env zsh", Some("zsh"))]
+    #[test_case("", None)]
+    fn shebang_interpreter_name(shebang: &str, expected: Option<&str>) {
+        assert_eq!(expected, shebang_interpreter(shebang));
+    }
+
     #[test_case("file.txt")]
+    #[test_case("file.sh"; "shell script without shebang")]
     fn not_supported(file_name: &str) {
         let file_info = any_file_info(file_name);
         let result = validate_file(file_info);
@@ -185,13 +310,17 @@ mod tests {
     }
 
     fn create_elf_file(file_name: &str) -> FileInfo {
+        create_file(file_name, &ELF_MAGIC_NUMBER)
+    }
+
+    fn create_file(file_name: &str, content: &[u8]) -> FileInfo {
         let temp_dir = std::env::temp_dir().join("dra-file-tests");
         std::fs::create_dir_all(&temp_dir).unwrap();
 
         let path = temp_dir.join(file_name);
 
         let mut file = std::fs::File::create(&path).unwrap();
-        file.write_all(&ELF_MAGIC_NUMBER).unwrap();
+        file.write_all(content).unwrap();
 
         FileInfo {
             path,
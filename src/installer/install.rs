@@ -1,27 +1,53 @@
+use crate::installer::alpine_installer::AlpineInstaller;
+use crate::installer::appimage_installer::AppImageInstaller;
+use crate::installer::arch_installer::ArchInstaller;
 use crate::installer::compressed_file_installer::CompressedFileInstaller;
 use crate::installer::debian_installer::DebianInstaller;
 use crate::installer::destination::Destination;
+use crate::installer::disk_image_installer::DiskImageInstaller;
 use crate::installer::error::InstallError;
 use crate::installer::executable::Executable;
 use crate::installer::executable_file_installer::ExecutableFileInstaller;
 use crate::installer::file::{Compression, FileInfo, FileType, SupportedFileInfo, validate_file};
+use crate::installer::msi_installer::MsiInstaller;
+use crate::installer::options::InstallOptions;
+use crate::installer::quarantine::remove_quarantine;
 use crate::installer::result::InstallerResult;
 use crate::installer::rpm_installer::RpmInstaller;
 use crate::installer::seven_zip_archive_installer::SevenZipArchiveInstaller;
+use crate::installer::shell_script_installer::ShellScriptInstaller;
 use crate::installer::tar_archive_installer::TarArchiveInstaller;
 use crate::installer::zip_archive_installer::ZipArchiveInstaller;
+use log::{debug, info};
 use std::path::Path;
+use std::sync::Mutex;
+
+/// System package managers lock their database, so concurrent installations (`--parallel-install`)
+/// of packages run one at a time
+static PACKAGE_MANAGER: Mutex<()> = Mutex::new(());
 
 pub fn install(
     asset_name: String,
     source: &Path,
     destination: Destination,
     executables: Vec<Executable>,
+    options: &InstallOptions,
 ) -> InstallerResult {
     let file_info = file_info_from(&asset_name, source).and_then(validate_file)?;
+    debug!("{} file type is {:?}", file_info.name, file_info.file_type);
+    info!("Installing {} to {:?}", file_info.name, destination);
     let installer = find_installer_for(&file_info.file_type);
 
-    installer(file_info, destination, executables)
+    let _package_manager = is_system_package(&file_info.file_type)
+        .then(|| PACKAGE_MANAGER.lock().unwrap_or_else(|e| e.into_inner()));
+    let output = installer(file_info, destination, executables, options)?;
+    if options.remove_quarantine {
+        output
+            .executables()
+            .iter()
+            .for_each(|x| remove_quarantine(x));
+    }
+    Ok(output)
 }
 
 fn file_info_from(name: &str, path: &Path) -> Result<FileInfo, InstallError> {
@@ -31,20 +57,54 @@ fn file_info_from(name: &str, path: &Path) -> Result<FileInfo, InstallError> {
     Ok(FileInfo::new(name, path))
 }
 
+fn is_system_package(file_type: &FileType) -> bool {
+    matches!(
+        file_type,
+        FileType::Debian
+            | FileType::Rpm
+            | FileType::AlpinePackage
+            | FileType::ArchPackage
+            | FileType::WindowsInstaller
+    )
+}
+
 fn find_installer_for(
     file_type: &FileType,
-) -> fn(SupportedFileInfo, Destination, Vec<Executable>) -> InstallerResult {
+) -> fn(SupportedFileInfo, Destination, Vec<Executable>, &InstallOptions) -> InstallerResult {
     match file_type {
         FileType::Debian => DebianInstaller::run,
         FileType::Rpm => RpmInstaller::run,
+        FileType::AlpinePackage => AlpineInstaller::run,
+        FileType::ArchPackage => ArchInstaller::run,
+        FileType::WindowsInstaller => MsiInstaller::run,
         FileType::TarArchive(Compression::Gz) => TarArchiveInstaller::gz,
         FileType::TarArchive(Compression::Xz) => TarArchiveInstaller::xz,
         FileType::TarArchive(Compression::Bz2) => TarArchiveInstaller::bz2,
+        FileType::TarArchive(Compression::Zst) => TarArchiveInstaller::zst,
         FileType::ZipArchive => ZipArchiveInstaller::run,
         FileType::SevenZipArchive => SevenZipArchiveInstaller::run,
+        FileType::DiskImage => DiskImageInstaller::run,
         FileType::CompressedFile(Compression::Gz) => CompressedFileInstaller::gz,
         FileType::CompressedFile(Compression::Xz) => CompressedFileInstaller::xz,
         FileType::CompressedFile(Compression::Bz2) => CompressedFileInstaller::bz2,
+        FileType::CompressedFile(Compression::Zst) => CompressedFileInstaller::zst,
         FileType::ExecutableFile => ExecutableFileInstaller::run,
+        FileType::AppImage => AppImageInstaller::run,
+        FileType::ShellScript => ShellScriptInstaller::run,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(FileType::Debian, true; "debian")]
+    #[test_case(FileType::Rpm, true; "rpm")]
+    #[test_case(FileType::ArchPackage, true; "arch")]
+    #[test_case(FileType::TarArchive(Compression::Gz), false; "tar archive")]
+    #[test_case(FileType::ExecutableFile, false; "executable")]
+    fn system_package(file_type: FileType, expected: bool) {
+        assert_eq!(expected, is_system_package(&file_type));
     }
 }
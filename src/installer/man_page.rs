@@ -0,0 +1,128 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use walkdir::WalkDir;
+
+use crate::env_var;
+use crate::installer::error::{InstallError, InstallErrorMapErr};
+use crate::installer::result::{InstallOutput, InstallerResult};
+
+const DEFAULT_MAN_DIRECTORY: &str = "/usr/local/share/man";
+const MANDB: &str = "mandb";
+
+/// Copy the man pages found in `directory` to the `man<N>` directories of the first `$MANPATH` entry
+/// (or `/usr/local/share/man`) and update the man database when `mandb` is available.
+pub fn install_man_pages(directory: &Path) -> InstallerResult {
+    let man_directory = man_directory(env_var::string("MANPATH"));
+
+    let installed = find_man_pages(directory)
+        .into_iter()
+        .map(|(path, section)| copy_man_page(&path, section, &man_directory))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if installed.is_empty() {
+        return Ok(InstallOutput::new("No man pages found".to_string()));
+    }
+
+    // NOTE: mandb is not available on every system (e.g. macOS), so failures are ignored
+    let _ = Command::new(MANDB)
+        .arg("--quiet")
+        .arg(&man_directory)
+        .output();
+
+    let message = installed
+        .iter()
+        .map(|x| format!("Installed man page to '{}'", x.display()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(InstallOutput::new(message))
+}
+
+fn find_man_pages(directory: &Path) -> Vec<(PathBuf, u8)> {
+    WalkDir::new(directory)
+        .max_depth(5)
+        .into_iter()
+        .filter_map(|x| x.ok())
+        .filter(|x| x.file_type().is_file())
+        .filter_map(|x| {
+            let name = x.file_name().to_str()?;
+            let section = man_page_section(name, &read_header(x.path()))?;
+            Some((x.path().to_path_buf(), section))
+        })
+        .collect()
+}
+
+fn copy_man_page(path: &Path, section: u8, man_directory: &Path) -> Result<PathBuf, InstallError> {
+    let section_directory = man_directory.join(format!("man{}", section));
+    std::fs::create_dir_all(&section_directory)
+        .map_fatal_err(format!("Error creating {}", section_directory.display()))?;
+
+    let file_name = path.file_name().unwrap_or(path.as_os_str());
+    let destination = section_directory.join(file_name);
+    std::fs::copy(path, &destination).map_fatal_err(format!(
+        "Error copying {} to {}",
+        path.display(),
+        destination.display()
+    ))?;
+    Ok(destination)
+}
+
+fn read_header(path: &Path) -> Vec<u8> {
+    let mut header = vec![];
+    let _ = std::fs::File::open(path).and_then(|x| x.take(2).read_to_end(&mut header));
+    header
+}
+
+/// A man page has a section number (1-8) as extension and its content is written in roff,
+/// where every line starts with a request (`.TH`) or a comment (`.\"` or `'\"`)
+fn man_page_section(file_name: &str, header: &[u8]) -> Option<u8> {
+    let (_, extension) = file_name.rsplit_once('.')?;
+    let section = match extension.as_bytes() {
+        [x @ b'1'..=b'8'] => x - b'0',
+        _ => return None,
+    };
+    match header.first() {
+        Some(b'.') | Some(b'\'') => Some(section),
+        _ => None,
+    }
+}
+
+fn man_directory(manpath: Option<String>) -> PathBuf {
+    manpath
+        .and_then(|x| std::env::split_paths(&x).find(|path| !path.as_os_str().is_empty()))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_MAN_DIRECTORY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("tool.1", Some(1); "section 1")]
+    #[test_case("tool.conf.5", Some(5); "section 5")]
+    #[test_case("tool.8", Some(8); "section 8")]
+    #[test_case("tool.9", None; "not a section")]
+    #[test_case("tool.12", None; "two digits")]
+    #[test_case("tool", None; "no extension")]
+    fn section_from_file_name(file_name: &str, expected: Option<u8>) {
+        assert_eq!(expected, man_page_section(file_name, b".TH"));
+    }
+
+    #[test]
+    fn not_a_roff_file() {
+        assert_eq!(None, man_page_section("libtool.so.1", &[0x7F, b'E']));
+    }
+
+    #[test]
+    fn first_manpath_entry() {
+        let result = man_directory(Some("/opt/man:/usr/share/man".to_string()));
+
+        assert_eq!(PathBuf::from("/opt/man"), result);
+    }
+
+    #[test]
+    fn default_man_directory() {
+        assert_eq!(PathBuf::from(DEFAULT_MAN_DIRECTORY), man_directory(None));
+    }
+}
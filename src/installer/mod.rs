@@ -1,17 +1,29 @@
+mod alpine_installer;
+mod appimage_installer;
+mod arch_installer;
 mod archive_installer;
 mod command;
 mod compressed_file_installer;
 mod debian_installer;
+mod desktop_entry;
 pub mod destination;
+mod disk_image_installer;
 pub mod error;
 pub mod executable;
 mod executable_file_installer;
 mod file;
 mod install;
+mod man_page;
+mod msi_installer;
+pub mod options;
+mod quarantine;
 mod result;
 mod rpm_installer;
 mod seven_zip_archive_installer;
+mod shell_script_installer;
+mod symlink;
 mod tar_archive_installer;
 mod zip_archive_installer;
 
 pub use install::install;
+pub use symlink::create_symlink;
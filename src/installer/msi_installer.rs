@@ -0,0 +1,48 @@
+use std::process::Command;
+
+use crate::installer::command::exec_command;
+use crate::installer::destination::Destination;
+use crate::installer::error::InstallError;
+use crate::installer::executable::Executable;
+use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
+use crate::installer::result::{InstallOutput, InstallerResult};
+use log::debug;
+
+const MSIEXEC: &str = "msiexec";
+
+pub struct MsiInstaller;
+
+impl MsiInstaller {
+    pub fn run(
+        file_info: SupportedFileInfo,
+        _destination: Destination,
+        _executables: Vec<Executable>,
+        _options: &InstallOptions,
+    ) -> InstallerResult {
+        if !cfg!(windows) {
+            return Err(InstallError::NotSupported(format!(
+                "{} is a Windows Installer package, which can only be installed on Windows",
+                file_info.name
+            )));
+        }
+
+        // Some packages can't be installed without their user interface (e.g. they ask for
+        // elevated privileges or options), so they are installed interactively
+        exec_command(
+            MSIEXEC,
+            Command::new(MSIEXEC)
+                .arg("/i")
+                .arg(&file_info.path)
+                .arg("/qn"),
+        )
+        .or_else(|e| {
+            debug!("Quiet install failed, installing interactively: {:?}", e);
+            exec_command(
+                MSIEXEC,
+                Command::new(MSIEXEC).arg("/i").arg(&file_info.path),
+            )
+        })
+        .map(|_| InstallOutput::new(format!("MSI package '{}' installed", file_info.name)))
+    }
+}
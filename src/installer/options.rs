@@ -0,0 +1,10 @@
+#[derive(Debug, Clone, Default)]
+pub struct InstallOptions {
+    pub allow_shell_scripts: bool,
+    pub self_extract_args: Vec<String>,
+    pub install_man_pages: bool,
+    pub allow_untrusted_packages: bool,
+    pub desktop_entry: bool,
+    pub remove_quarantine: bool,
+    pub extract_deb: bool,
+}
@@ -0,0 +1,23 @@
+use std::path::Path;
+use std::process::Command;
+
+use log::debug;
+
+const XATTR: &str = "xattr";
+const QUARANTINE_ATTRIBUTE: &str = "com.apple.quarantine";
+
+/// Gatekeeper blocks the files downloaded from the internet, which have the quarantine attribute.
+/// Application bundles have the attribute on every file, so it is removed recursively
+pub fn remove_quarantine(path: &Path) {
+    if !cfg!(target_os = "macos") {
+        return;
+    }
+    // NOTE: xattr fails when the attribute is missing, so failures are ignored
+    let result = Command::new(XATTR)
+        .arg("-d")
+        .arg("-r")
+        .arg(QUARANTINE_ATTRIBUTE)
+        .arg(path)
+        .output();
+    debug!("Removed quarantine attribute of {:?}: {:?}", path, result);
+}
@@ -1,18 +1,34 @@
 use crate::installer::error::InstallError;
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 
 #[derive(Debug)]
-pub struct InstallOutput(String);
+pub struct InstallOutput {
+    message: String,
+    executables: Vec<PathBuf>,
+}
 
 impl InstallOutput {
     pub fn new(message: String) -> Self {
-        Self(message)
+        Self::with_executables(message, vec![])
+    }
+
+    pub fn with_executables(message: String, executables: Vec<PathBuf>) -> Self {
+        Self {
+            message,
+            executables,
+        }
+    }
+
+    /// Paths of the installed executables, when the installer knows them
+    pub fn executables(&self) -> &[PathBuf] {
+        &self.executables
     }
 }
 
 impl Display for InstallOutput {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.0)
+        f.write_str(&self.message)
     }
 }
 
@@ -1,12 +1,14 @@
 use std::process::Command;
 
-use crate::installer::command::exec_command;
+use crate::installer::command::{exec_command, find_command};
 use crate::installer::destination::Destination;
 use crate::installer::executable::Executable;
 use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
 use crate::installer::result::{InstallOutput, InstallerResult};
 
 const RPM: &str = "rpm";
+const DNF: &str = "dnf";
 
 pub struct RpmInstaller;
 
@@ -15,14 +17,25 @@ impl RpmInstaller {
         file_info: SupportedFileInfo,
         _destination: Destination,
         _executables: Vec<Executable>,
+        _options: &InstallOptions,
     ) -> InstallerResult {
-        exec_command(
-            RPM,
-            Command::new(RPM)
-                .arg("--install")
-                .arg("--replacepkgs")
-                .arg(file_info.path),
-        )
-        .map(|_| InstallOutput::new(format!("RPM package '{}' installed", file_info.name)))
+        // dnf also installs the missing dependencies of the package
+        let result = match find_command(&[DNF]) {
+            Some(dnf) => exec_command(
+                dnf,
+                Command::new(dnf)
+                    .arg("install")
+                    .arg("--assumeyes")
+                    .arg(file_info.path),
+            ),
+            None => exec_command(
+                RPM,
+                Command::new(RPM)
+                    .arg("--install")
+                    .arg("--replacepkgs")
+                    .arg(file_info.path),
+            ),
+        };
+        result.map(|_| InstallOutput::new(format!("RPM package '{}' installed", file_info.name)))
     }
 }
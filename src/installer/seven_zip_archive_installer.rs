@@ -1,14 +1,17 @@
 use crate::installer::archive_installer::ArchiveInstaller;
-use crate::installer::command::exec_command;
+use crate::installer::command::{exec_command, find_command};
 use crate::installer::destination::Destination;
 use crate::installer::error::InstallError;
 use crate::installer::executable::Executable;
 use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
 use crate::installer::result::InstallerResult;
 use std::path::Path;
 use std::process::Command;
 
-const _7Z: &str = "7z";
+/// `7z` is the usual name, while `7zz` is the 7-Zip build for Linux and macOS and `7za`
+/// the standalone version of p7zip
+const _7Z: [&str; 3] = ["7z", "7zz", "7za"];
 
 pub struct SevenZipArchiveInstaller;
 
@@ -17,14 +20,22 @@ impl SevenZipArchiveInstaller {
         file_info: SupportedFileInfo,
         destination: Destination,
         executables: Vec<Executable>,
+        options: &InstallOptions,
     ) -> InstallerResult {
-        ArchiveInstaller::run(Self::extract_archive, file_info, destination, executables)
+        ArchiveInstaller::run(
+            Self::extract_archive,
+            file_info,
+            destination,
+            executables,
+            options,
+        )
     }
 
     fn extract_archive(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
+        let name = find_command(&_7Z).unwrap_or(_7Z[0]);
         exec_command(
-            _7Z,
-            Command::new(_7Z)
+            name,
+            Command::new(name)
                 .arg("x")
                 .arg(source)
                 .arg(format!("-o{}", temp_dir.display())),
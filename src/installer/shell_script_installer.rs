@@ -0,0 +1,49 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::installer::command::exec_command;
+use crate::installer::destination::Destination;
+use crate::installer::error::InstallError;
+use crate::installer::executable::{Executable, set_executable_permissions};
+use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
+use crate::installer::result::{InstallOutput, InstallerResult};
+
+pub struct ShellScriptInstaller;
+
+impl ShellScriptInstaller {
+    pub fn run(
+        file_info: SupportedFileInfo,
+        destination: Destination,
+        _executables: Vec<Executable>,
+        options: &InstallOptions,
+    ) -> InstallerResult {
+        if !options.allow_shell_scripts {
+            return Err(InstallError::NotSupported(format!(
+                "{} is a self-extracting shell script. Use --allow-shell-scripts to run it",
+                file_info.name
+            )));
+        }
+
+        set_executable_permissions(&file_info.path)?;
+
+        // Self-extracting scripts usually unpack their payload in the current directory
+        let working_directory = match &destination {
+            Destination::Directory(dir) => dir.as_path(),
+            Destination::File(file) => file.parent().unwrap_or(Path::new(".")),
+        };
+
+        exec_command(
+            &file_info.name,
+            Command::new(&file_info.path)
+                .args(&options.self_extract_args)
+                .current_dir(working_directory),
+        )
+        .map(|_| {
+            InstallOutput::new(format!(
+                "Self-extracting shell script '{}' executed",
+                file_info.name
+            ))
+        })
+    }
+}
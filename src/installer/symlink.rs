@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use crate::installer::error::{InstallError, InstallErrorMapErr};
+use crate::installer::result::{InstallOutput, InstallerResult};
+
+/// Create a symlink at `dst` pointing to `src`.
+///
+/// An existing symlink at `dst` is replaced, while any other existing file is never overwritten.
+pub fn create_symlink(src: &Path, dst: &Path) -> InstallerResult {
+    if let Ok(metadata) = dst.symlink_metadata() {
+        if !metadata.file_type().is_symlink() {
+            return Err(InstallError::Fatal(format!(
+                "Cannot create symlink {}: file already exists and is not a symlink",
+                dst.display()
+            )));
+        }
+        std::fs::remove_file(dst)
+            .map_fatal_err(format!("Error removing existing symlink {}", dst.display()))?;
+    }
+
+    // The link is resolved relative to its own directory, so it must point to an absolute path
+    let src = std::fs::canonicalize(src)
+        .map_fatal_err(format!("Error resolving path {}", src.display()))?;
+
+    symlink(&src, dst).map_fatal_err(format!(
+        "Error creating symlink {} -> {}",
+        dst.display(),
+        src.display()
+    ))?;
+
+    Ok(InstallOutput::new(format!(
+        "Created symlink '{}' -> '{}'",
+        dst.display(),
+        src.display()
+    )))
+}
+
+#[cfg(target_family = "unix")]
+fn symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(src, dst)
+}
+
+#[cfg(target_os = "windows")]
+fn symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(src, dst)
+}
+
+#[cfg(all(test, target_family = "unix"))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn create_new_symlink() {
+        let dir = temp_dir("create_new_symlink");
+        let src = create_file(&dir, "my-tool");
+        let dst = dir.join("tool");
+
+        let result = create_symlink(&src, &dst);
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(src, std::fs::read_link(&dst).unwrap());
+    }
+
+    #[test]
+    fn replace_existing_symlink() {
+        let dir = temp_dir("replace_existing_symlink");
+        let old_src = create_file(&dir, "my-tool-v1");
+        let src = create_file(&dir, "my-tool-v2");
+        let dst = dir.join("tool");
+        std::os::unix::fs::symlink(&old_src, &dst).unwrap();
+
+        let result = create_symlink(&src, &dst);
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(src, std::fs::read_link(&dst).unwrap());
+    }
+
+    #[test]
+    fn existing_regular_file_is_not_replaced() {
+        let dir = temp_dir("existing_regular_file_is_not_replaced");
+        let src = create_file(&dir, "my-tool");
+        let dst = create_file(&dir, "tool");
+
+        let result = create_symlink(&src, &dst);
+
+        assert!(matches!(result, Err(InstallError::Fatal(_))));
+        assert!(!dst.symlink_metadata().unwrap().file_type().is_symlink());
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("dra-symlink-tests").join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::canonicalize(dir).unwrap()
+    }
+
+    fn create_file(directory: &Path, name: &str) -> PathBuf {
+        let path = directory.join(name);
+        std::fs::write(&path, "").unwrap();
+        path
+    }
+}
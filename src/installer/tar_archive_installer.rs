@@ -7,6 +7,7 @@ use crate::installer::destination::Destination;
 use crate::installer::error::InstallError;
 use crate::installer::executable::Executable;
 use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
 use crate::installer::result::InstallerResult;
 
 pub struct TarArchiveInstaller;
@@ -16,29 +17,65 @@ impl TarArchiveInstaller {
         file_info: SupportedFileInfo,
         destination: Destination,
         executables: Vec<Executable>,
+        options: &InstallOptions,
     ) -> InstallerResult {
-        ArchiveInstaller::run(Self::extract_gz, file_info, destination, executables)
+        ArchiveInstaller::run(
+            Self::extract_gz,
+            file_info,
+            destination,
+            executables,
+            options,
+        )
     }
 
     pub fn xz(
         file_info: SupportedFileInfo,
         destination: Destination,
         executables: Vec<Executable>,
+        options: &InstallOptions,
     ) -> InstallerResult {
-        ArchiveInstaller::run(Self::extract_xz, file_info, destination, executables)
+        ArchiveInstaller::run(
+            Self::extract_xz,
+            file_info,
+            destination,
+            executables,
+            options,
+        )
     }
 
     pub fn bz2(
         file_info: SupportedFileInfo,
         destination: Destination,
         executables: Vec<Executable>,
+        options: &InstallOptions,
+    ) -> InstallerResult {
+        ArchiveInstaller::run(
+            Self::extract_bz2,
+            file_info,
+            destination,
+            executables,
+            options,
+        )
+    }
+
+    pub fn zst(
+        file_info: SupportedFileInfo,
+        destination: Destination,
+        executables: Vec<Executable>,
+        options: &InstallOptions,
     ) -> InstallerResult {
-        ArchiveInstaller::run(Self::extract_bz2, file_info, destination, executables)
+        ArchiveInstaller::run(
+            Self::extract_zst,
+            file_info,
+            destination,
+            executables,
+            options,
+        )
     }
 
     fn extract_gz(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
         Self::extract_archive(
-            |file| Box::new(flate2::read::GzDecoder::new(file)),
+            |file| Ok(Box::new(flate2::read::GzDecoder::new(file))),
             source,
             temp_dir,
         )
@@ -46,7 +83,7 @@ impl TarArchiveInstaller {
 
     fn extract_xz(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
         Self::extract_archive(
-            |file| Box::new(xz2::read::XzDecoder::new(file)),
+            |file| Ok(Box::new(xz2::read::XzDecoder::new(file))),
             source,
             temp_dir,
         )
@@ -54,7 +91,15 @@ impl TarArchiveInstaller {
 
     fn extract_bz2(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
         Self::extract_archive(
-            |file| Box::new(bzip2::read::BzDecoder::new(file)),
+            |file| Ok(Box::new(bzip2::read::BzDecoder::new(file))),
+            source,
+            temp_dir,
+        )
+    }
+
+    fn extract_zst(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
+        Self::extract_archive(
+            |file| Ok(Box::new(zstd::stream::read::Decoder::new(file)?)),
             source,
             temp_dir,
         )
@@ -62,7 +107,7 @@ impl TarArchiveInstaller {
 
     fn extract_archive<D>(decode: D, source: &Path, temp_dir: &Path) -> Result<(), InstallError>
     where
-        D: FnOnce(File) -> Box<dyn Read>,
+        D: FnOnce(File) -> std::io::Result<Box<dyn Read>>,
     {
         let archive = File::open(source).map_err(|x| {
             InstallError::Fatal(format!(
@@ -72,7 +117,8 @@ impl TarArchiveInstaller {
             ))
         })?;
 
-        let stream = decode(archive);
+        let stream = decode(archive)
+            .map_err(|x| InstallError::Fatal(format!("Error reading tar archive: {}", x)))?;
         let mut archive = tar::Archive::new(stream);
 
         archive
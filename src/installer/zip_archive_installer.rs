@@ -1,11 +1,13 @@
 use std::fs::File;
+use std::io::Read;
 use std::path::Path;
 
 use crate::installer::archive_installer::ArchiveInstaller;
 use crate::installer::destination::Destination;
 use crate::installer::error::InstallError;
-use crate::installer::executable::Executable;
+use crate::installer::executable::{Executable, set_executable_permissions};
 use crate::installer::file::SupportedFileInfo;
+use crate::installer::options::InstallOptions;
 use crate::installer::result::InstallerResult;
 
 pub struct ZipArchiveInstaller;
@@ -15,8 +17,15 @@ impl ZipArchiveInstaller {
         file_info: SupportedFileInfo,
         destination: Destination,
         executables: Vec<Executable>,
+        options: &InstallOptions,
     ) -> InstallerResult {
-        ArchiveInstaller::run(Self::extract_archive, file_info, destination, executables)
+        ArchiveInstaller::run(
+            Self::extract_archive,
+            file_info,
+            destination,
+            executables,
+            options,
+        )
     }
 
     fn extract_archive(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
@@ -33,6 +42,66 @@ impl ZipArchiveInstaller {
 
         archive
             .extract(temp_dir)
-            .map_err(|x| InstallError::Fatal(format!("Error extracting zip archive: {}", x)))
+            .map_err(|x| InstallError::Fatal(format!("Error extracting zip archive: {}", x)))?;
+        Self::set_missing_permissions(&mut archive, temp_dir)
+    }
+
+    /// Zip archives created on Windows (and by some tools) don't store unix permissions, so
+    /// their executables are extracted without the executable bit. Files that are native
+    /// executables or scripts (by their first bytes) are made executable
+    fn set_missing_permissions(
+        archive: &mut zip::ZipArchive<File>,
+        temp_dir: &Path,
+    ) -> Result<(), InstallError> {
+        for index in 0..archive.len() {
+            let path = match archive.by_index_raw(index) {
+                Ok(file) if file.is_file() && file.unix_mode().is_none_or(|x| x & 0o111 == 0) => {
+                    file.enclosed_name().map(|x| temp_dir.join(x))
+                }
+                _ => None,
+            };
+            if let Some(path) = path.filter(|x| starts_like_executable(x)) {
+                set_executable_permissions(&path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// ELF, Mach-O (32/64 bit and universal binaries) and shebang scripts
+fn is_executable_header(header: &[u8]) -> bool {
+    const MAGIC_NUMBERS: [&[u8]; 7] = [
+        b"\x7fELF",
+        &[0xfe, 0xed, 0xfa, 0xce],
+        &[0xce, 0xfa, 0xed, 0xfe],
+        &[0xfe, 0xed, 0xfa, 0xcf],
+        &[0xcf, 0xfa, 0xed, 0xfe],
+        &[0xca, 0xfe, 0xba, 0xbe],
+        b"#!",
+    ];
+    MAGIC_NUMBERS.iter().any(|x| header.starts_with(x))
+}
+
+fn starts_like_executable(path: &Path) -> bool {
+    let mut header = Vec::with_capacity(4);
+    File::open(path)
+        .and_then(|file| file.take(4).read_to_end(&mut header))
+        .is_ok_and(|_| is_executable_header(&header))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(b"\x7fELF\x02\x01", true; "elf")]
+    #[test_case(&[0xcf, 0xfa, 0xed, 0xfe, 0x0c], true; "mach-o 64 bit")]
+    #[test_case(&[0xca, 0xfe, 0xba, 0xbe, 0x00], true; "mach-o universal")]
+    #[test_case(b"#!/bin/sh", true; "shebang")]
+    #[test_case(b"MZ\x90\x00", false; "windows executable")]
+    #[test_case(b"MIT License", false; "text")]
+    #[test_case(b"", false; "empty")]
+    fn executable_header(header: &[u8], expected: bool) {
+        assert_eq!(expected, is_executable_header(header));
     }
 }
@@ -1,16 +1,23 @@
 // NOTE: this rule is not supported by rust-analyzer or JetBrains Rust plugin go to definition/refactoring tools so disable it until it's supported properly
 #![allow(clippy::uninlined_format_args)]
 
+use crate::cli::assets_handler::AssetsHandler;
+use crate::cli::auth_handler::AuthHandler;
 use crate::cli::color::Color;
 use crate::cli::completion_handler::CompletionHandler;
+use crate::cli::doctor_handler::DoctorHandler;
 use crate::cli::download_handler::DownloadHandler;
+use crate::cli::notes_handler::NotesHandler;
+use crate::cli::releases_handler::ReleasesHandler;
 use crate::cli::result::{HandlerError, HandlerResult};
 use crate::cli::root_command::{Cli, Command};
+use crate::cli::tags_handler::TagsHandler;
 use crate::cli::untag_handler::UntagHandler;
 use clap::Parser;
 use std::process::exit;
 
 mod cli;
+mod config;
 mod env_var;
 mod github;
 mod installer;
@@ -20,6 +27,8 @@ mod vector;
 
 fn main() {
     let cli: Cli = Cli::parse();
+    cli::logger::init(cli.verbose, cli.quiet);
+    cli::output::set_quiet(cli.quiet);
     init_ctrl_c_handler();
     handle(run(cli));
 }
@@ -37,31 +46,27 @@ fn init_ctrl_c_handler() {
 
 fn run(cli: Cli) -> HandlerResult {
     match cli.cmd {
-        Command::Download {
-            repo,
-            select,
-            automatic,
-            tag,
-            output,
-            install,
-            install_file,
-        } => {
-            DownloadHandler::new(repo, select, automatic, tag, output, install, install_file).run()
-        }
+        Command::Download(args) => DownloadHandler::new(*args)?.run(),
         Command::Untag { repo } => UntagHandler::new(repo).run(),
         Command::Completion { shell } => CompletionHandler::new(shell).run(),
+        Command::Doctor => DoctorHandler::new().run(),
+        Command::Releases { repo, limit, json } => ReleasesHandler::new(repo, limit, json).run(),
+        Command::Assets { repo, tag, json } => AssetsHandler::new(repo, tag, json).run(),
+        Command::Notes { repo, tag, raw } => NotesHandler::new(repo, tag, raw).run(),
+        Command::Tags { repo, pattern } => TagsHandler::new(repo, pattern).run(),
+        Command::Auth { cmd } => AuthHandler::new(cmd).run(),
     }
 }
 
 fn handle(result: HandlerResult) {
     if let Err(error) = result {
-        match error {
-            HandlerError::Default(msg) => {
-                eprintln!("{}", Color::new(&msg).red().bold());
-                exit(1)
+        match &error {
+            HandlerError::Default(msg) | HandlerError::Failure(_, msg) => {
+                eprintln!("{}", Color::new(msg).red().bold());
+                exit(error.exit_code())
             }
             HandlerError::OperationCancelled(msg) => {
-                println!("Operation cancelled: {}", Color::new(&msg).bold());
+                println!("Operation cancelled: {}", Color::new(msg).bold());
             }
         }
     }
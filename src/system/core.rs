@@ -11,6 +11,7 @@ pub enum Arch {
     X86_64,
     ArmV6,
     Arm64,
+    PowerPC64LE,
 }
 
 pub trait System {
@@ -42,6 +43,7 @@ impl Arch {
             Arch::X86_64 => "x86_64",
             Arch::ArmV6 => "arm",
             Arch::Arm64 => "aarch64",
+            Arch::PowerPC64LE => "powerpc64le",
         }
     }
 }
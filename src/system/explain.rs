@@ -0,0 +1,127 @@
+use crate::github::release::Asset;
+use crate::system::core::System;
+use crate::system::find_asset_by_system::skip_ignored_asset;
+use std::fmt::Formatter;
+
+const OS_TOKENS: [&str; 8] = [
+    "linux",
+    "macos",
+    "darwin",
+    "apple",
+    "osx",
+    "windows",
+    "win64",
+    "win-64bit",
+];
+const ARCH_TOKENS: [&str; 11] = [
+    "x86_64",
+    "amd64",
+    "x64",
+    "aarch64",
+    "arm64",
+    "armv6",
+    "armv7",
+    "arm",
+    "powerpc64le",
+    "ppc64le",
+    "ppc64el",
+];
+const LIBC_TOKENS: [&str; 2] = ["musl", "gnu"];
+
+/// How automatic mode evaluated an asset: the detected tokens and, when the asset matches
+/// the system, its priority (lower is better)
+#[derive(Debug, PartialEq)]
+pub struct AssetExplanation {
+    pub name: String,
+    pub os: Option<&'static str>,
+    pub arch: Option<&'static str>,
+    pub libc: Option<&'static str>,
+    pub ignored: bool,
+    pub priority: Option<i32>,
+}
+
+pub fn explain(system: &impl System, assets: &[Asset]) -> Vec<AssetExplanation> {
+    assets
+        .iter()
+        .map(|asset| {
+            let name = asset.name.to_lowercase();
+            let ignored = !skip_ignored_asset(asset);
+            AssetExplanation {
+                name: asset.name.clone(),
+                os: detect_token(&OS_TOKENS, &name),
+                arch: detect_token(&ARCH_TOKENS, &name),
+                libc: detect_token(&LIBC_TOKENS, &name),
+                ignored,
+                priority: (!ignored && system.matches(asset))
+                    .then(|| system.by_asset_priority(asset)),
+            }
+        })
+        .collect()
+}
+
+fn detect_token(tokens: &[&'static str], asset_name: &str) -> Option<&'static str> {
+    tokens.iter().find(|x| asset_name.contains(*x)).copied()
+}
+
+impl std::fmt::Display for AssetExplanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let result = match (self.ignored, self.priority) {
+            (true, _) => "ignored".to_string(),
+            (false, Some(priority)) => format!("priority={}", priority),
+            (false, None) => "no match".to_string(),
+        };
+        write!(
+            f,
+            "{} os={} arch={} libc={} {}",
+            self.name,
+            self.os.unwrap_or("-"),
+            self.arch.unwrap_or("-"),
+            self.libc.unwrap_or("-"),
+            result
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::linux::LinuxX86_64;
+    use test_case::test_case;
+
+    #[test_case("mypackage-x86_64-unknown-linux-musl.tar.gz", Some("linux"), Some("x86_64"), Some("musl"); "rust target")]
+    #[test_case("mypackage_Darwin_arm64.tar.gz", Some("darwin"), Some("arm64"), None; "go target")]
+    #[test_case("mypackage.tar.gz", None, None, None; "no tokens")]
+    fn detect_tokens(asset_name: &str, os: Option<&str>, arch: Option<&str>, libc: Option<&str>) {
+        let result = explain(&LinuxX86_64, &[any_asset(asset_name)]);
+
+        let explanation = &result[0];
+        assert_eq!(os, explanation.os);
+        assert_eq!(arch, explanation.arch);
+        assert_eq!(libc, explanation.libc);
+    }
+
+    #[test_case("mypackage-x86_64-unknown-linux-musl.tar.gz", "os=linux arch=x86_64 libc=musl priority=1"; "matching")]
+    #[test_case("mypackage-x86_64-apple-darwin.tar.gz", "os=darwin arch=x86_64 libc=- no match"; "not matching")]
+    #[test_case("mypackage-x86_64-unknown-linux-musl.tar.gz.sha256", "os=linux arch=x86_64 libc=musl ignored"; "ignored")]
+    fn display(asset_name: &str, expected: &str) {
+        let result = explain(&LinuxX86_64, &[any_asset(asset_name)]);
+
+        assert_eq!(
+            format!("{} {}", asset_name, expected),
+            result[0].to_string()
+        );
+    }
+
+    fn any_asset(name: &str) -> Asset {
+        Asset {
+            name: name.into(),
+            display_name: None,
+            content_type: None,
+            size: None,
+            download_count: None,
+            updated_at: None,
+            download_url: "ANY_DOWNLOAD_URL".into(),
+            api_url: None,
+        }
+    }
+}
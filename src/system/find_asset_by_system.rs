@@ -1,19 +1,52 @@
-use crate::github::release::Asset;
+use crate::config::weights::AssetWeights;
+use crate::github::release::{Asset, Tag};
 use crate::system::core::System;
 
-pub fn find_asset_by_system(system: &impl System, assets: Vec<Asset>) -> Option<Asset> {
-    let mut matches: Vec<_> = assets
+/// All the matching assets that share the best system asset priority
+pub fn find_candidates_by_system(system: &impl System, assets: Vec<Asset>) -> Vec<Asset> {
+    let matches: Vec<_> = assets
         .into_iter()
         .filter(skip_ignored_asset)
         .filter(|asset| system.matches(asset))
         .collect();
-    matches.sort_by_key(|asset| system.by_asset_priority(asset));
-    matches.into_iter().next()
+    best_candidates(matches, |asset| system.by_asset_priority(asset))
+}
+
+/// Weights have the highest priority: assets are filtered by the weights pattern (or by system when missing)
+/// and sorted by weights score, then by system asset priority.
+/// Returns all the matching assets that share the best score and priority
+pub fn find_candidates_by_weights(
+    system: &impl System,
+    weights: &AssetWeights,
+    tag: &Tag,
+    assets: Vec<Asset>,
+) -> Vec<Asset> {
+    let pattern = weights.pattern_for(tag, system.os().as_str(), system.arch().as_str());
+    let matches: Vec<_> = assets
+        .into_iter()
+        .filter(skip_ignored_asset)
+        .filter(|asset| match pattern.as_ref() {
+            Some(pattern) => pattern.matches(&asset.name),
+            None => system.matches(asset),
+        })
+        .collect();
+    best_candidates(matches, |asset| {
+        (-weights.score(&asset.name), system.by_asset_priority(asset))
+    })
+}
+
+fn best_candidates<K: Ord>(mut assets: Vec<Asset>, key: impl Fn(&Asset) -> K) -> Vec<Asset> {
+    assets.sort_by_key(&key);
+    let best = match assets.first() {
+        Some(asset) => key(asset),
+        None => return assets,
+    };
+    assets.into_iter().take_while(|x| key(x) == best).collect()
 }
 
 const IGNORED_ASSETS: [&str; 3] = ["sha256", "sha512", "checksums"];
 
-fn skip_ignored_asset(asset: &Asset) -> bool {
+pub(super) fn skip_ignored_asset(asset: &Asset) -> bool {
     !IGNORED_ASSETS
         .iter()
         .any(|ignored| asset.name.contains(ignored))
@@ -35,7 +68,9 @@ mod acceptance_tests {
             asset("mypackage-x86_64-unknown-linux-musl.tar.gz"),
         ];
 
-        let result = find_asset_by_system(&system, assets);
+        let result = find_candidates_by_system(&system, assets)
+            .into_iter()
+            .next();
 
         assert_eq_asset("mypackage-x86_64-unknown-linux-musl.tar.gz", result)
     }
@@ -50,11 +85,110 @@ mod acceptance_tests {
             asset("mypackage-x86_64-unknown-linux-musl.tar.gz"),
         ];
 
-        let result = find_asset_by_system(&system, assets);
+        let result = find_candidates_by_system(&system, assets)
+            .into_iter()
+            .next();
 
         assert!(result.is_none())
     }
 
+    #[test]
+    fn asset_found_by_weights_pattern() {
+        let system = FixedAssetSystem {
+            asset: "mypackage-x86_64-unknown-linux-gnu.tar.gz".to_string(),
+        };
+        let weights = AssetWeights {
+            pattern: Some("mypackage-{version}-{os}-{arch}.*".to_string()),
+            scores: Default::default(),
+        };
+        let assets = vec![
+            asset("mypackage-x86_64-unknown-linux-gnu.tar.gz"),
+            asset("mypackage-1.0.0-linux-x86_64.tar.gz"),
+            asset("mypackage-1.0.0-linux-x86_64.tar.gz.sha256"),
+        ];
+
+        let result = find_candidates_by_weights(&system, &weights, &Tag("v1.0.0".into()), assets)
+            .into_iter()
+            .next();
+
+        assert_eq_asset("mypackage-1.0.0-linux-x86_64.tar.gz", result)
+    }
+
+    #[test]
+    fn asset_found_by_weights_score() {
+        let system = AnyAssetSystem;
+        let weights = AssetWeights {
+            pattern: None,
+            scores: [("musl".to_string(), 10)].into(),
+        };
+        let assets = vec![
+            asset("mypackage-x86_64-unknown-linux-gnu.tar.gz"),
+            asset("mypackage-x86_64-unknown-linux-musl.tar.gz"),
+        ];
+
+        let result = find_candidates_by_weights(&system, &weights, &Tag("v1.0.0".into()), assets)
+            .into_iter()
+            .next();
+
+        assert_eq_asset("mypackage-x86_64-unknown-linux-musl.tar.gz", result)
+    }
+
+    #[test]
+    fn candidates_with_same_priority() {
+        let system = AnyAssetSystem;
+        let assets = vec![
+            asset("mypackage-x86_64-unknown-linux-gnu.tar.gz"),
+            asset("mypackage-x86_64-unknown-linux-musl.tar.gz"),
+            asset("mypackage-x86_64-unknown-linux-musl.tar.gz.sha256"),
+        ];
+
+        let result = find_candidates_by_system(&system, assets);
+
+        let names: Vec<_> = result.into_iter().map(|x| x.name).collect();
+        assert_eq!(
+            vec![
+                "mypackage-x86_64-unknown-linux-gnu.tar.gz",
+                "mypackage-x86_64-unknown-linux-musl.tar.gz",
+            ],
+            names
+        )
+    }
+
+    #[test]
+    fn single_candidate_by_weights_score() {
+        let system = AnyAssetSystem;
+        let weights = AssetWeights {
+            pattern: None,
+            scores: [("musl".to_string(), 10)].into(),
+        };
+        let assets = vec![
+            asset("mypackage-x86_64-unknown-linux-gnu.tar.gz"),
+            asset("mypackage-x86_64-unknown-linux-musl.tar.gz"),
+        ];
+
+        let result = find_candidates_by_weights(&system, &weights, &Tag("v1.0.0".into()), assets);
+
+        let names: Vec<_> = result.into_iter().map(|x| x.name).collect();
+        assert_eq!(vec!["mypackage-x86_64-unknown-linux-musl.tar.gz"], names)
+    }
+
+    struct AnyAssetSystem;
+    impl System for AnyAssetSystem {
+        fn os(&self) -> OS {
+            OS::Linux
+        }
+        fn arch(&self) -> Arch {
+            Arch::X86_64
+        }
+        fn matches(&self, _asset: &Asset) -> bool {
+            true
+        }
+
+        fn by_asset_priority(&self, _asset: &Asset) -> i32 {
+            1
+        }
+    }
+
     struct FixedAssetSystem {
         asset: String,
     }
@@ -87,7 +221,12 @@ mod acceptance_tests {
         Asset {
             name: name.into(),
             display_name: None,
+            content_type: None,
+            size: None,
+            download_count: None,
+            updated_at: None,
             download_url: "ANY_DOWNLOAD_URL".into(),
+            api_url: None,
         }
     }
 }
@@ -0,0 +1,146 @@
+use crate::github::release::Asset;
+use crate::system::core::{Arch, OS, System};
+use std::fmt::Formatter;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Libc {
+    Musl,
+    Gnu,
+}
+
+impl Libc {
+    pub fn try_parse(src: &str) -> Result<Libc, String> {
+        match src.to_lowercase().as_str() {
+            "musl" => Ok(Libc::Musl),
+            "gnu" | "glibc" => Ok(Libc::Gnu),
+            _ => Err(format!(
+                "Invalid libc {}. Supported values are: musl, gnu",
+                src
+            )),
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            Libc::Musl => "musl",
+            Libc::Gnu => "gnu",
+        }
+    }
+
+    fn other(&self) -> Libc {
+        match self {
+            Libc::Musl => Libc::Gnu,
+            Libc::Gnu => Libc::Musl,
+        }
+    }
+
+    /// Assets built with this libc come first, then the ones that don't mention any libc
+    fn rank(&self, asset_name: &str) -> i32 {
+        let asset_name = asset_name.to_lowercase();
+        if asset_name.contains(self.as_str()) {
+            0
+        } else if asset_name.contains(self.other().as_str()) {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+impl std::fmt::Display for Libc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Sort assets by the preferred libc first (if any), then by the system asset priority
+pub struct PreferLibc<S: System> {
+    system: S,
+    libc: Option<Libc>,
+}
+
+impl<S: System> PreferLibc<S> {
+    pub fn new(system: S, libc: Option<Libc>) -> Self {
+        Self { system, libc }
+    }
+}
+
+impl<S: System> System for PreferLibc<S> {
+    fn os(&self) -> OS {
+        self.system.os()
+    }
+    fn arch(&self) -> Arch {
+        self.system.arch()
+    }
+    fn matches(&self, asset: &Asset) -> bool {
+        self.system.matches(asset)
+    }
+    fn by_asset_priority(&self, asset: &Asset) -> i32 {
+        // System priorities are always lower than 100
+        let rank = self.libc.map(|x| x.rank(&asset.name)).unwrap_or(0);
+        rank * 100 + self.system.by_asset_priority(asset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::find_candidates_by_system;
+    use crate::system::linux::LinuxX86_64;
+    use test_case::test_case;
+
+    #[test_case(Libc::Gnu, "mypackage-x86_64-unknown-linux-gnu.tar.gz"; "gnu")]
+    #[test_case(Libc::Musl, "mypackage-x86_64-unknown-linux-musl.tar.gz"; "musl")]
+    fn preferred_libc(libc: Libc, expected: &str) {
+        let system = PreferLibc::new(LinuxX86_64, Some(libc));
+        let assets = vec![
+            any_asset("mypackage-x86_64-unknown-linux-musl.tar.gz"),
+            any_asset("mypackage-x86_64-unknown-linux-gnu.tar.gz"),
+            any_asset("mypackage-x86_64-unknown-linux-gnu"),
+        ];
+
+        let result = find_candidates_by_system(&system, assets)
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_eq!(expected, result.name);
+    }
+
+    #[test]
+    fn asset_without_libc_before_other_libc() {
+        let system = PreferLibc::new(LinuxX86_64, Some(Libc::Gnu));
+        let assets = vec![
+            any_asset("mypackage-x86_64-unknown-linux-musl.tar.gz"),
+            any_asset("mypackage-linux-amd64.tar.gz"),
+        ];
+
+        let result = find_candidates_by_system(&system, assets)
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_eq!("mypackage-linux-amd64.tar.gz", result.name);
+    }
+
+    #[test_case("musl", Ok(Libc::Musl); "musl")]
+    #[test_case("GNU", Ok(Libc::Gnu); "gnu")]
+    #[test_case("glibc", Ok(Libc::Gnu); "glibc")]
+    #[test_case("uclibc", Err("Invalid libc uclibc. Supported values are: musl, gnu".to_string()); "invalid")]
+    fn try_parse(src: &str, expected: Result<Libc, String>) {
+        assert_eq!(expected, Libc::try_parse(src));
+    }
+
+    fn any_asset(name: &str) -> Asset {
+        Asset {
+            name: name.to_string(),
+            display_name: None,
+            content_type: None,
+            size: None,
+            download_count: None,
+            updated_at: None,
+            download_url: "ANY_DOWNLOAD_URL".to_string(),
+            api_url: None,
+        }
+    }
+}
@@ -64,6 +64,27 @@ impl System for LinuxArm64 {
     }
 }
 
+pub struct LinuxPowerPC64LE;
+impl LinuxPowerPC64LE {
+    const OS: OS = OS::Linux;
+    const ARCH: Arch = Arch::PowerPC64LE;
+}
+
+impl System for LinuxPowerPC64LE {
+    fn os(&self) -> OS {
+        Self::OS
+    }
+    fn arch(&self) -> Arch {
+        Self::ARCH
+    }
+    fn matches(&self, asset: &Asset) -> bool {
+        matches(Self::OS, Self::ARCH, asset)
+    }
+    fn by_asset_priority(&self, asset: &Asset) -> i32 {
+        asset_priority(asset)
+    }
+}
+
 fn matches(os: OS, arch: Arch, asset: &Asset) -> bool {
     let asset_name = asset.name.to_lowercase();
     let same_arch = is_same_arch(arch, &asset_name);
@@ -81,6 +102,7 @@ fn is_same_arch(arch: Arch, asset_name: &str) -> bool {
         Arch::X86_64 => vec!["x86_64", "amd64", "x64"],
         Arch::Arm64 => vec!["aarch64", "arm64"],
         Arch::ArmV6 => vec!["arm", "armv6", "armv7"],
+        Arch::PowerPC64LE => vec!["ppc64le", "powerpc64le", "ppc64el"],
     };
     aliases.into_iter().any(|alias| asset_name.contains(alias))
 }
@@ -92,7 +114,9 @@ fn contains_extension(asset_name: &str) -> bool {
         .any(|extension| asset_name.ends_with(extension))
 }
 
-const ARCHIVES: [&str; 7] = [".gz", ".tgz", ".bz2", ".tbz", ".xz", ".txz", ".zip"];
+const ARCHIVES: [&str; 10] = [
+    ".gz", ".tgz", ".bz2", ".tbz", ".xz", ".txz", ".zst", ".tzst", ".zip", ".7z",
+];
 
 fn asset_priority(a: &Asset) -> i32 {
     let is_archive = ARCHIVES.iter().any(|x| a.name.ends_with(x));
@@ -112,6 +136,7 @@ fn asset_priority(a: &Asset) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_case::test_case;
 
     #[test]
     fn asset_found() {
@@ -140,6 +165,36 @@ mod tests {
         assert!(!result)
     }
 
+    #[test_case("mypackage-powerpc64le-unknown-linux-gnu.tar.gz"; "rust target")]
+    #[test_case("mypackage_linux_ppc64le.tar.gz"; "go arch")]
+    #[test_case("mypackage-linux-ppc64el.deb"; "debian arch")]
+    fn found_by_powerpc64le_alias(asset_name: &str) {
+        let asset = any_asset(asset_name);
+
+        let result = matches(LinuxPowerPC64LE::OS, LinuxPowerPC64LE::ARCH, &asset);
+
+        assert!(result)
+    }
+
+    #[test_case("mypackage-powerpc64-unknown-linux-gnu.tar.gz"; "big endian")]
+    #[test_case("mypackage-x86_64-unknown-linux-musl.tar.gz"; "x86_64")]
+    fn powerpc64le_not_matching(asset_name: &str) {
+        let asset = any_asset(asset_name);
+
+        let result = matches(LinuxPowerPC64LE::OS, LinuxPowerPC64LE::ARCH, &asset);
+
+        assert!(!result)
+    }
+
+    #[test]
+    fn powerpc64le_asset_not_matching_x86_64() {
+        let asset = any_asset("mypackage_linux_ppc64le.tar.gz");
+
+        let result = matches(LinuxX86_64::OS, LinuxX86_64::ARCH, &asset);
+
+        assert!(!result)
+    }
+
     #[test]
     fn find_asset_case_insensitive() {
         let asset = any_asset("mypackage-X86_64-unknown-LiNuX-musl.tar.gz");
@@ -200,7 +255,12 @@ mod tests {
         Asset {
             name: name.into(),
             display_name: None,
+            content_type: None,
+            size: None,
+            download_count: None,
+            updated_at: None,
             download_url: "ANY_DOWNLOAD_URL".into(),
+            api_url: None,
         }
     }
 }
@@ -60,7 +60,7 @@ fn is_same_arch(arch: Arch, asset_name: &str) -> bool {
     let aliases: Vec<&str> = match arch {
         Arch::X86_64 => vec!["x86_64", "amd64", "x64"],
         Arch::Arm64 => vec!["aarch64", "arm64"],
-        Arch::ArmV6 => return false,
+        Arch::ArmV6 | Arch::PowerPC64LE => return false,
     };
     aliases.into_iter().any(|alias| asset_name.contains(alias))
 }
@@ -72,7 +72,9 @@ fn contains_extension(asset_name: &str) -> bool {
         .any(|extension| asset_name.ends_with(extension))
 }
 
-const ARCHIVES: [&str; 7] = [".gz", ".tgz", ".bz2", ".tbz", ".xz", ".txz", ".zip"];
+const ARCHIVES: [&str; 10] = [
+    ".gz", ".tgz", ".bz2", ".tbz", ".xz", ".txz", ".zst", ".tzst", ".zip", ".7z",
+];
 
 fn asset_priority(a: &Asset) -> i32 {
     let is_archive = ARCHIVES.iter().any(|x| a.name.ends_with(x));
@@ -183,7 +185,12 @@ mod tests {
         Asset {
             name: name.into(),
             display_name: None,
+            content_type: None,
+            size: None,
+            download_count: None,
+            updated_at: None,
             download_url: "ANY_DOWNLOAD_URL".into(),
+            api_url: None,
         }
     }
 }
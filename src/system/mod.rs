@@ -1,10 +1,18 @@
 mod core;
+mod explain;
 mod find_asset_by_system;
+mod libc;
 mod linux;
 mod macos;
+mod placeholders;
+mod rules;
 mod supported_systems;
 mod windows;
 
 pub use core::System;
-pub use find_asset_by_system::find_asset_by_system;
-pub use supported_systems::{SystemError, from_environment};
+pub use explain::explain;
+pub use find_asset_by_system::{find_candidates_by_system, find_candidates_by_weights};
+pub use libc::{Libc, PreferLibc};
+pub use placeholders::{expand_platform, has_platform_placeholders};
+pub use rules::PreferRules;
+pub use supported_systems::{SupportedSystem, SystemError, from_os_arch};
@@ -0,0 +1,83 @@
+use crate::system::core::{OS, System};
+use crate::system::libc::Libc;
+
+const OS_PLACEHOLDER: &str = "{os}";
+const ARCH_PLACEHOLDER: &str = "{arch}";
+const TARGET_PLACEHOLDER: &str = "{target}";
+
+pub fn has_platform_placeholders(pattern: &str) -> bool {
+    [OS_PLACEHOLDER, ARCH_PLACEHOLDER, TARGET_PLACEHOLDER]
+        .iter()
+        .any(|x| pattern.contains(x))
+}
+
+/// `{target}` is replaced by the Rust target triple of the system (e.g. `x86_64-unknown-linux-musl`)
+pub fn expand_platform(pattern: &str, system: &impl System, libc: Libc) -> String {
+    pattern
+        .replace(OS_PLACEHOLDER, system.os().as_str())
+        .replace(ARCH_PLACEHOLDER, system.arch().as_str())
+        .replace(TARGET_PLACEHOLDER, &target(system, libc))
+}
+
+fn target(system: &impl System, libc: Libc) -> String {
+    let arch = system.arch();
+    match system.os() {
+        OS::Linux if arch.as_str() == "arm" => format!("arm-unknown-linux-{}eabihf", libc),
+        OS::Linux => format!("{}-unknown-linux-{}", arch, libc),
+        OS::Mac => format!("{}-apple-darwin", arch),
+        OS::Windows => format!("{}-pc-windows-msvc", arch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::linux::{LinuxArmV6, LinuxX86_64};
+    use crate::system::macos::MacOSArm64;
+    use crate::system::windows::WindowsX86_64;
+    use test_case::test_case;
+
+    #[test_case("tool-{os}-{arch}.tar.gz", "tool-linux-x86_64.tar.gz"; "os and arch")]
+    #[test_case("tool-{target}.tar.gz", "tool-x86_64-unknown-linux-musl.tar.gz"; "target")]
+    #[test_case("tool.tar.gz", "tool.tar.gz"; "no placeholders")]
+    fn expand_linux(pattern: &str, expected: &str) {
+        let result = expand_platform(pattern, &LinuxX86_64, Libc::Musl);
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn target_gnu() {
+        let result = expand_platform("{target}", &LinuxX86_64, Libc::Gnu);
+
+        assert_eq!("x86_64-unknown-linux-gnu", result);
+    }
+
+    #[test]
+    fn target_arm() {
+        let result = expand_platform("{target}", &LinuxArmV6, Libc::Gnu);
+
+        assert_eq!("arm-unknown-linux-gnueabihf", result);
+    }
+
+    #[test]
+    fn target_macos() {
+        let result = expand_platform("{target}", &MacOSArm64, Libc::Musl);
+
+        assert_eq!("aarch64-apple-darwin", result);
+    }
+
+    #[test]
+    fn target_windows() {
+        let result = expand_platform("{target}", &WindowsX86_64, Libc::Musl);
+
+        assert_eq!("x86_64-pc-windows-msvc", result);
+    }
+
+    #[test_case("tool-{os}.zip", true; "os")]
+    #[test_case("tool-{target}.zip", true; "target")]
+    #[test_case("tool-{tag}.zip", false; "tag only")]
+    fn detect_placeholders(pattern: &str, expected: bool) {
+        assert_eq!(expected, has_platform_placeholders(pattern));
+    }
+}
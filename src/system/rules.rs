@@ -0,0 +1,83 @@
+use crate::github::release::Asset;
+use crate::system::core::{Arch, OS, System};
+use regex::Regex;
+
+/// Sort assets by the first matching rule (e.g. prefer `musl`, then `static`, then `gnu`),
+/// then by the system asset priority. Assets that don't match any rule come last.
+pub struct PreferRules<S: System> {
+    system: S,
+    rules: Vec<Regex>,
+}
+
+impl<S: System> PreferRules<S> {
+    pub fn new(system: S, rules: Vec<Regex>) -> Self {
+        Self { system, rules }
+    }
+
+    fn rank(&self, asset_name: &str) -> i32 {
+        let index = self
+            .rules
+            .iter()
+            .position(|x| x.is_match(asset_name))
+            .unwrap_or(self.rules.len());
+        index as i32
+    }
+}
+
+impl<S: System> System for PreferRules<S> {
+    fn os(&self) -> OS {
+        self.system.os()
+    }
+    fn arch(&self) -> Arch {
+        self.system.arch()
+    }
+    fn matches(&self, asset: &Asset) -> bool {
+        self.system.matches(asset)
+    }
+    fn by_asset_priority(&self, asset: &Asset) -> i32 {
+        // Inner priorities (system and libc preference) are always lower than 10000
+        self.rank(&asset.name) * 10_000 + self.system.by_asset_priority(asset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::find_candidates_by_system;
+    use crate::system::linux::LinuxX86_64;
+    use test_case::test_case;
+
+    #[test_case(vec!["static", "musl"], "mypackage-x86_64-linux-static"; "first rule")]
+    #[test_case(vec!["not-found", "gnu"], "mypackage-x86_64-unknown-linux-gnu.tar.gz"; "second rule")]
+    #[test_case(vec!["not-found"], "mypackage-x86_64-unknown-linux-musl.tar.gz"; "built-in priority")]
+    #[test_case(vec![r"linux-(gnu|static)\.tar\.gz$"], "mypackage-x86_64-unknown-linux-gnu.tar.gz"; "regex")]
+    fn preferred_by_rules(rules: Vec<&str>, expected: &str) {
+        let rules = rules.into_iter().map(|x| Regex::new(x).unwrap()).collect();
+        let system = PreferRules::new(LinuxX86_64, rules);
+        let assets = vec![
+            any_asset("mypackage-x86_64-unknown-linux-musl.tar.gz"),
+            any_asset("mypackage-x86_64-unknown-linux-gnu.tar.gz"),
+            any_asset("mypackage-x86_64-linux-static"),
+        ];
+
+        let result = find_candidates_by_system(&system, assets)
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_eq!(expected, result.name);
+    }
+
+    fn any_asset(name: &str) -> Asset {
+        Asset {
+            name: name.to_string(),
+            display_name: None,
+            content_type: None,
+            size: None,
+            download_count: None,
+            updated_at: None,
+            download_url: "ANY_DOWNLOAD_URL".to_string(),
+            api_url: None,
+        }
+    }
+}
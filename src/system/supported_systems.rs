@@ -1,7 +1,7 @@
 use crate::github::release::Asset;
 use crate::system::core::{Arch, OS, System};
 use crate::system::{linux, macos, windows};
-use linux::{LinuxArm64, LinuxArmV6, LinuxX86_64};
+use linux::{LinuxArm64, LinuxArmV6, LinuxPowerPC64LE, LinuxX86_64};
 use macos::{MacOSArm64, MacOSX86_64};
 use std::fmt::{Display, Formatter};
 use windows::WindowsX86_64;
@@ -10,6 +10,7 @@ pub enum SupportedSystem {
     LinuxX86_64(LinuxX86_64),
     LinuxArmV6(LinuxArmV6),
     LinuxArm64(LinuxArm64),
+    LinuxPowerPC64LE(LinuxPowerPC64LE),
     MacOSX86_64(MacOSX86_64),
     MacOSArm64(MacOSArm64),
     WindowsX86_64(WindowsX86_64),
@@ -21,6 +22,7 @@ impl System for SupportedSystem {
             SupportedSystem::LinuxX86_64(system) => system.os(),
             SupportedSystem::LinuxArmV6(system) => system.os(),
             SupportedSystem::LinuxArm64(system) => system.os(),
+            SupportedSystem::LinuxPowerPC64LE(system) => system.os(),
             SupportedSystem::MacOSX86_64(system) => system.os(),
             SupportedSystem::MacOSArm64(system) => system.os(),
             SupportedSystem::WindowsX86_64(system) => system.os(),
@@ -32,6 +34,7 @@ impl System for SupportedSystem {
             SupportedSystem::LinuxX86_64(system) => system.arch(),
             SupportedSystem::LinuxArmV6(system) => system.arch(),
             SupportedSystem::LinuxArm64(system) => system.arch(),
+            SupportedSystem::LinuxPowerPC64LE(system) => system.arch(),
             SupportedSystem::MacOSX86_64(system) => system.arch(),
             SupportedSystem::MacOSArm64(system) => system.arch(),
             SupportedSystem::WindowsX86_64(system) => system.arch(),
@@ -43,6 +46,7 @@ impl System for SupportedSystem {
             SupportedSystem::LinuxX86_64(system) => system.matches(asset),
             SupportedSystem::LinuxArmV6(system) => system.matches(asset),
             SupportedSystem::LinuxArm64(system) => system.matches(asset),
+            SupportedSystem::LinuxPowerPC64LE(system) => system.matches(asset),
             SupportedSystem::MacOSX86_64(system) => system.matches(asset),
             SupportedSystem::MacOSArm64(system) => system.matches(asset),
             SupportedSystem::WindowsX86_64(system) => system.matches(asset),
@@ -54,6 +58,7 @@ impl System for SupportedSystem {
             SupportedSystem::LinuxX86_64(system) => system.by_asset_priority(asset),
             SupportedSystem::LinuxArmV6(system) => system.by_asset_priority(asset),
             SupportedSystem::LinuxArm64(system) => system.by_asset_priority(asset),
+            SupportedSystem::LinuxPowerPC64LE(system) => system.by_asset_priority(asset),
             SupportedSystem::MacOSX86_64(system) => system.by_asset_priority(asset),
             SupportedSystem::MacOSArm64(system) => system.by_asset_priority(asset),
             SupportedSystem::WindowsX86_64(system) => system.by_asset_priority(asset),
@@ -75,17 +80,75 @@ impl Display for SystemError {
     }
 }
 
-pub fn from_environment() -> Result<SupportedSystem, SystemError> {
-    let os = std::env::consts::OS;
-    let arch = std::env::consts::ARCH;
+/// Build the system from the given operating system and architecture (e.g. to download assets
+/// for another platform), using the current ones when missing
+pub fn from_os_arch(os: Option<&str>, arch: Option<&str>) -> Result<SupportedSystem, SystemError> {
+    let os = os
+        .map(normalize_os)
+        .unwrap_or_else(|| std::env::consts::OS.to_string());
+    let arch = arch.map(normalize_arch).unwrap_or_else(environment_arch);
 
-    match (os, arch) {
+    match (os.as_str(), arch.as_str()) {
         ("linux", "x86_64") => Ok(SupportedSystem::LinuxX86_64(LinuxX86_64)),
         ("linux", "arm") => Ok(SupportedSystem::LinuxArmV6(LinuxArmV6)),
         ("linux", "aarch64") => Ok(SupportedSystem::LinuxArm64(LinuxArm64)),
+        ("linux", "powerpc64le") => Ok(SupportedSystem::LinuxPowerPC64LE(LinuxPowerPC64LE)),
         ("macos", "x86_64") => Ok(SupportedSystem::MacOSX86_64(MacOSX86_64)),
         ("macos", "aarch64") => Ok(SupportedSystem::MacOSArm64(MacOSArm64)),
         ("windows", "x86_64") => Ok(SupportedSystem::WindowsX86_64(WindowsX86_64)),
         _ => Err(SystemError::UnknownSystem(format!("{} {}", os, arch))),
     }
 }
+
+fn environment_arch() -> String {
+    match std::env::consts::ARCH {
+        // NOTE: std::env::consts::ARCH is the same for big and little endian
+        "powerpc64" if cfg!(target_endian = "little") => "powerpc64le".to_string(),
+        arch => arch.to_string(),
+    }
+}
+
+fn normalize_os(os: &str) -> String {
+    let os = os.to_lowercase();
+    match os.as_str() {
+        "darwin" | "mac" | "osx" => "macos".to_string(),
+        "win" => "windows".to_string(),
+        _ => os,
+    }
+}
+
+fn normalize_arch(arch: &str) -> String {
+    let arch = arch.to_lowercase();
+    match arch.as_str() {
+        "amd64" | "x64" => "x86_64".to_string(),
+        "arm64" => "aarch64".to_string(),
+        "armv6" | "armv7" => "arm".to_string(),
+        "ppc64le" | "ppc64el" => "powerpc64le".to_string(),
+        _ => arch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("linux", "amd64", "linux", "x86_64"; "linux x86_64 alias")]
+    #[test_case("darwin", "arm64", "macos", "aarch64"; "macos arm64")]
+    #[test_case("Windows", "x86_64", "windows", "x86_64"; "case insensitive")]
+    #[test_case("linux", "ppc64le", "linux", "powerpc64le"; "linux powerpc64le")]
+    fn supported(os: &str, arch: &str, expected_os: &str, expected_arch: &str) {
+        let system = from_os_arch(Some(os), Some(arch)).ok().unwrap();
+
+        assert_eq!(expected_os, system.os().as_str());
+        assert_eq!(expected_arch, system.arch().as_str());
+    }
+
+    #[test_case("freebsd", "x86_64"; "unknown os")]
+    #[test_case("windows", "arm64"; "unsupported arch")]
+    fn not_supported(os: &str, arch: &str) {
+        let result = from_os_arch(Some(os), Some(arch));
+
+        assert!(result.is_err());
+    }
+}